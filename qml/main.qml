@@ -111,6 +111,69 @@ ApplicationWindow {
                             harmonicSpinBox.value = 1;
                         }
                     }
+                    
+                    // Dial type label
+                    Text {
+                        text: "Dial:"
+                        color: "white"
+                        font.pixelSize: 14
+                        verticalAlignment: Text.AlignVCenter
+                        height: 40
+                    }
+                    
+                    // Dial type selector (modulus in degrees)
+                    ComboBox {
+                        id: dialTypeBox
+                        model: ["360", "180", "90", "45", "30", "22.5"]
+                        currentIndex: 0
+                        Material.foreground: "white"
+                        
+                        onActivated: {
+                            dialController.set_dial_type(currentText);
+                        }
+                    }
+                    
+                    // Zodiac label
+                    Text {
+                        text: "Zodiac:"
+                        color: "white"
+                        font.pixelSize: 14
+                        verticalAlignment: Text.AlignVCenter
+                        height: 40
+                    }
+                    
+                    // Zodiac selector: tropical or a sidereal ayanamsa
+                    ComboBox {
+                        id: zodiacBox
+                        model: ["tropical", "lahiri", "fagan_bradley", "krishnamurti", "raman"]
+                        currentIndex: 0
+                        Material.foreground: "white"
+                        
+                        onActivated: {
+                            dialController.set_zodiac(currentText);
+                        }
+                    }
+                    
+                    // Coordinate frame label
+                    Text {
+                        text: "Frame:"
+                        color: "white"
+                        font.pixelSize: 14
+                        verticalAlignment: Text.AlignVCenter
+                        height: 40
+                    }
+                    
+                    // Coordinate frame selector: the point the chart is seen from
+                    ComboBox {
+                        id: frameBox
+                        model: ["geocentric", "topocentric", "heliocentric", "barycentric"]
+                        currentIndex: 0
+                        Material.foreground: "white"
+                        
+                        onActivated: {
+                            dialController.set_frame(currentText);
+                        }
+                    }
                 }
             }
         }
@@ -199,6 +262,13 @@ ApplicationWindow {
                                 }
                             }
                             
+                            // Graphic ephemeris toggle
+                            CheckBox {
+                                id: showGraphicEphemeris
+                                text: "Show Graphic Ephemeris"
+                                checked: false
+                            }
+                            
                             // Grid toggle
                             CheckBox {
                                 text: "Show Grid"
@@ -208,6 +278,28 @@ ApplicationWindow {
                                     uranianDial.showGrid = checked;
                                 }
                             }
+                            
+                            // The chart seen from a second point in the inner ring
+                            RowLayout {
+                                Label {
+                                    text: "Second Frame"
+                                }
+                                
+                                ComboBox {
+                                    id: ringFrameBox
+                                    model: ["none", "geocentric", "topocentric", "heliocentric", "barycentric"]
+                                    currentIndex: 0
+                                    Layout.fillWidth: true
+                                    
+                                    onActivated: {
+                                        if (currentIndex === 0) {
+                                            dialController.clear_frame_ring();
+                                        } else {
+                                            dialController.show_frame_ring(currentText);
+                                        }
+                                    }
+                                }
+                            }
                         }
                     }
                     
@@ -276,6 +368,14 @@ ApplicationWindow {
                     showPlanets: displayOptions.showPlanets
                     showMidpoints: displayOptions.showMidpoints
                     
+                    // Dial type, kept separate from the harmonic chart
+                    dialModulus: dialController.dial_modulus
+                    tickCount: dialController.tick_count
+                    tickStep: dialController.tick_step
+                    labelStep: dialController.label_step
+                    signMarkers: dialController.sign_markers
+                    ayanamsa: dialController.ayanamsa
+                    
                     // Interaction properties
                     rotation: 0
                     zoom: 1.0
@@ -286,6 +386,25 @@ ApplicationWindow {
                     }
                 }
                 
+                // Graphic ephemeris of the coming six months below the dial
+                GraphicEphemeris {
+                    id: graphicEphemeris
+                    anchors.left: parent.left
+                    anchors.right: parent.right
+                    anchors.bottom: parent.bottom
+                    height: parent.height * 0.3
+                    visible: showGraphicEphemeris.checked
+                    controller: dialController
+                    modulus: Math.min(dialController.dial_modulus, 90)
+                    
+                    Component.onCompleted: {
+                        var start = new Date();
+                        var end = new Date(start.getTime() + 182 * 86400000);
+                        from = Qt.formatDateTime(start, "yyyy-MM-dd 00:00:00");
+                        to = Qt.formatDateTime(end, "yyyy-MM-dd 00:00:00");
+                    }
+                }
+                
                 // Debug overlay (visible only in debug mode)
                 Rectangle {
                     visible: false  // Set to true for debugging
@@ -6,4 +6,5 @@ pub mod uranian;
 // Re-export commonly used types
 pub use models::planet::{Planet, PlanetPosition, ChartPositions};
 pub use models::zodiac::{ZodiacSign, Element, Modality};
-pub use uranian::dial::{UranianDial, Midpoint};
+pub use uranian::dial::UranianDial;
+pub use uranian::midpoint::Midpoint;
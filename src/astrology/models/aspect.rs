@@ -0,0 +1,502 @@
+//! Aspects between chart points
+//!
+//! An [`AspectCalculator`] holds the aspects to look for and their orbs. Orbs
+//! are set per aspect in degrees and scaled per body by an orb factor, the
+//! effective orb of a pair being the aspect orb times the mean factor of its
+//! two points. Whether an aspect is applying or separating follows from the
+//! daily motion of both points; points without motion (fixed stars, points
+//! placed by hand on the dial) only separate or apply through the other one.
+
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::minor_body::MinorBodyPosition;
+use crate::astrology::models::planet::{ChartPositions, Planet, PlanetPosition};
+use crate::astrology::uranian::dial::UranianDial;
+
+/// Orb below which an aspect is reported as exact (one minute of arc)
+const EXACT_ORB: f64 = 1.0 / 60.0;
+
+/// Aspects by angle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum AspectKind {
+    // Major aspects
+    Conjunction,
+    Opposition,
+    Trine,
+    Square,
+    Sextile,
+
+    // Minor aspects
+    SemiSextile,
+    SemiSquare,
+    Sesquiquadrate,
+    Quincunx,
+    Quintile,
+    BiQuintile,
+
+    // 16th harmonic aspects of the Uranian system (multiples of 22.5°)
+    Sixteenth,
+    ThreeSixteenths,
+    FiveSixteenths,
+    SevenSixteenths,
+}
+
+/// Groups of aspects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum AspectClass {
+    Major,
+    Minor,
+    Uranian,
+}
+
+impl AspectKind {
+    /// Get the exact angle of the aspect in degrees
+    pub fn angle(&self) -> f64 {
+        match self {
+            AspectKind::Conjunction => 0.0,
+            AspectKind::Opposition => 180.0,
+            AspectKind::Trine => 120.0,
+            AspectKind::Square => 90.0,
+            AspectKind::Sextile => 60.0,
+            AspectKind::SemiSextile => 30.0,
+            AspectKind::SemiSquare => 45.0,
+            AspectKind::Sesquiquadrate => 135.0,
+            AspectKind::Quincunx => 150.0,
+            AspectKind::Quintile => 72.0,
+            AspectKind::BiQuintile => 144.0,
+            AspectKind::Sixteenth => 22.5,
+            AspectKind::ThreeSixteenths => 67.5,
+            AspectKind::FiveSixteenths => 112.5,
+            AspectKind::SevenSixteenths => 157.5,
+        }
+    }
+
+    /// Get the lowest harmonic in which the aspect becomes a conjunction
+    pub fn harmonic(&self) -> u32 {
+        match self {
+            AspectKind::Conjunction => 1,
+            AspectKind::Opposition => 2,
+            AspectKind::Trine => 3,
+            AspectKind::Square => 4,
+            AspectKind::Quintile | AspectKind::BiQuintile => 5,
+            AspectKind::Sextile => 6,
+            AspectKind::SemiSquare | AspectKind::Sesquiquadrate => 8,
+            AspectKind::SemiSextile | AspectKind::Quincunx => 12,
+            AspectKind::Sixteenth | AspectKind::ThreeSixteenths |
+            AspectKind::FiveSixteenths | AspectKind::SevenSixteenths => 16,
+        }
+    }
+
+    /// Get the group the aspect belongs to
+    pub fn class(&self) -> AspectClass {
+        match self {
+            AspectKind::Conjunction | AspectKind::Opposition | AspectKind::Trine |
+            AspectKind::Square | AspectKind::Sextile => AspectClass::Major,
+            AspectKind::Sixteenth | AspectKind::ThreeSixteenths |
+            AspectKind::FiveSixteenths | AspectKind::SevenSixteenths => AspectClass::Uranian,
+            _ => AspectClass::Minor,
+        }
+    }
+
+    /// Get the standard symbol for the aspect
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            AspectKind::Conjunction => "☌",
+            AspectKind::Opposition => "☍",
+            AspectKind::Trine => "△",
+            AspectKind::Square => "□",
+            AspectKind::Sextile => "⚹",
+            AspectKind::SemiSextile => "⚺",
+            AspectKind::SemiSquare => "∠",
+            AspectKind::Sesquiquadrate => "⚼",
+            AspectKind::Quincunx => "⚻",
+            AspectKind::Quintile => "Q",
+            AspectKind::BiQuintile => "bQ",
+            AspectKind::Sixteenth => "⅟16",
+            AspectKind::ThreeSixteenths => "3/16",
+            AspectKind::FiveSixteenths => "5/16",
+            AspectKind::SevenSixteenths => "7/16",
+        }
+    }
+
+    /// Get the default orb in degrees
+    pub fn default_orb(&self) -> f64 {
+        match self {
+            AspectKind::Conjunction | AspectKind::Opposition => 8.0,
+            AspectKind::Trine | AspectKind::Square => 7.0,
+            AspectKind::Sextile => 5.0,
+            AspectKind::Quincunx => 3.0,
+            AspectKind::SemiSextile | AspectKind::SemiSquare | AspectKind::Sesquiquadrate |
+            AspectKind::Quintile | AspectKind::BiQuintile => 2.0,
+            AspectKind::Sixteenth | AspectKind::ThreeSixteenths |
+            AspectKind::FiveSixteenths | AspectKind::SevenSixteenths => 1.0,
+        }
+    }
+
+    /// Get the aspects of a harmonic series, e.g. the 8th harmonic gives the
+    /// multiples of 45° and the 16th the multiples of 22.5°
+    pub fn of_harmonic(harmonic: u32) -> Vec<AspectKind> {
+        AspectKind::iter()
+            .filter(|kind| harmonic > 0 && harmonic.is_multiple_of(kind.harmonic()))
+            .collect()
+    }
+}
+
+/// Whether the orb of an aspect is shrinking or growing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum AspectMotion {
+    Applying,
+    Separating,
+    /// Neither point moves relative to the other
+    Stationary,
+}
+
+/// A point taking part in aspects
+#[derive(Debug, Clone, PartialEq)]
+pub struct AspectPoint {
+    pub point: ChartPoint,
+    /// Ecliptic longitude in degrees
+    pub longitude: f64,
+    /// Daily motion in longitude in degrees (0 for fixed points)
+    pub speed: f64,
+}
+
+impl AspectPoint {
+    pub fn new(point: impl Into<ChartPoint>, longitude: f64, speed: f64) -> Self {
+        Self {
+            point: point.into(),
+            longitude,
+            speed,
+        }
+    }
+}
+
+impl From<&PlanetPosition> for AspectPoint {
+    fn from(position: &PlanetPosition) -> Self {
+        Self::new(position.planet, position.longitude, position.speed)
+    }
+}
+
+impl From<&MinorBodyPosition> for AspectPoint {
+    fn from(position: &MinorBodyPosition) -> Self {
+        let point = ChartPoint::MinorBody {
+            number: position.number,
+            name: position.name.clone(),
+        };
+        Self::new(point, position.longitude, position.speed)
+    }
+}
+
+/// An aspect found between two points
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Aspect {
+    pub point1: ChartPoint,
+    pub point2: ChartPoint,
+    pub kind: AspectKind,
+    /// Angular distance between the points in degrees (0-180)
+    pub separation: f64,
+    /// Distance from the exact aspect in degrees
+    pub orb: f64,
+    pub motion: AspectMotion,
+}
+
+impl Aspect {
+    /// Check if the aspect is exact to within a minute of arc
+    pub fn is_exact(&self) -> bool {
+        self.orb < EXACT_ORB
+    }
+
+    /// Check if the aspect is applying
+    pub fn is_applying(&self) -> bool {
+        self.motion == AspectMotion::Applying
+    }
+
+    /// Check if a point takes part in the aspect
+    pub fn involves(&self, point: &ChartPoint) -> bool {
+        self.point1 == *point || self.point2 == *point
+    }
+}
+
+/// Finds aspects with configurable aspects and orbs
+#[derive(Debug, Clone)]
+pub struct AspectCalculator {
+    kinds: Vec<AspectKind>,
+    orbs: HashMap<AspectKind, f64>,
+    orb_factors: HashMap<ChartPoint, f64>,
+}
+
+impl Default for AspectCalculator {
+    fn default() -> Self {
+        Self::major()
+    }
+}
+
+impl AspectCalculator {
+    /// Look for the given aspects with their default orbs
+    pub fn new(kinds: impl IntoIterator<Item = AspectKind>) -> Self {
+        let mut orb_factors = HashMap::new();
+        orb_factors.insert(ChartPoint::Planet(Planet::Sun), 1.25);
+        orb_factors.insert(ChartPoint::Planet(Planet::Moon), 1.25);
+
+        Self {
+            kinds: kinds.into_iter().collect(),
+            orbs: HashMap::new(),
+            orb_factors,
+        }
+    }
+
+    /// Look for the major aspects
+    pub fn major() -> Self {
+        Self::new(AspectKind::iter().filter(|kind| kind.class() == AspectClass::Major))
+    }
+
+    /// Look for the major and minor aspects
+    pub fn major_and_minor() -> Self {
+        Self::new(AspectKind::iter().filter(|kind| kind.class() != AspectClass::Uranian))
+    }
+
+    /// Look for the aspects of a harmonic series (8 or 16 for Uranian work)
+    /// with the same orb for every aspect and body
+    pub fn harmonic(harmonic: u32, orb: f64) -> Self {
+        let mut calculator = Self::new(AspectKind::of_harmonic(harmonic));
+        calculator.orb_factors.clear();
+        for kind in calculator.kinds.clone() {
+            calculator.orbs.insert(kind, orb);
+        }
+        calculator
+    }
+
+    /// Set the orb of an aspect in degrees
+    pub fn with_orb(mut self, kind: AspectKind, orb: f64) -> Self {
+        self.set_orb(kind, orb);
+        self
+    }
+
+    /// Set the orb factor of a point (1.0 by default)
+    pub fn with_orb_factor(mut self, point: impl Into<ChartPoint>, factor: f64) -> Self {
+        self.set_orb_factor(point, factor);
+        self
+    }
+
+    /// Set the orb of an aspect in degrees
+    pub fn set_orb(&mut self, kind: AspectKind, orb: f64) {
+        self.orbs.insert(kind, orb.max(0.0));
+    }
+
+    /// Set the orb factor of a point
+    pub fn set_orb_factor(&mut self, point: impl Into<ChartPoint>, factor: f64) {
+        self.orb_factors.insert(point.into(), factor.max(0.0));
+    }
+
+    /// Get the aspects looked for
+    pub fn kinds(&self) -> &[AspectKind] {
+        &self.kinds
+    }
+
+    /// Get the orb of an aspect in degrees
+    pub fn orb(&self, kind: AspectKind) -> f64 {
+        self.orbs.get(&kind).copied().unwrap_or_else(|| kind.default_orb())
+    }
+
+    /// Get the effective orb of an aspect between two points
+    pub fn orb_between(&self, kind: AspectKind, point1: &ChartPoint, point2: &ChartPoint) -> f64 {
+        let factor = |point| self.orb_factors.get(point).copied().unwrap_or(1.0);
+        self.orb(kind) * (factor(point1) + factor(point2)) / 2.0
+    }
+
+    /// Find the closest aspect between two points, if any is within orb
+    pub fn find_aspect(&self, point1: &AspectPoint, point2: &AspectPoint) -> Option<Aspect> {
+        let difference = (point2.longitude - point1.longitude + 540.0).rem_euclid(360.0) - 180.0;
+        let separation = difference.abs();
+
+        let (kind, orb) = self.kinds.iter()
+            .map(|kind| (*kind, (separation - kind.angle()).abs()))
+            .filter(|(kind, orb)| *orb <= self.orb_between(*kind, &point1.point, &point2.point))
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        // Rate of change of the separation, then of the orb
+        let separation_rate = difference.signum() * (point2.speed - point1.speed);
+        let orb_rate = (separation - kind.angle()).signum() * separation_rate;
+        let motion = if orb_rate < 0.0 {
+            AspectMotion::Applying
+        } else if orb_rate > 0.0 {
+            AspectMotion::Separating
+        } else {
+            AspectMotion::Stationary
+        };
+
+        Some(Aspect {
+            point1: point1.point.clone(),
+            point2: point2.point.clone(),
+            kind,
+            separation,
+            orb,
+            motion,
+        })
+    }
+
+    /// Find the aspects between every pair of points, closest first
+    pub fn find_aspects(&self, points: &[AspectPoint]) -> Vec<Aspect> {
+        let mut aspects = Vec::new();
+
+        for (i, point1) in points.iter().enumerate() {
+            for point2 in &points[i + 1..] {
+                aspects.extend(self.find_aspect(point1, point2));
+            }
+        }
+
+        aspects.sort_by(|a, b| a.orb.total_cmp(&b.orb));
+        aspects
+    }
+
+    /// Find the aspects between the points of two charts, each made of a
+    /// point of the first and a point of the second, closest first
+    pub fn find_cross_aspects(&self, points1: &[AspectPoint], points2: &[AspectPoint]) -> Vec<Aspect> {
+        let mut aspects: Vec<Aspect> = points1.iter()
+            .flat_map(|point1| points2.iter().filter_map(move |point2| self.find_aspect(point1, point2)))
+            .collect();
+
+        aspects.sort_by(|a, b| a.orb.total_cmp(&b.orb));
+        aspects
+    }
+
+    /// Find the aspects between every pair of bodies of a chart
+    pub fn chart_aspects(&self, chart: &ChartPositions) -> Vec<Aspect> {
+        let points: Vec<AspectPoint> = Planet::iter()
+            .filter_map(|planet| chart.get_position(&planet))
+            .map(AspectPoint::from)
+            .collect();
+        self.find_aspects(&points)
+    }
+
+    /// Find the aspects between every pair of points on a dial
+    ///
+    /// The dial only holds longitudes, so every aspect is reported as stationary.
+    pub fn dial_aspects(&self, dial: &UranianDial) -> Vec<Aspect> {
+        let points: Vec<AspectPoint> = dial.points()
+            .into_iter()
+            .map(|(point, angle)| AspectPoint::new(point, angle.degrees(), 0.0))
+            .collect();
+        self.find_aspects(&points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_aspect_kinds() {
+        assert_eq!(AspectKind::of_harmonic(4), vec![AspectKind::Conjunction, AspectKind::Opposition, AspectKind::Square]);
+        assert_eq!(AspectKind::of_harmonic(8).len(), 5);
+        assert!(AspectKind::of_harmonic(16).iter().all(|kind| (kind.angle() / 22.5).fract() == 0.0));
+        assert_eq!(AspectKind::of_harmonic(16).len(), 9);
+        assert_eq!("sesquiquadrate".parse::<AspectKind>().unwrap(), AspectKind::Sesquiquadrate);
+        assert_eq!(AspectKind::Quincunx.class(), AspectClass::Minor);
+    }
+
+    #[test]
+    fn test_find_aspect() {
+        let calculator = AspectCalculator::major();
+        let mars = AspectPoint::new(Planet::Mars, 5.0, 0.7);
+        let saturn = AspectPoint::new(Planet::Saturn, 127.5, 0.05);
+
+        // Mars moves towards the exact trine at 127.5 - 120
+        let trine = calculator.find_aspect(&mars, &saturn).unwrap();
+        assert_eq!(trine.kind, AspectKind::Trine);
+        assert!(approx_eq!(f64, trine.orb, 2.5, epsilon = 1e-9));
+        assert!(approx_eq!(f64, trine.separation, 122.5, epsilon = 1e-9));
+        assert!(trine.is_applying());
+
+        // Venus catches up with Mars, closing a wide trine and then leaving it
+        let mars = AspectPoint::new(Planet::Mars, 350.0, 0.7);
+        let venus = AspectPoint::new(Planet::Venus, 229.0, 1.2);
+        let trine = calculator.find_aspect(&mars, &venus).unwrap();
+        assert!(approx_eq!(f64, trine.orb, 1.0, epsilon = 1e-9));
+        assert_eq!(trine.motion, AspectMotion::Applying);
+
+        let venus = AspectPoint::new(Planet::Venus, 231.0, 1.2);
+        assert_eq!(calculator.find_aspect(&mars, &venus).unwrap().motion, AspectMotion::Separating);
+
+        // Out of orb
+        let saturn = AspectPoint::new(Planet::Saturn, 140.0, 0.05);
+        assert!(calculator.find_aspect(&mars, &saturn).is_none());
+    }
+
+    #[test]
+    fn test_orbs() {
+        let sun = AspectPoint::new(Planet::Sun, 0.0, 1.0);
+        let mars = AspectPoint::new(Planet::Mars, 97.5, 0.5);
+        let pluto = AspectPoint::new(Planet::Pluto, 96.0, 0.01);
+
+        // The Sun widens the square orb to 7 * (1.25 + 1) / 2
+        let calculator = AspectCalculator::major();
+        assert!(approx_eq!(f64, calculator.orb_between(AspectKind::Square, &sun.point, &mars.point), 7.875));
+        assert!(calculator.find_aspect(&sun, &mars).is_some());
+
+        let calculator = calculator.with_orb(AspectKind::Square, 4.0).with_orb_factor(Planet::Pluto, 2.0);
+        assert!(calculator.find_aspect(&sun, &mars).is_none());
+        assert!(calculator.find_aspect(&sun, &pluto).is_some());
+    }
+
+    #[test]
+    fn test_uranian_harmonics() {
+        let calculator = AspectCalculator::harmonic(16, 1.0);
+        let sun = AspectPoint::new(Planet::Sun, 100.0, 1.0);
+        let points = [
+            sun.clone(),
+            AspectPoint::new(Planet::Kronos, 167.6, 0.0),
+            AspectPoint::new(Planet::Moon, 190.3, 13.0),
+            AspectPoint::new(ChartPoint::Star("Regulus".to_string()), 150.0, 0.0),
+        ];
+
+        let aspects = calculator.find_aspects(&points);
+        assert_eq!(aspects[0].kind, AspectKind::ThreeSixteenths);
+        assert!(aspects[0].involves(&ChartPoint::Planet(Planet::Kronos)));
+        assert_eq!(aspects[1].kind, AspectKind::Sixteenth);
+        assert_eq!(aspects[2].kind, AspectKind::Square);
+        assert_eq!(aspects.len(), 3);
+        assert!(aspects.windows(2).all(|pair| pair[0].orb <= pair[1].orb));
+
+        // No factors in harmonic mode: the Sun and the Moon get the same 1° orb
+        let moon = AspectPoint::new(Planet::Moon, 191.5, 13.0);
+        assert!(calculator.find_aspect(&sun, &moon).is_none());
+    }
+
+    #[test]
+    fn test_cross_aspects() {
+        // Only pairs across the charts: the conjunction of the first Sun and
+        // Moon is left out
+        let first = [AspectPoint::new(Planet::Sun, 10.0, 1.0), AspectPoint::new(Planet::Moon, 14.0, 13.0)];
+        let second = [AspectPoint::new(Planet::Sun, 12.0, 1.0), AspectPoint::new(Planet::Mars, 200.0, 0.5)];
+
+        let aspects = AspectCalculator::major().find_cross_aspects(&first, &second);
+        assert_eq!(aspects.len(), 3);
+        assert!(aspects.iter().all(|aspect| aspect.point2 != ChartPoint::Planet(Planet::Moon)));
+        assert_eq!(aspects[0].kind, AspectKind::Conjunction);
+        assert!(approx_eq!(f64, aspects[0].orb, 2.0, epsilon = 1e-9));
+        assert_eq!(aspects[2].point2, ChartPoint::Planet(Planet::Mars));
+        assert_eq!(aspects[2].kind, AspectKind::Opposition);
+    }
+
+    #[test]
+    fn test_dial_aspects() {
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 10.0);
+        dial.update_planet_position(Planet::Saturn, 190.5);
+
+        let aspects = AspectCalculator::major().dial_aspects(&dial);
+        assert_eq!(aspects.len(), 1);
+        assert_eq!(aspects[0].kind, AspectKind::Opposition);
+        assert_eq!(aspects[0].motion, AspectMotion::Stationary);
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+use serde::{Serialize, Deserialize};
+
+use crate::astrology::models::planet::Planet;
+
+/// A point that can be placed in a chart: a body from [`Planet`], a fixed star
+/// from the star catalogue identified by name, a minor planet added at
+/// runtime identified by its MPC number, the Aries point at 0° Aries, or the
+/// antiscion or contra-antiscion of another point
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChartPoint {
+    Planet(Planet),
+    Star(String),
+    MinorBody { number: u32, name: String },
+    AriesPoint,
+    Antiscion(Box<ChartPoint>),
+    ContraAntiscion(Box<ChartPoint>),
+}
+
+impl ChartPoint {
+    /// Get the planet this point refers to, if any
+    pub fn as_planet(&self) -> Option<Planet> {
+        match self {
+            ChartPoint::Planet(planet) => Some(*planet),
+            _ => None,
+        }
+    }
+
+    /// Check if this point is a fixed star
+    pub fn is_star(&self) -> bool {
+        matches!(self, ChartPoint::Star(_))
+    }
+
+    /// Check if this point is a minor planet added by number
+    pub fn is_minor_body(&self) -> bool {
+        matches!(self, ChartPoint::MinorBody { .. })
+    }
+
+    /// Check if this point is the antiscion or contra-antiscion of another point
+    pub fn is_reflection(&self) -> bool {
+        matches!(self, ChartPoint::Antiscion(_) | ChartPoint::ContraAntiscion(_))
+    }
+
+    /// Get the point an antiscion or contra-antiscion reflects, or the point
+    /// itself for any other point
+    pub fn source(&self) -> &ChartPoint {
+        match self {
+            ChartPoint::Antiscion(point) | ChartPoint::ContraAntiscion(point) => point.source(),
+            _ => self,
+        }
+    }
+
+    /// Check if this is one of the personal points of the Hamburg School:
+    /// the Aries point, MC, Ascendant or lunar node
+    pub fn is_personal_point(&self) -> bool {
+        matches!(
+            self,
+            ChartPoint::AriesPoint |
+            ChartPoint::Planet(Planet::MC | Planet::Ascendant | Planet::TrueNode | Planet::MeanNode)
+        )
+    }
+
+    /// Get the ecliptic longitude of points fixed in the zodiac
+    pub fn fixed_longitude(&self) -> Option<f64> {
+        match self {
+            ChartPoint::AriesPoint => Some(0.0),
+            _ => None,
+        }
+    }
+
+    /// Get the display symbol of the point
+    pub fn symbol(&self) -> &str {
+        match self {
+            ChartPoint::Planet(planet) => planet.symbol(),
+            ChartPoint::Star(_) => "★",
+            ChartPoint::MinorBody { .. } => "✧",
+            ChartPoint::AriesPoint => "♈",
+            ChartPoint::Antiscion(point) | ChartPoint::ContraAntiscion(point) => point.symbol(),
+        }
+    }
+
+    /// Get the display color of the point (in hex format, e.g., "#RRGGBB")
+    pub fn color(&self) -> &'static str {
+        match self {
+            ChartPoint::Planet(planet) => planet.color(),
+            ChartPoint::Star(_) => "#FFD700", // Gold
+            ChartPoint::MinorBody { .. } => "#B0B0B0", // Silver
+            ChartPoint::AriesPoint => "#FF4500", // Red-Orange
+            ChartPoint::Antiscion(_) | ChartPoint::ContraAntiscion(_) => "#9370DB", // Medium Purple
+        }
+    }
+}
+
+impl From<Planet> for ChartPoint {
+    fn from(planet: Planet) -> Self {
+        ChartPoint::Planet(planet)
+    }
+}
+
+impl PartialEq<Planet> for ChartPoint {
+    fn eq(&self, other: &Planet) -> bool {
+        self.as_planet() == Some(*other)
+    }
+}
+
+impl fmt::Display for ChartPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartPoint::Planet(planet) => write!(f, "{}", planet),
+            ChartPoint::Star(name) | ChartPoint::MinorBody { name, .. } => write!(f, "{}", name),
+            ChartPoint::AriesPoint => write!(f, "aries_point"),
+            ChartPoint::Antiscion(point) => write!(f, "{}_antiscion", point),
+            ChartPoint::ContraAntiscion(point) => write!(f, "{}_contra_antiscion", point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_point_identity() {
+        let sun = ChartPoint::from(Planet::Sun);
+        let regulus = ChartPoint::Star("Regulus".to_string());
+
+        assert!(sun == Planet::Sun);
+        assert!(regulus != Planet::Sun);
+        assert_eq!(sun.as_planet(), Some(Planet::Sun));
+        assert!(regulus.is_star());
+        assert_eq!(regulus.to_string(), "Regulus");
+        assert_eq!(regulus.symbol(), "★");
+
+        let eros = ChartPoint::MinorBody { number: 433, name: "Eros".to_string() };
+        assert!(eros.is_minor_body());
+        assert!(eros != Planet::Sun);
+        assert_eq!(eros.to_string(), "Eros");
+
+        assert!(ChartPoint::AriesPoint.is_personal_point());
+        assert!(ChartPoint::from(Planet::MC).is_personal_point());
+        assert!(!sun.is_personal_point());
+        assert_eq!(ChartPoint::AriesPoint.fixed_longitude(), Some(0.0));
+
+        let antiscion = ChartPoint::Antiscion(Box::new(sun.clone()));
+        assert!(antiscion.is_reflection());
+        assert!(antiscion != Planet::Sun);
+        assert_eq!(*antiscion.source(), sun);
+        assert_eq!(antiscion.to_string(), "sun_antiscion");
+    }
+}
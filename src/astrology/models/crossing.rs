@@ -0,0 +1,178 @@
+//! Exact moments a body crosses a longitude
+//!
+//! Shared by the transit and return searches. The body is sampled at steps
+//! short enough that it cannot move across half the dial in between; stations
+//! found from the sign of the speed split the steps so that every retrograde
+//! pass is bracketed, and each crossing is refined with Newton's method on the
+//! ephemeris speed.
+
+use chrono::{DateTime, Utc};
+
+use crate::astrology::models::ephemeris::{at, Ephemeris, EphemerisError};
+use crate::astrology::models::planet::Planet;
+
+/// Longest sampling step in days
+const MAX_STEP: f64 = 1.0;
+
+/// Precision of the exact times in days (about a second)
+const TIME_PRECISION: f64 = 1e-5;
+
+/// Longitude and daily motion of a body at a moment, in days from the start
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub days: f64,
+    pub longitude: f64,
+    pub speed: f64,
+}
+
+/// Follows a body from a moment on, on a dial of a given modulus
+#[derive(Debug, Clone)]
+pub struct CrossingFinder {
+    /// Ephemeris supplying the backend and observer location
+    ephemeris: Ephemeris,
+    body: Planet,
+    from: DateTime<Utc>,
+    /// Span of the dial in degrees
+    modulus: f64,
+}
+
+impl CrossingFinder {
+    /// Follow a body from `from` with the backend and location of an
+    /// ephemeris, on a dial of the given modulus (360 for conjunctions only,
+    /// 90 to include squares and oppositions, ...)
+    pub fn new(ephemeris: &Ephemeris, body: Planet, from: DateTime<Utc>, modulus: f64) -> Self {
+        Self {
+            ephemeris: ephemeris.clone(),
+            body,
+            from,
+            modulus,
+        }
+    }
+
+    /// Find every crossing of a target longitude, which may move with time,
+    /// within `span` days after the start, in order of time
+    pub fn find(&mut self, span: f64, target: impl Fn(DateTime<Utc>) -> f64) -> Result<Vec<Sample>, EphemerisError> {
+        let mut crossings = Vec::new();
+        self.scan::<EphemerisError>(span, |finder, start, end| {
+            crossings.extend(finder.crossing(&target, start, end)?);
+            Ok(())
+        })?;
+
+        Ok(crossings)
+    }
+
+    /// Sample the body over `span` days after the start, passing each stretch
+    /// of steady motion between two samples to `visit`
+    pub fn scan<E>(&mut self, span: f64, mut visit: impl FnMut(&mut Self, Sample, Sample) -> Result<(), E>) -> Result<(), E>
+    where
+        E: From<EphemerisError>,
+    {
+        let mut previous = self.sample(0.0)?;
+
+        while previous.days < span {
+            // Never let the body cross half the dial between two samples
+            let step = (self.modulus / 4.0 / previous.speed.abs().max(1e-9)).min(MAX_STEP);
+            let next = self.sample((previous.days + step).min(span))?;
+
+            // A station splits the step into two stretches of steady motion
+            if previous.speed.signum() != next.speed.signum() {
+                let station = self.find_station(previous, next)?;
+                visit(self, previous, station)?;
+                visit(self, station, next)?;
+            } else {
+                visit(self, previous, next)?;
+            }
+
+            previous = next;
+        }
+
+        Ok(())
+    }
+
+    /// Find the crossing of a target longitude, which may move with time,
+    /// within a stretch of steady motion between two samples
+    pub fn crossing(
+        &mut self,
+        target: impl Fn(DateTime<Utc>) -> f64,
+        start: Sample,
+        end: Sample,
+    ) -> Result<Option<Sample>, EphemerisError> {
+        let offset_start = self.offset(&start, &target);
+        let offset_end = self.offset(&end, &target);
+        let crosses = offset_start <= 0.0 && offset_end > 0.0 || offset_start >= 0.0 && offset_end < 0.0;
+        // An offset jumping across half the dial is no crossing
+        if !crosses || (offset_end - offset_start).abs() > self.modulus / 2.0 {
+            return Ok(None);
+        }
+
+        self.refine(&target, start, end).map(Some)
+    }
+
+    /// Position of the body a number of days after the start
+    fn sample(&mut self, days: f64) -> Result<Sample, EphemerisError> {
+        self.ephemeris.update_time(at(self.from, days))?;
+        let position = self.ephemeris.get_planet_position(&self.body)?;
+
+        Ok(Sample {
+            days,
+            longitude: position.longitude,
+            speed: position.speed,
+        })
+    }
+
+    /// Signed distance from the target to a sample on the dial, in
+    /// [-modulus/2, modulus/2)
+    fn offset(&self, sample: &Sample, target: &impl Fn(DateTime<Utc>) -> f64) -> f64 {
+        let target = target(at(self.from, sample.days));
+        (sample.longitude - target + self.modulus / 2.0).rem_euclid(self.modulus) - self.modulus / 2.0
+    }
+
+    /// Find the station between two samples of opposite motion by bisection
+    fn find_station(&mut self, mut before: Sample, mut after: Sample) -> Result<Sample, EphemerisError> {
+        while after.days - before.days > TIME_PRECISION {
+            let middle = self.sample((before.days + after.days) / 2.0)?;
+            if middle.speed.signum() == before.speed.signum() {
+                before = middle;
+            } else {
+                after = middle;
+            }
+        }
+
+        Ok(before)
+    }
+
+    /// Refine a crossing bracketed by two samples, with Newton's method on
+    /// the speed and bisection whenever a step leaves the bracket
+    fn refine(
+        &mut self,
+        target: &impl Fn(DateTime<Utc>) -> f64,
+        mut start: Sample,
+        mut end: Sample,
+    ) -> Result<Sample, EphemerisError> {
+        let offset_start = self.offset(&start, target);
+        let offset_end = self.offset(&end, target);
+        let mut days = start.days + (end.days - start.days) * offset_start / (offset_start - offset_end);
+
+        for _ in 0..50 {
+            let current = self.sample(days)?;
+            let offset = self.offset(&current, target);
+            if end.days - start.days < TIME_PRECISION || offset.abs() < 1e-7 {
+                return Ok(current);
+            }
+
+            // Keep the crossing between start and end
+            if offset.signum() == offset_start.signum() {
+                start = current;
+            } else {
+                end = current;
+            }
+
+            days = current.days - offset / current.speed;
+            if !(start.days..=end.days).contains(&days) || !days.is_finite() {
+                days = (start.days + end.days) / 2.0;
+            }
+        }
+
+        self.sample(days)
+    }
+}
@@ -0,0 +1,266 @@
+//! Parallels and contra-parallels of declination
+//!
+//! Two points are parallel when they share the same declination on the same
+//! side of the celestial equator, and contra-parallel when their declinations
+//! are equal but of opposite sign. A [`DeclinationCalculator`] finds both with
+//! a single orb in degrees of declination, conventionally much tighter than
+//! the orbs of longitude aspects.
+
+use serde::{Serialize, Deserialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::astrology::models::aspect::AspectMotion;
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::planet::{ChartPositions, Planet, PlanetPosition};
+
+/// Orb below which a parallel is reported as exact (one minute of arc)
+const EXACT_ORB: f64 = 1.0 / 60.0;
+
+/// Default orb of parallels and contra-parallels in degrees
+const DEFAULT_ORB: f64 = 1.0;
+
+/// Aspects of declination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum DeclinationKind {
+    /// Same declination on the same side of the equator
+    Parallel,
+    /// Same declination on opposite sides of the equator
+    ContraParallel,
+}
+
+impl DeclinationKind {
+    /// Get the standard symbol for the aspect
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            DeclinationKind::Parallel => "∥",
+            DeclinationKind::ContraParallel => "⋕",
+        }
+    }
+}
+
+/// A point taking part in aspects of declination
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclinationPoint {
+    pub point: ChartPoint,
+    /// Declination in degrees, positive north of the equator
+    pub declination: f64,
+    /// Daily motion in declination in degrees (0 for fixed points)
+    pub speed: f64,
+}
+
+impl DeclinationPoint {
+    pub fn new(point: impl Into<ChartPoint>, declination: f64, speed: f64) -> Self {
+        Self {
+            point: point.into(),
+            declination,
+            speed,
+        }
+    }
+}
+
+impl From<&PlanetPosition> for DeclinationPoint {
+    fn from(position: &PlanetPosition) -> Self {
+        Self::new(position.planet, position.declination, position.speed_declination)
+    }
+}
+
+/// A parallel or contra-parallel found between two points
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeclinationAspect {
+    pub point1: ChartPoint,
+    pub point2: ChartPoint,
+    pub kind: DeclinationKind,
+    /// Distance from the exact aspect in degrees of declination
+    pub orb: f64,
+    pub motion: AspectMotion,
+}
+
+impl DeclinationAspect {
+    /// Check if the aspect is exact to within a minute of arc
+    pub fn is_exact(&self) -> bool {
+        self.orb < EXACT_ORB
+    }
+
+    /// Check if the aspect is applying
+    pub fn is_applying(&self) -> bool {
+        self.motion == AspectMotion::Applying
+    }
+
+    /// Check if a point takes part in the aspect
+    pub fn involves(&self, point: &ChartPoint) -> bool {
+        self.point1 == *point || self.point2 == *point
+    }
+}
+
+/// Finds parallels and contra-parallels within an orb
+#[derive(Debug, Clone)]
+pub struct DeclinationCalculator {
+    kinds: Vec<DeclinationKind>,
+    orb: f64,
+}
+
+impl Default for DeclinationCalculator {
+    fn default() -> Self {
+        Self::new(DeclinationKind::iter())
+    }
+}
+
+impl DeclinationCalculator {
+    /// Look for the given aspects with the default orb of 1°
+    pub fn new(kinds: impl IntoIterator<Item = DeclinationKind>) -> Self {
+        Self {
+            kinds: kinds.into_iter().collect(),
+            orb: DEFAULT_ORB,
+        }
+    }
+
+    /// Set the orb in degrees of declination
+    pub fn with_orb(mut self, orb: f64) -> Self {
+        self.set_orb(orb);
+        self
+    }
+
+    /// Set the orb in degrees of declination
+    pub fn set_orb(&mut self, orb: f64) {
+        self.orb = orb.max(0.0);
+    }
+
+    /// Get the aspects looked for
+    pub fn kinds(&self) -> &[DeclinationKind] {
+        &self.kinds
+    }
+
+    /// Get the orb in degrees of declination
+    pub fn orb(&self) -> f64 {
+        self.orb
+    }
+
+    /// Find the closest aspect of declination between two points, if any is
+    /// within orb
+    pub fn find_aspect(&self, point1: &DeclinationPoint, point2: &DeclinationPoint) -> Option<DeclinationAspect> {
+        // A parallel closes the difference of the declinations, a
+        // contra-parallel their sum
+        let distance = |kind| match kind {
+            DeclinationKind::Parallel => (point1.declination - point2.declination, point1.speed - point2.speed),
+            DeclinationKind::ContraParallel => (point1.declination + point2.declination, point1.speed + point2.speed),
+        };
+
+        let (kind, (difference, rate)) = self.kinds.iter()
+            .map(|kind| (*kind, distance(*kind)))
+            .filter(|(_, (difference, _))| difference.abs() <= self.orb)
+            .min_by(|a, b| a.1.0.abs().total_cmp(&b.1.0.abs()))?;
+
+        let orb_rate = difference.signum() * rate;
+        let motion = if orb_rate < 0.0 {
+            AspectMotion::Applying
+        } else if orb_rate > 0.0 {
+            AspectMotion::Separating
+        } else {
+            AspectMotion::Stationary
+        };
+
+        Some(DeclinationAspect {
+            point1: point1.point.clone(),
+            point2: point2.point.clone(),
+            kind,
+            orb: difference.abs(),
+            motion,
+        })
+    }
+
+    /// Find the aspects of declination between every pair of points, closest
+    /// first
+    pub fn find_aspects(&self, points: &[DeclinationPoint]) -> Vec<DeclinationAspect> {
+        let mut aspects = Vec::new();
+
+        for (i, point1) in points.iter().enumerate() {
+            for point2 in &points[i + 1..] {
+                aspects.extend(self.find_aspect(point1, point2));
+            }
+        }
+
+        aspects.sort_by(|a, b| a.orb.total_cmp(&b.orb));
+        aspects
+    }
+
+    /// Find the aspects of declination between the points of two charts, each
+    /// made of a point of the first and a point of the second, closest first
+    pub fn find_cross_aspects(&self, points1: &[DeclinationPoint], points2: &[DeclinationPoint]) -> Vec<DeclinationAspect> {
+        let mut aspects: Vec<DeclinationAspect> = points1.iter()
+            .flat_map(|point1| points2.iter().filter_map(move |point2| self.find_aspect(point1, point2)))
+            .collect();
+
+        aspects.sort_by(|a, b| a.orb.total_cmp(&b.orb));
+        aspects
+    }
+
+    /// Find the aspects of declination between every pair of bodies of a chart
+    pub fn chart_aspects(&self, chart: &ChartPositions) -> Vec<DeclinationAspect> {
+        let points: Vec<DeclinationPoint> = Planet::iter()
+            .filter_map(|planet| chart.get_position(&planet))
+            .map(DeclinationPoint::from)
+            .collect();
+        self.find_aspects(&points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_parallels() {
+        let calculator = DeclinationCalculator::default();
+
+        // The Moon climbs towards the declination of Venus
+        let venus = DeclinationPoint::new(Planet::Venus, 18.2, 0.1);
+        let moon = DeclinationPoint::new(Planet::Moon, 17.6, 4.0);
+        let parallel = calculator.find_aspect(&venus, &moon).unwrap();
+        assert_eq!(parallel.kind, DeclinationKind::Parallel);
+        assert!(approx_eq!(f64, parallel.orb, 0.6, epsilon = 1e-9));
+        assert!(parallel.is_applying());
+
+        // Mars south of the equator mirrors Venus
+        let mars = DeclinationPoint::new(Planet::Mars, -18.0, 0.2);
+        let contra = calculator.find_aspect(&venus, &mars).unwrap();
+        assert_eq!(contra.kind, DeclinationKind::ContraParallel);
+        assert!(approx_eq!(f64, contra.orb, 0.2, epsilon = 1e-9));
+        assert_eq!(contra.motion, AspectMotion::Separating);
+
+        // Out of orb, and within a tighter orb only when exact
+        let saturn = DeclinationPoint::new(Planet::Saturn, 5.0, 0.0);
+        assert!(calculator.find_aspect(&venus, &saturn).is_none());
+        let calculator = calculator.with_orb(0.5);
+        assert!(calculator.find_aspect(&venus, &moon).is_none());
+
+        let sun = DeclinationPoint::new(Planet::Sun, 18.21, 0.0);
+        assert!(calculator.find_aspect(&venus, &sun).unwrap().is_exact());
+    }
+
+    #[test]
+    fn test_find_declination_aspects() {
+        let points = [
+            DeclinationPoint::new(Planet::Sun, 23.0, 0.0),
+            DeclinationPoint::new(Planet::Moon, -22.5, -1.0),
+            DeclinationPoint::new(Planet::Mercury, 22.8, 0.3),
+            DeclinationPoint::new(Planet::Jupiter, 2.0, 0.0),
+        ];
+
+        let aspects = DeclinationCalculator::default().find_aspects(&points);
+        assert_eq!(aspects.len(), 3);
+        assert!(aspects.windows(2).all(|pair| pair[0].orb <= pair[1].orb));
+        assert_eq!(aspects[0].kind, DeclinationKind::Parallel);
+        assert!(aspects[0].involves(&ChartPoint::Planet(Planet::Mercury)));
+        assert!(aspects[1..].iter().all(|aspect| aspect.kind == DeclinationKind::ContraParallel));
+
+        let only_parallels = DeclinationCalculator::new([DeclinationKind::Parallel]);
+        assert_eq!(only_parallels.find_aspects(&points).len(), 1);
+
+        let cross = DeclinationCalculator::default().find_cross_aspects(&points[..1], &points[1..]);
+        assert_eq!(cross.len(), 2);
+        assert!(cross.iter().all(|aspect| aspect.point1 == ChartPoint::Planet(Planet::Sun)));
+    }
+}
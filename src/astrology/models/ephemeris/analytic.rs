@@ -0,0 +1,334 @@
+//! Pure-Rust analytic ephemeris
+//!
+//! Works without any data files: the Sun and the planets from Mercury to
+//! Neptune use the truncated VSOP87 theory, Pluto the Meeus periodic terms and
+//! the Moon, the lunar nodes and Lilith the Meeus lunar theory, the Hamburg
+//! points their Witte/Sieggrün elements and minor planets Keplerian orbits
+//! from their osculating elements. Positions are
+//! apparent (light-time, aberration and nutation applied). Between 1950 and
+//! 2050 they agree with the Swiss Ephemeris to within 5" for the Sun and the
+//! planets, 15" for the Moon and about a minute of arc for the true node;
+//! further out the extrapolated ΔT dominates the error. Barycentric positions
+//! place the Sun by the masses of the planets, to a few thousandths of its
+//! distance from the barycentre.
+
+use std::f64::consts::TAU;
+
+use super::vsop87::{self, Heliocentric, VsopBody};
+use super::kepler::OrbitalElements;
+use super::{delta_t, hamburg, moon, nutation, pluto, EclipticPosition, EphemerisBackend, EphemerisError, J2000};
+use crate::astrology::models::minor_body::MinorBody;
+use crate::astrology::models::planet::Planet;
+
+/// Light time for one AU in days
+const LIGHT_TIME_PER_AU: f64 = 0.005_775_518_3;
+
+/// Astronomical unit in km
+const AU_KM: f64 = 149_597_870.7;
+
+/// Half-width of the interval used to differentiate positions, in days
+const SPEED_STEP: f64 = 0.01;
+
+/// Ratio of the mass of the Sun to that of each planet (IAU 1976, the Earth
+/// together with the Moon)
+const SUN_PLANET_MASS_RATIOS: [(VsopBody, f64); 8] = [
+    (VsopBody::Mercury, 6_023_600.0),
+    (VsopBody::Venus, 408_523.71),
+    (VsopBody::Earth, 328_900.56),
+    (VsopBody::Mars, 3_098_708.0),
+    (VsopBody::Jupiter, 1_047.348_6),
+    (VsopBody::Saturn, 3_497.898),
+    (VsopBody::Uranus, 22_902.98),
+    (VsopBody::Neptune, 19_412.24),
+];
+
+/// Ephemeris computed from analytic theories, without external data
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AnalyticEphemeris;
+
+impl AnalyticEphemeris {
+    /// Create the backend
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Apparent geocentric (longitude, latitude, distance in AU) of a body
+    fn apparent(&self, planet: Planet, jd_tt: f64) -> Option<(f64, f64, f64)> {
+        let (delta_psi, _) = nutation::nutation(jd_tt);
+
+        let (longitude, latitude, distance) = match planet {
+            Planet::Moon => {
+                let moon = moon::moon(jd_tt);
+                (moon.longitude, moon.latitude, moon.distance_km / AU_KM)
+            }
+            Planet::MeanNode => (moon::mean_node(jd_tt), 0.0, moon::MEAN_DISTANCE_KM / AU_KM),
+            Planet::TrueNode => (moon::true_node(jd_tt), 0.0, moon::MEAN_DISTANCE_KM / AU_KM),
+            Planet::BlackMoonLilith => {
+                let apogee = moon::mean_apogee(jd_tt);
+                (apogee.longitude, apogee.latitude, apogee.distance_km / AU_KM)
+            }
+            _ => {
+                let heliocentric = heliocentric_body(planet)?;
+                let (longitude, latitude, distance) = geocentric(heliocentric, jd_tt);
+                let (longitude, latitude) = fk5_correction(longitude, latitude, jd_tt);
+                (longitude, latitude, distance)
+            }
+        };
+
+        Some(((longitude + delta_psi).rem_euclid(360.0), latitude, distance))
+    }
+
+    /// Heliocentric or barycentric (longitude, latitude, distance in AU) of a body
+    fn centered(&self, planet: Planet, jd_tt: f64, barycentric: bool) -> Option<(f64, f64, f64)> {
+        if planet == Planet::Sun && !barycentric {
+            return None;
+        }
+
+        let heliocentric: HeliocentricFn = match planet {
+            Planet::Moon => moon_heliocentric,
+            _ => heliocentric_body(planet)?,
+        };
+
+        Some(centered(|jd| rectangular(&heliocentric(jd)), jd_tt, barycentric))
+    }
+}
+
+impl EphemerisBackend for AnalyticEphemeris {
+    fn name(&self) -> &'static str {
+        "Analytic (VSOP87/ELP)"
+    }
+
+    fn calculate(&self, planet: Planet, jd_ut: f64) -> Result<EclipticPosition, EphemerisError> {
+        let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+
+        with_speed(jd_tt, |jd| self.apparent(planet, jd)).ok_or(EphemerisError::UnsupportedBody(planet))
+    }
+
+    fn calculate_minor_body(&self, body: &MinorBody, jd_ut: f64) -> Result<EclipticPosition, EphemerisError> {
+        let elements = body.elements.as_ref().ok_or_else(|| EphemerisError::MinorBody {
+            body: body.to_string(),
+            message: "no orbital elements available".to_string(),
+        })?;
+        let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+
+        Ok(with_speed(jd_tt, |jd| Some(apparent_orbit(elements, jd))).expect("Keplerian orbits are defined at any date"))
+    }
+
+    fn calculate_heliocentric(&self, planet: Planet, jd_ut: f64, barycentric: bool) -> Result<EclipticPosition, EphemerisError> {
+        let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+
+        with_speed(jd_tt, |jd| self.centered(planet, jd, barycentric)).ok_or(EphemerisError::UnsupportedBody(planet))
+    }
+
+    fn calculate_minor_body_heliocentric(&self, body: &MinorBody, jd_ut: f64, barycentric: bool) -> Result<EclipticPosition, EphemerisError> {
+        let elements = body.elements.as_ref().ok_or_else(|| EphemerisError::MinorBody {
+            body: body.to_string(),
+            message: "no orbital elements available".to_string(),
+        })?;
+        let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+        let orbit = |jd| Some(centered(|jd| rectangular(&elements.heliocentric(jd)), jd, barycentric));
+
+        Ok(with_speed(jd_tt, orbit).expect("Keplerian orbits are defined at any date"))
+    }
+}
+
+/// Evaluate an apparent (longitude, latitude, distance) at a Julian day (TT),
+/// with the daily motion by centered difference
+fn with_speed(jd_tt: f64, apparent: impl Fn(f64) -> Option<(f64, f64, f64)>) -> Option<EclipticPosition> {
+    let (longitude, latitude, distance_au) = apparent(jd_tt)?;
+    let (before_lon, before_lat, _) = apparent(jd_tt - SPEED_STEP)?;
+    let (after_lon, after_lat, _) = apparent(jd_tt + SPEED_STEP)?;
+
+    let speed = (after_lon - before_lon + 540.0).rem_euclid(360.0) - 180.0;
+
+    Some(EclipticPosition {
+        longitude,
+        latitude,
+        distance_au,
+        speed: speed / (2.0 * SPEED_STEP),
+        speed_latitude: (after_lat - before_lat) / (2.0 * SPEED_STEP),
+    })
+}
+
+/// Apparent geocentric (longitude, latitude, distance in AU) of a body on a Keplerian orbit
+fn apparent_orbit(elements: &OrbitalElements, jd_tt: f64) -> (f64, f64, f64) {
+    let (delta_psi, _) = nutation::nutation(jd_tt);
+    let (longitude, latitude, distance) = geocentric(|jd| elements.heliocentric(jd), jd_tt);
+
+    ((longitude + delta_psi).rem_euclid(360.0), latitude, distance)
+}
+
+/// Function giving the heliocentric position of a body for a Julian day (TT)
+type HeliocentricFn = fn(f64) -> Heliocentric;
+
+fn heliocentric_body(planet: Planet) -> Option<HeliocentricFn> {
+    let body: HeliocentricFn = match planet {
+        Planet::Sun => |_| Heliocentric { longitude: 0.0, latitude: 0.0, radius: 0.0 },
+        Planet::Mercury => |jd| vsop87::heliocentric(VsopBody::Mercury, millennia(jd)),
+        Planet::Venus => |jd| vsop87::heliocentric(VsopBody::Venus, millennia(jd)),
+        Planet::Mars => |jd| vsop87::heliocentric(VsopBody::Mars, millennia(jd)),
+        Planet::Jupiter => |jd| vsop87::heliocentric(VsopBody::Jupiter, millennia(jd)),
+        Planet::Saturn => |jd| vsop87::heliocentric(VsopBody::Saturn, millennia(jd)),
+        Planet::Uranus => |jd| vsop87::heliocentric(VsopBody::Uranus, millennia(jd)),
+        Planet::Neptune => |jd| vsop87::heliocentric(VsopBody::Neptune, millennia(jd)),
+        Planet::Pluto => pluto::heliocentric,
+        Planet::Cupido => |jd| hamburg_point(Planet::Cupido, jd),
+        Planet::Hades => |jd| hamburg_point(Planet::Hades, jd),
+        Planet::Zeus => |jd| hamburg_point(Planet::Zeus, jd),
+        Planet::Kronos => |jd| hamburg_point(Planet::Kronos, jd),
+        Planet::Apollon => |jd| hamburg_point(Planet::Apollon, jd),
+        Planet::Admetos => |jd| hamburg_point(Planet::Admetos, jd),
+        Planet::Vulkanus => |jd| hamburg_point(Planet::Vulkanus, jd),
+        Planet::Poseidon => |jd| hamburg_point(Planet::Poseidon, jd),
+        _ => return None,
+    };
+
+    Some(body)
+}
+
+/// Heliocentric position of the Moon: that of the Earth plus the geocentric one
+fn moon_heliocentric(jd_tt: f64) -> Heliocentric {
+    let moon = moon::moon(jd_tt);
+    let [mx, my, mz] = rectangular(&Heliocentric {
+        longitude: moon.longitude.to_radians(),
+        latitude: moon.latitude.to_radians(),
+        radius: moon.distance_km / AU_KM,
+    });
+    let [ex, ey, ez] = rectangular(&vsop87::heliocentric(VsopBody::Earth, millennia(jd_tt)));
+    let (x, y, z) = (ex + mx, ey + my, ez + mz);
+    let radius = (x * x + y * y + z * z).sqrt();
+
+    Heliocentric {
+        longitude: y.atan2(x).rem_euclid(TAU),
+        latitude: (z / radius).asin(),
+        radius,
+    }
+}
+
+fn hamburg_point(planet: Planet, jd_tt: f64) -> Heliocentric {
+    hamburg::heliocentric(planet, jd_tt).expect("planet is a Hamburg point")
+}
+
+/// Julian millennia from J2000.0
+fn millennia(jd_tt: f64) -> f64 {
+    (jd_tt - J2000) / 365_250.0
+}
+
+/// Geocentric ecliptic (longitude, latitude, distance) of a heliocentric body
+///
+/// Both the body and the Earth are taken at the time the light left the body,
+/// which corrects for light-time and annual aberration at once.
+fn geocentric(body: impl Fn(f64) -> Heliocentric, jd_tt: f64) -> (f64, f64, f64) {
+    let mut tau = 0.0;
+    let mut result = (0.0, 0.0, 0.0);
+
+    for _ in 0..3 {
+        let earth = vsop87::heliocentric(VsopBody::Earth, millennia(jd_tt - tau));
+        let [ex, ey, ez] = rectangular(&earth);
+        let [x, y, z] = rectangular(&body(jd_tt - tau));
+        let (dx, dy, dz) = (x - ex, y - ey, z - ez);
+
+        let distance = (dx * dx + dy * dy + dz * dz).sqrt();
+        let longitude = dy.atan2(dx).rem_euclid(TAU).to_degrees();
+        let latitude = (dz / distance).asin().to_degrees();
+
+        result = (longitude, latitude, distance);
+        tau = LIGHT_TIME_PER_AU * distance;
+    }
+
+    result
+}
+
+/// Ecliptic (longitude, latitude, distance) of a body seen from the centre
+/// of the Sun or the barycentre of the solar system, given its heliocentric
+/// rectangular coordinates, referred to the true equinox of date
+///
+/// The body is taken at the time the light left it, so the position is
+/// corrected for light-time but not for aberration.
+fn centered(body: impl Fn(f64) -> [f64; 3], jd_tt: f64, barycentric: bool) -> (f64, f64, f64) {
+    let position = |jd| {
+        let [x, y, z] = body(jd);
+        let [sx, sy, sz] = if barycentric { sun_barycentric(jd) } else { [0.0; 3] };
+        [x + sx, y + sy, z + sz]
+    };
+
+    let mut tau = 0.0;
+    let mut result = (0.0, 0.0, 0.0);
+    for _ in 0..3 {
+        let [x, y, z] = position(jd_tt - tau);
+        let distance = (x * x + y * y + z * z).sqrt();
+        result = (y.atan2(x).rem_euclid(TAU).to_degrees(), (z / distance).asin().to_degrees(), distance);
+        tau = LIGHT_TIME_PER_AU * distance;
+    }
+
+    let (longitude, latitude, distance) = result;
+    let (longitude, latitude) = fk5_correction(longitude, latitude, jd_tt);
+    let (delta_psi, _) = nutation::nutation(jd_tt);
+
+    ((longitude + delta_psi).rem_euclid(360.0), latitude, distance)
+}
+
+/// Rectangular coordinates (AU) of the Sun relative to the barycentre of the
+/// solar system for a Julian day (TT), from the masses and heliocentric
+/// positions of the planets
+fn sun_barycentric(jd_tt: f64) -> [f64; 3] {
+    let mut total_mass = 1.0;
+    let mut moment = [0.0; 3];
+
+    for (body, ratio) in SUN_PLANET_MASS_RATIOS {
+        let mass = 1.0 / ratio;
+        let position = rectangular(&vsop87::heliocentric(body, millennia(jd_tt)));
+        for (sum, coordinate) in moment.iter_mut().zip(position) {
+            *sum += mass * coordinate;
+        }
+        total_mass += mass;
+    }
+
+    moment.map(|sum| -sum / total_mass)
+}
+
+fn rectangular(position: &Heliocentric) -> [f64; 3] {
+    let (sin_l, cos_l) = position.longitude.sin_cos();
+    let (sin_b, cos_b) = position.latitude.sin_cos();
+    [
+        position.radius * cos_b * cos_l,
+        position.radius * cos_b * sin_l,
+        position.radius * sin_b,
+    ]
+}
+
+/// Convert VSOP87 coordinates to the FK5 system (Meeus, chapter 32)
+fn fk5_correction(longitude: f64, latitude: f64, jd_tt: f64) -> (f64, f64) {
+    let t = (jd_tt - J2000) / 36_525.0;
+    let shifted = (longitude - 1.397 * t - 0.000_31 * t * t).to_radians();
+    let (sin, cos) = shifted.sin_cos();
+
+    let delta_longitude = -0.090_33 + 0.039_16 * (cos + sin) * latitude.to_radians().tan();
+    let delta_latitude = 0.039_16 * (cos - sin);
+
+    (longitude + delta_longitude / 3600.0, latitude + delta_latitude / 3600.0)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_apparent_positions() {
+        // Meeus, example 33.a: Venus on 1992 December 20, 0h TD; the light
+        // leaving the planet and the Earth at the same time stands in for
+        // the aberration, within 2"
+        let (longitude, latitude, distance) = AnalyticEphemeris.apparent(Planet::Venus, 2_448_976.5).unwrap();
+        assert!(approx_eq!(f64, longitude, 313.081_02, epsilon = 0.0006), "{longitude}");
+        assert!(approx_eq!(f64, latitude, -2.084_74, epsilon = 0.0003), "{latitude}");
+        assert!(approx_eq!(f64, distance, 0.910_947, epsilon = 1e-4), "{distance}");
+
+        // Meeus, example 25.b: the Sun on 1992 October 13, 0h TD, at 199°54'21.818"
+        let (longitude, latitude, distance) = AnalyticEphemeris.apparent(Planet::Sun, 2_448_908.5).unwrap();
+        assert!(approx_eq!(f64, longitude, 199.906_061, epsilon = 0.0003), "{longitude}");
+        assert!(approx_eq!(f64, latitude, 0.000_2, epsilon = 0.0003), "{latitude}");
+        assert!(approx_eq!(f64, distance, 0.997_608_53, epsilon = 1e-5), "{distance}");
+    }
+}
@@ -0,0 +1,47 @@
+//! Hamburg School transneptunian points
+//!
+//! Cupido, Hades, Zeus and Kronos (Alfred Witte) and Apollon, Admetos,
+//! Vulkanus and Poseidon (Friedrich Sieggrün) are hypothetical bodies on
+//! circular orbits in the plane of the ecliptic. Their standard elements give
+//! the mean longitude at 1900 January 0.5 and the semi-major axis, both
+//! referred to the equinox of 1900.
+
+use super::precession::precess_ecliptic;
+use super::vsop87::Heliocentric;
+use crate::astrology::models::planet::Planet;
+
+/// Julian day of the B1900.0 epoch and equinox of the elements
+const J1900: f64 = 2_415_020.0;
+
+/// Gaussian gravitational constant in radians per day
+const GAUSS_CONSTANT: f64 = 0.017_202_098_95;
+
+/// Witte/Sieggrün elements: mean longitude at J1900 (degrees) and semi-major axis (AU)
+const ELEMENTS: [(Planet, f64, f64); 8] = [
+    (Planet::Cupido, 104.5959, 40.99837),
+    (Planet::Hades, 337.4517, 50.667443),
+    (Planet::Zeus, 104.0904, 59.214362),
+    (Planet::Kronos, 17.7346, 64.816896),
+    (Planet::Apollon, 138.0354, 70.361652),
+    (Planet::Admetos, 351.322, 73.736476),
+    (Planet::Vulkanus, 55.9826, 77.445895),
+    (Planet::Poseidon, 165.3595, 83.493733),
+];
+
+/// Heliocentric position of a Hamburg point for a Julian day (TT), referred to
+/// the mean ecliptic and equinox of date
+///
+/// Returns `None` for bodies that are not transneptunian points.
+pub fn heliocentric(planet: Planet, jd_tt: f64) -> Option<Heliocentric> {
+    let (_, mean_longitude, semi_axis) = ELEMENTS.iter().find(|(p, _, _)| *p == planet)?;
+
+    let mean_motion = GAUSS_CONSTANT.to_degrees() / semi_axis.powf(1.5);
+    let longitude = mean_longitude + mean_motion * (jd_tt - J1900);
+    let (longitude, latitude) = precess_ecliptic(longitude.rem_euclid(360.0), 0.0, J1900, jd_tt);
+
+    Some(Heliocentric {
+        longitude: longitude.to_radians(),
+        latitude: latitude.to_radians(),
+        radius: *semi_axis,
+    })
+}
@@ -0,0 +1,129 @@
+//! Two-body orbits from osculating elements
+//!
+//! Minor planets without a Swiss Ephemeris asteroid file are placed on the
+//! Keplerian ellipse of their osculating elements. Planetary perturbations are
+//! ignored, so the error grows with the distance from the epoch of the
+//! elements: typically well below a minute of arc within a few months of it
+//! and a few minutes of arc after a couple of years.
+
+use serde::{Deserialize, Serialize};
+
+use super::precession::precess_ecliptic;
+use super::vsop87::Heliocentric;
+use super::J2000;
+
+/// Gaussian gravitational constant in radians per day
+const GAUSS_CONSTANT: f64 = 0.017_202_098_95;
+
+/// Osculating elliptic elements referred to the J2000 ecliptic and equinox
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrbitalElements {
+    /// Epoch of the elements as a Julian day (TT)
+    pub epoch: f64,
+    /// Mean anomaly at the epoch in degrees
+    pub mean_anomaly: f64,
+    /// Argument of perihelion in degrees
+    pub perihelion: f64,
+    /// Longitude of the ascending node in degrees
+    pub node: f64,
+    /// Inclination to the ecliptic in degrees
+    pub inclination: f64,
+    /// Eccentricity (below 1)
+    pub eccentricity: f64,
+    /// Semi-major axis in AU
+    pub semi_major_axis: f64,
+}
+
+impl OrbitalElements {
+    /// Mean daily motion in degrees
+    pub fn mean_motion(&self) -> f64 {
+        GAUSS_CONSTANT.to_degrees() / self.semi_major_axis.powf(1.5)
+    }
+
+    /// Heliocentric position for a Julian day (TT), referred to the mean
+    /// ecliptic and equinox of date
+    pub fn heliocentric(&self, jd_tt: f64) -> Heliocentric {
+        let e = self.eccentricity;
+        let mean_anomaly = (self.mean_anomaly + self.mean_motion() * (jd_tt - self.epoch)).to_radians();
+        let eccentric_anomaly = solve_kepler(mean_anomaly, e);
+
+        // Position in the orbital plane, x towards the perihelion
+        let (sin_e, cos_e) = eccentric_anomaly.sin_cos();
+        let x = self.semi_major_axis * (cos_e - e);
+        let y = self.semi_major_axis * (1.0 - e * e).sqrt() * sin_e;
+
+        // Rotate into the J2000 ecliptic
+        let (sin_w, cos_w) = self.perihelion.to_radians().sin_cos();
+        let (sin_n, cos_n) = self.node.to_radians().sin_cos();
+        let (sin_i, cos_i) = self.inclination.to_radians().sin_cos();
+        let (u, v) = (x * cos_w - y * sin_w, x * sin_w + y * cos_w);
+        let ecliptic_x = u * cos_n - v * sin_n * cos_i;
+        let ecliptic_y = u * sin_n + v * cos_n * cos_i;
+        let ecliptic_z = v * sin_i;
+
+        let radius = (x * x + y * y).sqrt();
+        let longitude = ecliptic_y.atan2(ecliptic_x).to_degrees().rem_euclid(360.0);
+        let latitude = (ecliptic_z / radius).asin().to_degrees();
+        let (longitude, latitude) = precess_ecliptic(longitude, latitude, J2000, jd_tt);
+
+        Heliocentric {
+            longitude: longitude.to_radians(),
+            latitude: latitude.to_radians(),
+            radius,
+        }
+    }
+}
+
+/// Solve Kepler's equation `E - e sin E = M` for the eccentric anomaly (radians)
+fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    let mean_anomaly = mean_anomaly.rem_euclid(std::f64::consts::TAU);
+    let mut eccentric_anomaly = if eccentricity > 0.8 { std::f64::consts::PI } else { mean_anomaly };
+
+    for _ in 0..50 {
+        let delta = (eccentric_anomaly - eccentricity * eccentric_anomaly.sin() - mean_anomaly)
+            / (1.0 - eccentricity * eccentric_anomaly.cos());
+        eccentric_anomaly -= delta;
+        if delta.abs() < 1e-12 {
+            break;
+        }
+    }
+
+    eccentric_anomaly
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_solve_kepler() {
+        // Meeus, example 30.a: e = 0.100, M = 5° gives E = 5.554589°
+        let eccentric_anomaly = solve_kepler(5f64.to_radians(), 0.1).to_degrees();
+        assert!(approx_eq!(f64, eccentric_anomaly, 5.554_589, epsilon = 1e-6));
+
+        // Highly eccentric orbits converge as well
+        let eccentric_anomaly = solve_kepler(0.2, 0.99);
+        assert!(approx_eq!(f64, eccentric_anomaly - 0.99 * eccentric_anomaly.sin(), 0.2, epsilon = 1e-10));
+    }
+
+    #[test]
+    fn test_heliocentric_at_epoch() {
+        // A circular orbit in the ecliptic sits at node + perihelion + mean anomaly
+        let elements = OrbitalElements {
+            epoch: J2000,
+            mean_anomaly: 30.0,
+            perihelion: 20.0,
+            node: 10.0,
+            inclination: 0.0,
+            eccentricity: 0.0,
+            semi_major_axis: 4.0,
+        };
+        let position = elements.heliocentric(J2000);
+
+        assert!(approx_eq!(f64, position.longitude.to_degrees(), 60.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, position.latitude, 0.0, epsilon = 1e-12));
+        assert!(approx_eq!(f64, position.radius, 4.0, epsilon = 1e-12));
+        assert!(approx_eq!(f64, elements.mean_motion(), 0.985_607_7 / 8.0, epsilon = 1e-7));
+    }
+}
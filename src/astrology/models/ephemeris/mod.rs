@@ -0,0 +1,1008 @@
+//! Ephemeris calculations
+//!
+//! Positions come from one of two interchangeable backends implementing
+//! [`EphemerisBackend`]: the Swiss Ephemeris, which needs the compressed `.se1`
+//! data files, and a pure-Rust analytic backend (VSOP87 and the Meeus lunar
+//! theory) that works fully offline. [`BackendKind::detect`] picks the Swiss
+//! Ephemeris whenever its data files are installed. Numbered minor planets
+//! are computed from asteroid files or, failing that, from orbital elements.
+//! Positions are geocentric unless a [`CoordinateFrame`] places the observer
+//! on the surface of the Earth, at the centre of the Sun or at the barycentre
+//! of the solar system.
+
+mod analytic;
+mod hamburg;
+mod kepler;
+mod moon;
+pub(crate) mod nutation;
+mod observation;
+mod pluto;
+pub(crate) mod precession;
+mod swiss;
+pub(crate) mod vsop87;
+
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+use thiserror::Error;
+
+use crate::astrology::models::fixed_star::{FixedStar, FixedStarCatalog, StarPosition};
+use crate::astrology::models::houses::{HouseSystem, Houses};
+use crate::astrology::models::minor_body::{MinorBody, MinorBodyPosition};
+use crate::astrology::models::planet::{BodyType, ChartPositions, Planet, PlanetPosition};
+use crate::astrology::models::zodiac::Zodiac;
+
+pub use analytic::AnalyticEphemeris;
+pub use kepler::OrbitalElements;
+pub use swiss::SwissEphemeris;
+
+/// Environment variable that overrides the Swiss Ephemeris data directory
+pub const EPHEMERIS_PATH_ENV: &str = "SE_EPHE_PATH";
+
+/// Julian day of the J2000.0 epoch (2000-01-01 12:00 TT)
+pub const J2000: f64 = 2_451_545.0;
+
+/// Julian day of the Unix epoch (1970-01-01 00:00 UTC)
+const UNIX_EPOCH_JD: f64 = 2_440_587.5;
+
+/// Half-width of the interval used to differentiate the angles, in days (one minute)
+const ANGLE_SPEED_STEP: f64 = 1.0 / 1440.0;
+
+/// Equatorial radius of the Earth in AU (6378.137 km)
+const EARTH_RADIUS_AU: f64 = 6378.137 / 149_597_870.7;
+
+/// Flattening of the Earth (WGS84)
+const EARTH_FLATTENING: f64 = 1.0 / 298.257_223_563;
+
+/// Errors that can occur while calculating positions
+#[derive(Debug, Error)]
+pub enum EphemerisError {
+    /// The body cannot be computed by the ephemeris at all
+    #[error("{0} is not supported by the ephemeris")]
+    UnsupportedBody(Planet),
+
+    /// The ephemeris failed to compute a supported body (e.g. missing data files)
+    #[error("failed to calculate {planet}: {message}")]
+    Calculation { planet: Planet, message: String },
+
+    /// A minor planet has neither an asteroid file nor orbital elements
+    #[error("failed to calculate {body}: {message}")]
+    MinorBody { body: String, message: String },
+
+    /// The observer location is outside the valid range
+    #[error("invalid location: latitude {latitude}, longitude {longitude}")]
+    InvalidLocation { latitude: f64, longitude: f64 },
+}
+
+/// Geocentric ecliptic coordinates as produced by a backend
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EclipticPosition {
+    /// Apparent ecliptic longitude in degrees (0-360)
+    pub longitude: f64,
+    /// Ecliptic latitude in degrees
+    pub latitude: f64,
+    /// Distance from the Earth in AU
+    pub distance_au: f64,
+    /// Daily motion in longitude in degrees
+    pub speed: f64,
+    /// Daily motion in latitude in degrees
+    pub speed_latitude: f64,
+}
+
+/// Point the positions of a chart are seen from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum CoordinateFrame {
+    /// Centre of the Earth
+    #[default]
+    Geocentric,
+    /// Observer on the surface of the Earth, at the chart location
+    Topocentric,
+    /// Centre of the Sun
+    Heliocentric,
+    /// Barycentre of the solar system
+    Barycentric,
+}
+
+impl CoordinateFrame {
+    /// Whether the positions are seen from the Earth, so that the houses
+    /// and angles of the chart location apply to them
+    pub fn is_terrestrial(&self) -> bool {
+        matches!(self, CoordinateFrame::Geocentric | CoordinateFrame::Topocentric)
+    }
+}
+
+/// A source of planetary positions
+pub trait EphemerisBackend: Send + Sync + fmt::Debug {
+    /// Human readable name of the backend
+    fn name(&self) -> &'static str;
+
+    /// Calculate the apparent geocentric position of a body for a Julian day (UT)
+    fn calculate(&self, planet: Planet, jd_ut: f64) -> Result<EclipticPosition, EphemerisError>;
+
+    /// Calculate the position of a body seen from the centre of the Sun or,
+    /// with `barycentric`, from the barycentre of the solar system, for a
+    /// Julian day (UT)
+    ///
+    /// Only bodies in orbit have such a position: the Moon's nodes, its
+    /// apogee and the angles do not, nor does the Sun seen from itself.
+    fn calculate_heliocentric(&self, planet: Planet, jd_ut: f64, barycentric: bool) -> Result<EclipticPosition, EphemerisError>;
+
+    /// Calculate the apparent geocentric position of a numbered minor planet
+    fn calculate_minor_body(&self, body: &MinorBody, jd_ut: f64) -> Result<EclipticPosition, EphemerisError>;
+
+    /// Calculate the position of a numbered minor planet seen from the centre
+    /// of the Sun or the barycentre of the solar system
+    fn calculate_minor_body_heliocentric(&self, body: &MinorBody, jd_ut: f64, barycentric: bool) -> Result<EclipticPosition, EphemerisError>;
+}
+
+/// Available ephemeris backends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum BackendKind {
+    /// Swiss Ephemeris with the `.se1` data files
+    Swiss,
+    /// Built-in analytic theories, no data files required
+    Analytic,
+}
+
+impl BackendKind {
+    /// Use the Swiss Ephemeris if its data files are installed, the analytic backend otherwise
+    pub fn detect() -> Self {
+        let has_data_files = std::fs::read_dir(ephemeris_path())
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .any(|entry| entry.path().extension().is_some_and(|ext| ext == "se1"))
+            })
+            .unwrap_or(false);
+
+        if has_data_files {
+            BackendKind::Swiss
+        } else {
+            BackendKind::Analytic
+        }
+    }
+
+    /// Instantiate the backend
+    pub fn create(self) -> Arc<dyn EphemerisBackend> {
+        match self {
+            BackendKind::Swiss => Arc::new(SwissEphemeris::new()),
+            BackendKind::Analytic => Arc::new(AnalyticEphemeris::new()),
+        }
+    }
+}
+
+/// Calculates planetary positions for a moment in time and an observer location
+#[derive(Debug, Clone)]
+pub struct Ephemeris {
+    /// Source of the positions
+    backend: Arc<dyn EphemerisBackend>,
+    /// Moment of the calculation
+    datetime: DateTime<Utc>,
+    /// Geographic latitude of the observer in degrees (north positive)
+    latitude: f64,
+    /// Geographic longitude of the observer in degrees (east positive)
+    longitude: f64,
+    /// House system used for the cusps and the house of each body
+    house_system: HouseSystem,
+    /// Zodiac the signs of the positions are counted in
+    zodiac: Zodiac,
+    /// Point the positions are seen from
+    frame: CoordinateFrame,
+}
+
+impl Ephemeris {
+    /// Create a new ephemeris for the given time and location, using the best available backend
+    pub fn new(datetime: DateTime<Utc>, latitude: f64, longitude: f64) -> Result<Self, EphemerisError> {
+        Self::with_backend(datetime, latitude, longitude, BackendKind::detect())
+    }
+
+    /// Create a new ephemeris that uses a specific backend
+    pub fn with_backend(
+        datetime: DateTime<Utc>,
+        latitude: f64,
+        longitude: f64,
+        kind: BackendKind,
+    ) -> Result<Self, EphemerisError> {
+        validate_location(latitude, longitude)?;
+
+        Ok(Self {
+            backend: kind.create(),
+            datetime,
+            latitude,
+            longitude,
+            house_system: HouseSystem::default(),
+            zodiac: Zodiac::default(),
+            frame: CoordinateFrame::default(),
+        })
+    }
+
+    /// Get the name of the backend in use
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Get the moment the ephemeris is calculated for
+    pub fn datetime(&self) -> DateTime<Utc> {
+        self.datetime
+    }
+
+    /// Get the observer location as (latitude, longitude)
+    pub fn location(&self) -> (f64, f64) {
+        (self.latitude, self.longitude)
+    }
+
+    /// Get the Julian day (UT) of the current moment
+    pub fn julian_day_ut(&self) -> f64 {
+        julian_day(&self.datetime)
+    }
+
+    /// Change the moment of the calculation
+    pub fn update_time(&mut self, datetime: DateTime<Utc>) -> Result<(), EphemerisError> {
+        self.datetime = datetime;
+        Ok(())
+    }
+
+    /// Change the observer location
+    pub fn update_location(&mut self, latitude: f64, longitude: f64) -> Result<(), EphemerisError> {
+        validate_location(latitude, longitude)?;
+        self.latitude = latitude;
+        self.longitude = longitude;
+        Ok(())
+    }
+
+    /// Get the house system in use
+    pub fn house_system(&self) -> HouseSystem {
+        self.house_system
+    }
+
+    /// Change the house system
+    pub fn set_house_system(&mut self, system: HouseSystem) {
+        self.house_system = system;
+    }
+
+    /// Get the zodiac in use
+    pub fn zodiac(&self) -> Zodiac {
+        self.zodiac
+    }
+
+    /// Change the zodiac the signs of the positions are counted in
+    pub fn set_zodiac(&mut self, zodiac: Zodiac) {
+        self.zodiac = zodiac;
+    }
+
+    /// Get the coordinate frame in use
+    pub fn frame(&self) -> CoordinateFrame {
+        self.frame
+    }
+
+    /// Change the point the positions are seen from
+    pub fn set_frame(&mut self, frame: CoordinateFrame) {
+        self.frame = frame;
+    }
+
+    /// Get the ayanamsa of the zodiac in use at the current moment, 0 in the
+    /// tropical zodiac
+    pub fn ayanamsa(&self) -> f64 {
+        self.zodiac.ayanamsa(self.julian_day_ut())
+    }
+
+    /// Calculate the house cusps and angles in the current house system
+    pub fn houses(&self) -> Houses {
+        self.houses_in(self.house_system)
+    }
+
+    /// Calculate the house cusps and angles in a given house system
+    pub fn houses_in(&self, system: HouseSystem) -> Houses {
+        Houses::calculate(system, self.julian_day_ut(), self.latitude, self.longitude)
+    }
+
+    /// Calculate the ecliptic position of a body in the coordinate frame in use
+    ///
+    /// The Ascendant, MC, Vertex and East Point are taken from the houses of
+    /// the observer location and exist only in the geocentric and topocentric
+    /// frames; every other body comes from the backend. The longitude is
+    /// always tropical; the sign and degree are counted in the zodiac in use,
+    /// and the equatorial coordinates use the true obliquity of date. Seen
+    /// from the Earth, the altitude and azimuth are those of the topocentric
+    /// place for the observer location, without refraction, and the bodies
+    /// with a disc get their apparent size, magnitude, phase and elongation.
+    pub fn get_planet_position(&self, planet: &Planet) -> Result<PlanetPosition, EphemerisError> {
+        let jd_ut = self.julian_day_ut();
+        let position = match (self.frame, self.angle_position(planet, jd_ut)) {
+            (frame, Some(position)) if frame.is_terrestrial() => position,
+            (_, Some(_)) => return Err(EphemerisError::UnsupportedBody(*planet)),
+            (CoordinateFrame::Geocentric, None) => self.backend.calculate(*planet, jd_ut)?,
+            (CoordinateFrame::Topocentric, None) => {
+                let position = self.backend.calculate(*planet, jd_ut)?;
+                if has_parallax(planet) {
+                    self.topocentric(position, jd_ut)
+                } else {
+                    position
+                }
+            }
+            (CoordinateFrame::Heliocentric, None) => self.backend.calculate_heliocentric(*planet, jd_ut, false)?,
+            (CoordinateFrame::Barycentric, None) => self.backend.calculate_heliocentric(*planet, jd_ut, true)?,
+        };
+
+        let observation = if self.frame.is_terrestrial() { self.observe(planet, &position, jd_ut) } else { None };
+
+        let mut planet_position = PlanetPosition::new(
+            *planet,
+            position.longitude,
+            position.latitude,
+            position.distance_au,
+            position.speed,
+            position.speed_latitude,
+            position.speed < 0.0,
+            observation.map_or(0.0, |observation| observation.apparent_size),
+            observation.map_or(0.0, |observation| observation.magnitude),
+            observation.map_or(0.0, |observation| observation.phase),
+            observation.map_or(0.0, |observation| observation.elongation),
+            1,
+        );
+        if let Some(observation) = observation {
+            planet_position.set_phase_angle(observation.phase_angle, observation.is_east_of_sun);
+        }
+        let obliquity = nutation::true_obliquity(jd_ut + delta_t(jd_ut) / 86_400.0);
+        planet_position.set_zodiac(self.zodiac, self.zodiac.ayanamsa(jd_ut));
+        planet_position.set_obliquity(obliquity);
+
+        // Altitude and azimuth are those of the place seen by the observer
+        if self.frame.is_terrestrial() {
+            let (longitude, latitude) = match self.frame {
+                CoordinateFrame::Geocentric if has_parallax(planet) => {
+                    let (longitude, latitude, _) = parallax(&position, jd_ut, self.latitude, self.longitude);
+                    (longitude, latitude)
+                }
+                _ => (position.longitude, position.latitude),
+            };
+            let (right_ascension, declination) = equatorial(longitude, latitude, obliquity);
+            let (altitude, azimuth) = horizontal(right_ascension, declination, jd_ut, self.latitude, self.longitude);
+            planet_position.set_horizontal_coords(altitude, azimuth);
+        }
+
+        Ok(planet_position)
+    }
+
+    /// Calculate the ecliptic position of a numbered minor planet in the
+    /// coordinate frame in use
+    pub fn get_minor_body_position(&self, body: &MinorBody) -> Result<MinorBodyPosition, EphemerisError> {
+        let jd_ut = self.julian_day_ut();
+        let position = match self.frame {
+            CoordinateFrame::Geocentric => self.backend.calculate_minor_body(body, jd_ut)?,
+            CoordinateFrame::Topocentric => self.topocentric(self.backend.calculate_minor_body(body, jd_ut)?, jd_ut),
+            CoordinateFrame::Heliocentric => self.backend.calculate_minor_body_heliocentric(body, jd_ut, false)?,
+            CoordinateFrame::Barycentric => self.backend.calculate_minor_body_heliocentric(body, jd_ut, true)?,
+        };
+
+        Ok(MinorBodyPosition {
+            number: body.number,
+            name: body.name.clone(),
+            longitude: position.longitude,
+            latitude: position.latitude,
+            distance_au: position.distance_au,
+            speed: position.speed,
+            is_retrograde: position.speed < 0.0,
+        })
+    }
+
+    /// Calculate the apparent position of a fixed star
+    pub fn get_star_position(&self, star: &FixedStar) -> StarPosition {
+        star.position(self.julian_day_ut())
+    }
+
+    /// Calculate the apparent positions of every star in a catalogue
+    pub fn star_positions(&self, catalog: &FixedStarCatalog) -> Vec<StarPosition> {
+        catalog.positions(self.julian_day_ut())
+    }
+
+    /// Calculate the positions of every body the ephemeris supports, along
+    /// with the house cusps; seen from the Earth, each body is placed in its
+    /// house
+    pub fn chart_positions(&self) -> ChartPositions {
+        let mut chart = ChartPositions::new(self.datetime, (self.latitude, self.longitude));
+        let houses = self.houses();
+
+        for planet in Planet::iter() {
+            match self.get_planet_position(&planet) {
+                Ok(mut position) => {
+                    if self.frame.is_terrestrial() {
+                        position.set_house(houses.house_of(position.longitude));
+                    }
+                    chart.add_position(position);
+                }
+                Err(EphemerisError::UnsupportedBody(_)) => {}
+                Err(err) => warn!("{}", err),
+            }
+        }
+
+        chart.houses = Some(houses);
+        chart.zodiac = self.zodiac;
+        chart.frame = self.frame;
+        chart
+    }
+
+    /// Apparent size, magnitude and phase of a body seen from the Earth, none
+    /// for the points without a disc or when the Sun cannot be calculated
+    fn observe(&self, planet: &Planet, position: &EclipticPosition, jd_ut: f64) -> Option<observation::Observation> {
+        if !observation::has_disc(*planet) {
+            return None;
+        }
+        let sun = match planet {
+            Planet::Sun => *position,
+            _ => self.backend.calculate(Planet::Sun, jd_ut).ok()?,
+        };
+        observation::observe(*planet, position, &sun, jd_ut)
+    }
+
+    /// Shift a geocentric position to the observer on the surface of the
+    /// Earth, with the daily motion including the turning of the Earth
+    fn topocentric(&self, position: EclipticPosition, jd_ut: f64) -> EclipticPosition {
+        let shifted = |days: f64| {
+            let geocentric = EclipticPosition {
+                longitude: position.longitude + position.speed * days,
+                latitude: position.latitude + position.speed_latitude * days,
+                ..position
+            };
+            parallax(&geocentric, jd_ut + days, self.latitude, self.longitude)
+        };
+
+        let (longitude, latitude, distance_au) = shifted(0.0);
+        let (before_lon, before_lat, _) = shifted(-ANGLE_SPEED_STEP);
+        let (after_lon, after_lat, _) = shifted(ANGLE_SPEED_STEP);
+        let speed = (after_lon - before_lon + 540.0).rem_euclid(360.0) - 180.0;
+
+        EclipticPosition {
+            longitude,
+            latitude,
+            distance_au,
+            speed: speed / (2.0 * ANGLE_SPEED_STEP),
+            speed_latitude: (after_lat - before_lat) / (2.0 * ANGLE_SPEED_STEP),
+        }
+    }
+
+    /// Position of an angle (Ascendant, MC, Vertex or East Point), with its
+    /// daily motion by centered difference
+    fn angle_position(&self, planet: &Planet, jd_ut: f64) -> Option<EclipticPosition> {
+        let angle = |jd| Houses::calculate(self.house_system, jd, self.latitude, self.longitude).angle(planet);
+
+        let longitude = angle(jd_ut)?;
+        let before = angle(jd_ut - ANGLE_SPEED_STEP)?;
+        let after = angle(jd_ut + ANGLE_SPEED_STEP)?;
+        let speed = (after - before + 540.0).rem_euclid(360.0) - 180.0;
+
+        Some(EclipticPosition {
+            longitude,
+            latitude: 0.0,
+            distance_au: 0.0,
+            speed: speed / (2.0 * ANGLE_SPEED_STEP),
+            speed_latitude: 0.0,
+        })
+    }
+}
+
+/// Convert a UTC moment to a Julian day number
+pub fn julian_day(datetime: &DateTime<Utc>) -> f64 {
+    let seconds = datetime.timestamp() as f64 + datetime.timestamp_subsec_nanos() as f64 * 1e-9;
+    UNIX_EPOCH_JD + seconds / 86_400.0
+}
+
+/// Milliseconds in a day
+pub const DAY_MS: f64 = 86_400_000.0;
+
+/// The moment a number of days after `from`
+pub fn at(from: DateTime<Utc>, days: f64) -> DateTime<Utc> {
+    from + Duration::milliseconds((days * DAY_MS).round() as i64)
+}
+
+/// Get the directory the Swiss Ephemeris data files are loaded from
+pub fn ephemeris_path() -> PathBuf {
+    std::env::var_os(EPHEMERIS_PATH_ENV)
+        .map(PathBuf::from)
+        .or_else(|| dirs::data_dir().map(|dir| dir.join("rust-astrology").join("ephe")))
+        .unwrap_or_else(|| PathBuf::from("ephe"))
+}
+
+/// Difference between Terrestrial Time and Universal Time in seconds
+///
+/// Uses the Espenak–Meeus polynomials, which are fitted to observed values up
+/// to 2005 and extrapolated beyond.
+pub fn delta_t(jd_ut: f64) -> f64 {
+    let year = 2000.0 + (jd_ut - J2000) / 365.25;
+
+    match year {
+        y if !(1800.0..2150.0).contains(&y) => {
+            let u = (y - 1820.0) / 100.0;
+            -20.0 + 32.0 * u * u
+        }
+        y if y < 1860.0 => polynomial(
+            y - 1800.0,
+            &[13.72, -0.332447, 0.0068612, 0.0041116, -0.00037436, 0.0000121272, -0.0000001699, 0.000000000875],
+        ),
+        y if y < 1900.0 => polynomial(
+            y - 1860.0,
+            &[7.62, 0.5737, -0.251754, 0.01680668, -0.0004473624, 1.0 / 233_174.0],
+        ),
+        y if y < 1920.0 => polynomial(y - 1900.0, &[-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197]),
+        y if y < 1941.0 => polynomial(y - 1920.0, &[21.20, 0.84493, -0.076100, 0.0020936]),
+        y if y < 1961.0 => polynomial(y - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0]),
+        y if y < 1986.0 => polynomial(y - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0]),
+        y if y < 2005.0 => polynomial(
+            y - 2000.0,
+            &[63.86, 0.3345, -0.060374, 0.0017275, 0.000651814, 0.00002373599],
+        ),
+        y if y < 2050.0 => polynomial(y - 2000.0, &[62.92, 0.32217, 0.005589]),
+        y => {
+            let u = (y - 1820.0) / 100.0;
+            -20.0 + 32.0 * u * u - 0.5628 * (2150.0 - y)
+        }
+    }
+}
+
+/// Greenwich apparent sidereal time in degrees for a Julian day (UT)
+///
+/// Mean sidereal time (Meeus, formula 12.4) corrected by the equation of the
+/// equinoxes.
+pub fn apparent_sidereal_time(jd_ut: f64) -> f64 {
+    let t = (jd_ut - J2000) / 36_525.0;
+    let mean = 280.460_618_37 + 360.985_647_366_29 * (jd_ut - J2000) + 0.000_387_933 * t * t
+        - t * t * t / 38_710_000.0;
+
+    let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+    let (delta_psi, _) = nutation::nutation(jd_tt);
+    let equation_of_equinoxes = delta_psi * nutation::true_obliquity(jd_tt).to_radians().cos();
+
+    (mean + equation_of_equinoxes).rem_euclid(360.0)
+}
+
+/// Right ascension and declination in degrees of an ecliptic position, for
+/// an obliquity of the ecliptic in degrees
+pub fn equatorial(longitude: f64, latitude: f64, obliquity: f64) -> (f64, f64) {
+    let (sin_l, cos_l) = longitude.to_radians().sin_cos();
+    let (sin_b, cos_b) = latitude.to_radians().sin_cos();
+    let (sin_e, cos_e) = obliquity.to_radians().sin_cos();
+
+    let right_ascension = (cos_b * sin_l * cos_e - sin_b * sin_e).atan2(cos_b * cos_l);
+    let declination = (sin_b * cos_e + cos_b * sin_e * sin_l).asin();
+    (right_ascension.to_degrees().rem_euclid(360.0), declination.to_degrees())
+}
+
+/// Altitude and azimuth in degrees of an equatorial position for an
+/// observer at a geographic latitude and east longitude (Meeus, chapter 13)
+///
+/// The azimuth is counted from the north through the east. The altitude is
+/// geometric; see [`refraction`] for the apparent one.
+pub fn horizontal(right_ascension: f64, declination: f64, jd_ut: f64, latitude: f64, longitude: f64) -> (f64, f64) {
+    let hour_angle = hour_angle(right_ascension, jd_ut, longitude).to_radians();
+    let (sin_h, cos_h) = hour_angle.sin_cos();
+    let (sin_d, cos_d) = declination.to_radians().sin_cos();
+    let (sin_p, cos_p) = latitude.to_radians().sin_cos();
+
+    let altitude = (sin_p * sin_d + cos_p * cos_d * cos_h).clamp(-1.0, 1.0).asin();
+    let azimuth = (-cos_d * sin_h).atan2(sin_d * cos_p - cos_d * cos_h * sin_p);
+    (altitude.to_degrees(), azimuth.to_degrees().rem_euclid(360.0))
+}
+
+/// Local hour angle in degrees (-180 to 180) of a right ascension for an
+/// observer at an east longitude, positive west of the meridian
+pub fn hour_angle(right_ascension: f64, jd_ut: f64, longitude: f64) -> f64 {
+    (apparent_sidereal_time(jd_ut) + longitude - right_ascension + 540.0).rem_euclid(360.0) - 180.0
+}
+
+/// Atmospheric refraction in degrees at a geometric altitude in degrees,
+/// for standard pressure and temperature (Saemundsson's formula)
+pub fn refraction(altitude: f64) -> f64 {
+    if altitude < -2.0 {
+        return 0.0;
+    }
+    let minutes = 1.02 / (altitude + 10.3 / (altitude + 5.11)).to_radians().tan();
+    minutes.max(0.0) / 60.0
+}
+
+/// Whether the topocentric position of a body differs from the geocentric
+/// one: the Moon's nodes and apogee are directions, not bodies
+fn has_parallax(planet: &Planet) -> bool {
+    !matches!(planet.body_type(), BodyType::LunarNode | BodyType::Point)
+}
+
+/// Topocentric (longitude, latitude, distance in AU) of a geocentric
+/// position for an observer at sea level at a geographic latitude and east
+/// longitude
+///
+/// The geocentric position of the observer (Meeus, chapter 11) is taken from
+/// the body's position vector in the ecliptic of date.
+fn parallax(position: &EclipticPosition, jd_ut: f64, latitude: f64, longitude: f64) -> (f64, f64, f64) {
+    let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+    let (sin_eps, cos_eps) = nutation::true_obliquity(jd_tt).to_radians().sin_cos();
+
+    // Observer: geocentric latitude from the reduced latitude u
+    let phi = latitude.to_radians();
+    let u = ((1.0 - EARTH_FLATTENING) * phi.tan()).atan();
+    let rho_sin = (1.0 - EARTH_FLATTENING) * u.sin() * EARTH_RADIUS_AU;
+    let rho_cos = u.cos() * EARTH_RADIUS_AU;
+    let (sin_theta, cos_theta) = (apparent_sidereal_time(jd_ut) + longitude).to_radians().sin_cos();
+    let (x, y, z) = (rho_cos * cos_theta, rho_cos * sin_theta, rho_sin);
+    let observer = [x, y * cos_eps + z * sin_eps, z * cos_eps - y * sin_eps];
+
+    let (sin_l, cos_l) = position.longitude.to_radians().sin_cos();
+    let (sin_b, cos_b) = position.latitude.to_radians().sin_cos();
+    let r = position.distance_au;
+    let dx = r * cos_b * cos_l - observer[0];
+    let dy = r * cos_b * sin_l - observer[1];
+    let dz = r * sin_b - observer[2];
+
+    let distance = (dx * dx + dy * dy + dz * dz).sqrt();
+    (dy.atan2(dx).to_degrees().rem_euclid(360.0), (dz / distance).asin().to_degrees(), distance)
+}
+
+fn polynomial(x: f64, coefficients: &[f64]) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+fn validate_location(latitude: f64, longitude: f64) -> Result<(), EphemerisError> {
+    if (-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude) {
+        Ok(())
+    } else {
+        Err(EphemerisError::InvalidLocation { latitude, longitude })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use float_cmp::approx_eq;
+
+    fn j2000() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    fn ephemerides() -> Vec<Ephemeris> {
+        BackendKind::iter()
+            .map(|kind| Ephemeris::with_backend(j2000(), 0.0, 0.0, kind).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_julian_day() {
+        assert!(approx_eq!(f64, julian_day(&j2000()), J2000, epsilon = 1e-9));
+
+        let epoch = Utc.with_ymd_and_hms(1970, 1, 1, 0, 0, 0).unwrap();
+        assert!(approx_eq!(f64, julian_day(&epoch), UNIX_EPOCH_JD, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn test_delta_t() {
+        assert!(approx_eq!(f64, delta_t(2_451_545.0), 63.8, epsilon = 0.1));
+        assert!(approx_eq!(f64, delta_t(julian_day(&Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 0).unwrap())), -2.8, epsilon = 0.1));
+    }
+
+    #[test]
+    fn test_invalid_location() {
+        assert!(Ephemeris::new(j2000(), 91.0, 0.0).is_err());
+        assert!(Ephemeris::new(j2000(), 0.0, 181.0).is_err());
+
+        let mut ephemeris = Ephemeris::new(j2000(), 52.5, 13.4).unwrap();
+        assert!(ephemeris.update_location(-95.0, 0.0).is_err());
+        assert_eq!(ephemeris.location(), (52.5, 13.4));
+    }
+
+    #[test]
+    fn test_angles() {
+        // At the equator the Vertex is stuck on an equinox, so use Berlin
+        for kind in BackendKind::iter() {
+            let ephemeris = Ephemeris::with_backend(j2000(), 52.5, 13.4, kind).unwrap();
+            let houses = ephemeris.houses();
+
+            for planet in [Planet::Ascendant, Planet::MC, Planet::Vertex, Planet::EastPoint] {
+                // Backends only know bodies; the angles depend on the observer
+                assert!(matches!(
+                    ephemeris.backend.calculate(planet, ephemeris.julian_day_ut()),
+                    Err(EphemerisError::UnsupportedBody(p)) if p == planet
+                ));
+
+                let position = ephemeris.get_planet_position(&planet).unwrap();
+                assert!(approx_eq!(f64, position.longitude, houses.angle(&planet).unwrap(), epsilon = 1e-9));
+                assert!(position.speed > 200.0, "{planet}: {}", position.speed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sun_position_at_j2000() {
+        for ephemeris in ephemerides() {
+            let sun = ephemeris.get_planet_position(&Planet::Sun).unwrap();
+
+            assert!(approx_eq!(f64, sun.longitude, 280.369, epsilon = 0.01), "{}", ephemeris.backend_name());
+            assert!(sun.latitude.abs() < 0.001);
+            assert!(approx_eq!(f64, sun.distance_au, 0.9833, epsilon = 0.001));
+            assert!(approx_eq!(f64, sun.speed, 1.019, epsilon = 0.01));
+            assert!(!sun.is_retrograde);
+        }
+    }
+
+    #[test]
+    fn test_positions_at_j2000() {
+        // Apparent geocentric longitude, latitude and distance (AU)
+        let expected = [
+            (Planet::Moon, 223.3238, 5.1708, 0.0026900),
+            (Planet::Mercury, 271.8893, -0.9948, 1.41547),
+            (Planet::Venus, 241.5658, 2.0663, 1.13758),
+            (Planet::Mars, 327.9633, -1.0678, 1.84969),
+            (Planet::Jupiter, 25.2530, -1.2622, 4.62118),
+            (Planet::Saturn, 40.3956, -2.4448, 8.65280),
+            (Planet::Uranus, 314.8092, -0.6583, 20.72717),
+            (Planet::Neptune, 303.1930, 0.2350, 31.02453),
+            (Planet::Pluto, 251.4547, 10.8552, 31.06439),
+            (Planet::TrueNode, 123.9529, 0.0, 0.0),
+            (Planet::BlackMoonLilith, 263.4643, 3.4197, 0.0027106),
+        ];
+
+        for kind in BackendKind::iter() {
+            let ephemeris = Ephemeris::with_backend(j2000(), 0.0, 0.0, kind).unwrap();
+            for (planet, longitude, latitude, distance) in expected {
+                let position = ephemeris.get_planet_position(&planet).unwrap();
+                let name = ephemeris.backend_name();
+
+                // Without its data files the Swiss backend falls back to the
+                // Moshier ephemeris; the analytic true node follows the
+                // truncated lunar theory and is good to about 15"
+                let epsilon = match (kind, planet) {
+                    (BackendKind::Swiss, _) => 0.02,
+                    (BackendKind::Analytic, Planet::TrueNode) => 0.005,
+                    (BackendKind::Analytic, _) => 0.001,
+                };
+                assert!(approx_eq!(f64, position.longitude, longitude, epsilon = epsilon), "{planet} ({name})");
+                assert!(approx_eq!(f64, position.latitude, latitude, epsilon = epsilon.min(0.005)), "{planet} ({name})");
+                if distance > 0.0 {
+                    assert!(approx_eq!(f64, position.distance_au, distance, epsilon = distance * 2e-4), "{planet} ({name})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mean_node_is_retrograde() {
+        for ephemeris in ephemerides() {
+            let node = ephemeris.get_planet_position(&Planet::MeanNode).unwrap();
+
+            assert!(approx_eq!(f64, node.longitude, 125.04, epsilon = 0.01));
+            assert!(node.is_retrograde);
+        }
+    }
+
+    #[test]
+    fn test_hamburg_points() {
+        let uranian: Vec<Planet> = Planet::iter().filter(Planet::is_uranian_point).collect();
+        assert_eq!(uranian.len(), 8);
+
+        for ephemeris in ephemerides() {
+            let mut heliocentric_ephemeris = ephemeris.clone();
+            heliocentric_ephemeris.set_frame(CoordinateFrame::Heliocentric);
+
+            for planet in &uranian {
+                let geocentric = ephemeris.get_planet_position(planet).unwrap();
+                let heliocentric = heliocentric_ephemeris.get_planet_position(planet).unwrap();
+
+                // Seen from the Sun the points advance slowly and steadily
+                // close to the ecliptic; seen from the Earth they swing
+                // within about 1.5° of that
+                assert!(heliocentric.speed > 0.0 && heliocentric.speed < 0.004, "{planet}");
+                assert!(heliocentric.latitude.abs() < 0.02, "{planet}");
+                let parallax = (geocentric.longitude - heliocentric.longitude + 540.0).rem_euclid(360.0) - 180.0;
+                assert!(parallax.abs() < 1.5, "{planet}");
+            }
+        }
+
+        // Cupido was in early Sagittarius at the turn of the millennium
+        let mut ephemeris = ephemerides().remove(0);
+        ephemeris.set_frame(CoordinateFrame::Heliocentric);
+        let cupido = ephemeris.get_planet_position(&Planet::Cupido).unwrap();
+        assert!(approx_eq!(f64, cupido.longitude, 243.1, epsilon = 0.1));
+    }
+
+    #[test]
+    fn test_chart_positions() {
+        for ephemeris in ephemerides() {
+            let chart = ephemeris.chart_positions();
+
+            assert!(chart.get_position(&Planet::Sun).is_some());
+            assert!(chart.get_position(&Planet::Moon).is_some());
+            assert!(chart.get_position(&Planet::Poseidon).is_some());
+            assert!(chart.get_position(&Planet::Ascendant).is_some());
+
+            let houses = chart.houses.as_ref().unwrap();
+            assert_eq!(houses.system, HouseSystem::Placidus);
+            for position in chart.positions.values() {
+                assert_eq!(position.house, Some(houses.house_of(position.longitude)));
+            }
+            assert_eq!(chart.get_position(&Planet::MC).unwrap().house, Some(10));
+        }
+    }
+
+    #[test]
+    fn test_sidereal_positions() {
+        use crate::astrology::models::zodiac::Ayanamsa;
+
+        let datetime = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        let mut ephemeris = Ephemeris::with_backend(datetime, 51.5, 0.0, BackendKind::Analytic).unwrap();
+        let tropical = ephemeris.get_planet_position(&Planet::Sun).unwrap();
+        assert_eq!(tropical.zodiac_sign_name(), "Capricorn");
+
+        // The Sun at 10° Capricorn is in Sagittarius in the Lahiri zodiac,
+        // with the longitude left tropical
+        ephemeris.set_zodiac(Zodiac::Sidereal(Ayanamsa::Lahiri));
+        let sidereal = ephemeris.get_planet_position(&Planet::Sun).unwrap();
+        assert_eq!(sidereal.longitude, tropical.longitude);
+        assert_eq!(sidereal.zodiac_sign_name(), "Sagittarius");
+        assert!(approx_eq!(f64, sidereal.zodiac_degree, tropical.zodiac_degree + 30.0 - ephemeris.ayanamsa(), epsilon = 1e-9));
+        assert_eq!(ephemeris.chart_positions().zodiac, Zodiac::Sidereal(Ayanamsa::Lahiri));
+    }
+
+    #[test]
+    fn test_heliocentric_frames() {
+        let mut ephemeris = Ephemeris::with_backend(j2000(), 52.5, 13.4, BackendKind::Analytic).unwrap();
+        ephemeris.set_frame(CoordinateFrame::Heliocentric);
+
+        // Swiss Ephemeris heliocentric positions on 2000-01-01 12:00 UT
+        for (planet, longitude, latitude, distance) in [
+            (Planet::Mercury, 253.773_686, -3.022_178, 0.466_471),
+            (Planet::Mars, 359.438_868, -1.419_769, 1.391_204),
+            (Planet::Jupiter, 36.288_118, -1.174_599, 4.965_383),
+        ] {
+            let position = ephemeris.get_planet_position(&planet).unwrap();
+            assert!(approx_eq!(f64, position.longitude, longitude, epsilon = 0.001), "{planet}: {}", position.longitude);
+            assert!(approx_eq!(f64, position.latitude, latitude, epsilon = 0.001), "{planet}: {}", position.latitude);
+            assert!(approx_eq!(f64, position.distance_au, distance, epsilon = 1e-4), "{planet}: {}", position.distance_au);
+        }
+        let moon = ephemeris.get_planet_position(&Planet::Moon).unwrap();
+        assert!(approx_eq!(f64, moon.longitude, 100.500_212, epsilon = 0.001), "{}", moon.longitude);
+
+        // Nothing is seen from the Sun of the Sun, the nodes or the angles,
+        // and no body is placed in a house
+        for planet in [Planet::Sun, Planet::MeanNode, Planet::Ascendant] {
+            assert!(matches!(ephemeris.get_planet_position(&planet), Err(EphemerisError::UnsupportedBody(_))));
+        }
+        let chart = ephemeris.chart_positions();
+        assert_eq!(chart.frame, CoordinateFrame::Heliocentric);
+        assert!(chart.positions.values().all(|position| position.house.is_none()));
+
+        // The Sun circles the barycentre within about two of its radii
+        ephemeris.set_frame(CoordinateFrame::Barycentric);
+        let sun = ephemeris.get_planet_position(&Planet::Sun).unwrap();
+        assert!(sun.distance_au > 0.001 && sun.distance_au < 0.01, "{}", sun.distance_au);
+        let jupiter = ephemeris.get_planet_position(&Planet::Jupiter).unwrap();
+        assert!(approx_eq!(f64, jupiter.longitude, 36.288_118, epsilon = 0.2));
+    }
+
+    #[test]
+    fn test_topocentric_moon() {
+        // Swiss Ephemeris: geocentric 223°19'25.6" +5°10'15", topocentric
+        // 223°02'18.4" +4°18'38.7" seen from Berlin on 2000-01-01 12:00 UT
+        let mut ephemeris = Ephemeris::with_backend(j2000(), 52.5, 13.4, BackendKind::Analytic).unwrap();
+        let geocentric = ephemeris.get_planet_position(&Planet::Moon).unwrap();
+        ephemeris.set_frame(CoordinateFrame::Topocentric);
+        let topocentric = ephemeris.get_planet_position(&Planet::Moon).unwrap();
+
+        let shift = (topocentric.longitude - geocentric.longitude, topocentric.latitude - geocentric.latitude);
+        assert!(approx_eq!(f64, shift.0, -0.285_317, epsilon = 0.001), "{:?}", shift);
+        assert!(approx_eq!(f64, shift.1, -0.860_081, epsilon = 0.001), "{:?}", shift);
+        assert!(topocentric.distance_au < geocentric.distance_au);
+
+        // The daily motion includes the turning of the Earth; the nodes and
+        // the angles are the same from anywhere on the Earth
+        assert!(approx_eq!(f64, topocentric.speed, 10.779_458, epsilon = 0.01), "{}", topocentric.speed);
+        for planet in [Planet::MeanNode, Planet::Ascendant] {
+            let position = ephemeris.get_planet_position(&planet).unwrap();
+            ephemeris.set_frame(CoordinateFrame::Geocentric);
+            assert_eq!(position.longitude, ephemeris.get_planet_position(&planet).unwrap().longitude);
+            ephemeris.set_frame(CoordinateFrame::Topocentric);
+        }
+    }
+
+    #[test]
+    fn test_horizontal_coordinates() {
+        // The Sun culminates low in the south at Greenwich around 12:03 UT
+        let noon = Utc.with_ymd_and_hms(2000, 1, 1, 12, 3, 17).unwrap();
+        let mut ephemeris = Ephemeris::with_backend(noon, 51.4769, 0.0, BackendKind::Analytic).unwrap();
+        let sun = ephemeris.get_planet_position(&Planet::Sun).unwrap();
+        assert!(approx_eq!(f64, sun.altitude, 15.49, epsilon = 0.02), "{}", sun.altitude);
+        assert!(approx_eq!(f64, sun.azimuth, 180.0, epsilon = 0.05), "{}", sun.azimuth);
+
+        // The Moon is seen lower than from the centre of the Earth
+        let moon = ephemeris.get_planet_position(&Planet::Moon).unwrap();
+        let (right_ascension, declination) = equatorial(moon.longitude, moon.latitude, moon.obliquity);
+        let (geocentric, _) = horizontal(right_ascension, declination, ephemeris.julian_day_ut(), 51.4769, 0.0);
+        assert!(geocentric - moon.altitude > 0.8 && geocentric - moon.altitude < 1.0, "{} {}", geocentric, moon.altitude);
+
+        // Refraction lifts the horizon by some 34'
+        assert!(approx_eq!(f64, refraction(-0.57), 0.57, epsilon = 0.01));
+
+        ephemeris.set_frame(CoordinateFrame::Heliocentric);
+        assert_eq!(ephemeris.get_planet_position(&Planet::Mars).unwrap().altitude, 0.0);
+    }
+
+    #[test]
+    fn test_observational_data() {
+        // Meeus, example 41.a: Venus on 1992 December 20, an evening star
+        let datetime = Utc.with_ymd_and_hms(1992, 12, 20, 0, 0, 0).unwrap();
+        let mut ephemeris = Ephemeris::with_backend(datetime, 0.0, 0.0, BackendKind::Analytic).unwrap();
+        let venus = ephemeris.get_planet_position(&Planet::Venus).unwrap();
+        assert!(approx_eq!(f64, venus.phase_angle, 72.96, epsilon = 0.05), "{}", venus.phase_angle);
+        assert!(approx_eq!(f64, venus.phase, 0.647, epsilon = 0.002), "{}", venus.phase);
+        assert!(approx_eq!(f64, venus.magnitude, -4.2, epsilon = 0.05), "{}", venus.magnitude);
+        assert!(venus.is_east_of_sun);
+
+        // Mars at its close opposition of August 2003
+        ephemeris.update_time(Utc.with_ymd_and_hms(2003, 8, 28, 0, 0, 0).unwrap()).unwrap();
+        let mars = ephemeris.get_planet_position(&Planet::Mars).unwrap();
+        assert!(approx_eq!(f64, mars.apparent_size, 25.1, epsilon = 0.1), "{}", mars.apparent_size);
+        assert!(approx_eq!(f64, mars.magnitude, -2.9, epsilon = 0.05), "{}", mars.magnitude);
+        assert!(mars.elongation > 173.0 && !mars.is_east_of_sun);
+
+        // A waning crescent Moon at J2000, the Sun always full
+        let ephemeris = Ephemeris::with_backend(j2000(), 0.0, 0.0, BackendKind::Analytic).unwrap();
+        let moon = ephemeris.get_planet_position(&Planet::Moon).unwrap();
+        assert!(!moon.is_east_of_sun && moon.phase > 0.2 && moon.phase < 0.35, "{}", moon.phase);
+        assert!(approx_eq!(f64, moon.apparent_size, 1781.0, epsilon = 2.0), "{}", moon.apparent_size);
+        let sun = ephemeris.get_planet_position(&Planet::Sun).unwrap();
+        assert_eq!((sun.phase, sun.elongation), (1.0, 0.0));
+        assert!(approx_eq!(f64, sun.magnitude, -26.78, epsilon = 0.01), "{}", sun.magnitude);
+
+        // Points have no disc
+        let node = ephemeris.get_planet_position(&Planet::MeanNode).unwrap();
+        assert_eq!((node.apparent_size, node.magnitude, node.phase), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_minor_body_from_elements() {
+        // Ceres from its MPC elements of 2020 May 31; reference positions are
+        // the Swiss Ephemeris evaluating the same elements
+        let ceres = MinorBody::with_elements(1, "Ceres", OrbitalElements {
+            epoch: 2_459_000.5,
+            mean_anomaly: 162.686_31,
+            perihelion: 73.731_61,
+            node: 80.286_98,
+            inclination: 10.588_62,
+            eccentricity: 0.077_557_1,
+            semi_major_axis: 2.767_656_9,
+        });
+        let at_epoch = Utc.with_ymd_and_hms(2020, 5, 31, 0, 0, 0).unwrap();
+
+        // An asteroid file, where installed, agrees with the osculating orbit at its epoch
+        for kind in BackendKind::iter() {
+            let ephemeris = Ephemeris::with_backend(at_epoch, 0.0, 0.0, kind).unwrap();
+            let position = ephemeris.get_minor_body_position(&ceres).unwrap();
+
+            assert!(approx_eq!(f64, position.longitude, 339.158_06, epsilon = 0.003), "{}", position.longitude);
+            assert!(approx_eq!(f64, position.latitude, -9.681_38, epsilon = 0.003));
+            assert!(approx_eq!(f64, position.distance_au, 2.780_753, epsilon = 0.001));
+            assert_eq!(position.name, "Ceres");
+        }
+
+        // Eight years later, on the same orbit
+        let later = Utc.with_ymd_and_hms(2028, 8, 16, 12, 0, 0).unwrap();
+        let ephemeris = Ephemeris::with_backend(later, 0.0, 0.0, BackendKind::Analytic).unwrap();
+        let position = ephemeris.get_minor_body_position(&ceres).unwrap();
+        assert!(approx_eq!(f64, position.longitude, 226.955_77, epsilon = 0.003), "{}", position.longitude);
+        assert!(approx_eq!(f64, position.latitude, 2.350_35, epsilon = 0.003));
+    }
+
+    #[test]
+    fn test_minor_body_without_elements() {
+        let ephemeris = Ephemeris::with_backend(j2000(), 0.0, 0.0, BackendKind::Analytic).unwrap();
+
+        assert!(matches!(
+            ephemeris.get_minor_body_position(&MinorBody::numbered(90_377, "Sedna")),
+            Err(EphemerisError::MinorBody { .. })
+        ));
+    }
+}
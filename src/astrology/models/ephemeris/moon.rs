@@ -0,0 +1,324 @@
+//! Analytic lunar theory
+//!
+//! The Moon uses the truncated ELP-2000/82 series from Meeus (Astronomical
+//! Algorithms, chapter 47), accurate to about 10" in longitude and 4" in
+//! latitude. The lunar nodes and the mean apogee (Black Moon Lilith) use the
+//! mean elements of the same chapter. All results are referred to the mean
+//! ecliptic and equinox of date; nutation is left to the caller.
+
+use super::J2000;
+
+/// Inclination of the mean lunar orbit to the ecliptic in degrees
+const LUNAR_INCLINATION: f64 = 5.145_396;
+
+/// Mean distance of the Moon from the Earth in km
+pub const MEAN_DISTANCE_KM: f64 = 385_000.56;
+
+/// Mean distance of the lunar apogee from the Earth in km, as used for Lilith
+const MEAN_APOGEE_DISTANCE_KM: f64 = 405_500.0;
+
+/// Multipliers of (D, M, M', F) and the longitude (1e-6°) and distance (1e-3 km) amplitudes
+#[rustfmt::skip]
+const LONGITUDE_DISTANCE: &[([i8; 4], f64, f64)] = &[
+    ([0, 0, 1, 0], 6288774.0, -20905355.0),
+    ([2, 0, -1, 0], 1274027.0, -3699111.0),
+    ([2, 0, 0, 0], 658314.0, -2955968.0),
+    ([0, 0, 2, 0], 213618.0, -569925.0),
+    ([0, 1, 0, 0], -185116.0, 48888.0),
+    ([0, 0, 0, 2], -114332.0, -3149.0),
+    ([2, 0, -2, 0], 58793.0, 246158.0),
+    ([2, -1, -1, 0], 57066.0, -152138.0),
+    ([2, 0, 1, 0], 53322.0, -170733.0),
+    ([2, -1, 0, 0], 45758.0, -204586.0),
+    ([0, 1, -1, 0], -40923.0, -129620.0),
+    ([1, 0, 0, 0], -34720.0, 108743.0),
+    ([0, 1, 1, 0], -30383.0, 104755.0),
+    ([2, 0, 0, -2], 15327.0, 10321.0),
+    ([0, 0, 1, 2], -12528.0, 0.0),
+    ([0, 0, 1, -2], 10980.0, 79661.0),
+    ([4, 0, -1, 0], 10675.0, -34782.0),
+    ([0, 0, 3, 0], 10034.0, -23210.0),
+    ([4, 0, -2, 0], 8548.0, -21636.0),
+    ([2, 1, -1, 0], -7888.0, 24208.0),
+    ([2, 1, 0, 0], -6766.0, 30824.0),
+    ([1, 0, -1, 0], -5163.0, -8379.0),
+    ([1, 1, 0, 0], 4987.0, -16675.0),
+    ([2, -1, 1, 0], 4036.0, -12831.0),
+    ([2, 0, 2, 0], 3994.0, -10445.0),
+    ([4, 0, 0, 0], 3861.0, -11650.0),
+    ([2, 0, -3, 0], 3665.0, 14403.0),
+    ([0, 1, -2, 0], -2689.0, -7003.0),
+    ([2, 0, -1, 2], -2602.0, 0.0),
+    ([2, -1, -2, 0], 2390.0, 10056.0),
+    ([1, 0, 1, 0], -2348.0, 6322.0),
+    ([2, -2, 0, 0], 2236.0, -9884.0),
+    ([0, 1, 2, 0], -2120.0, 5751.0),
+    ([0, 2, 0, 0], -2069.0, 0.0),
+    ([2, -2, -1, 0], 2048.0, -4950.0),
+    ([2, 0, 1, -2], -1773.0, 4130.0),
+    ([2, 0, 0, 2], -1595.0, 0.0),
+    ([4, -1, -1, 0], 1215.0, -3958.0),
+    ([0, 0, 2, 2], -1110.0, 0.0),
+    ([3, 0, -1, 0], -892.0, 3258.0),
+    ([2, 1, 1, 0], -810.0, 2616.0),
+    ([4, -1, -2, 0], 759.0, -1897.0),
+    ([0, 2, -1, 0], -713.0, -2117.0),
+    ([2, 2, -1, 0], -700.0, 2354.0),
+    ([2, 1, -2, 0], 691.0, 0.0),
+    ([2, -1, 0, -2], 596.0, 0.0),
+    ([4, 0, 1, 0], 549.0, -1423.0),
+    ([0, 0, 4, 0], 537.0, -1117.0),
+    ([4, -1, 0, 0], 520.0, -1571.0),
+    ([1, 0, -2, 0], -487.0, -1739.0),
+    ([2, 1, 0, -2], -399.0, 0.0),
+    ([0, 0, 2, -2], -381.0, -4421.0),
+    ([1, 1, 1, 0], 351.0, 0.0),
+    ([3, 0, -2, 0], -340.0, 0.0),
+    ([4, 0, -3, 0], 330.0, 0.0),
+    ([2, -1, 2, 0], 327.0, 0.0),
+    ([0, 2, 1, 0], -323.0, 1165.0),
+    ([1, 1, -1, 0], 299.0, 0.0),
+    ([2, 0, 3, 0], 294.0, 0.0),
+    ([2, 0, -1, -2], 0.0, 8752.0),
+];
+
+/// Multipliers of (D, M, M', F) and the latitude amplitude (1e-6°)
+#[rustfmt::skip]
+const LATITUDE: &[([i8; 4], f64)] = &[
+    ([0, 0, 0, 1], 5128122.0),
+    ([0, 0, 1, 1], 280602.0),
+    ([0, 0, 1, -1], 277693.0),
+    ([2, 0, 0, -1], 173237.0),
+    ([2, 0, -1, 1], 55413.0),
+    ([2, 0, -1, -1], 46271.0),
+    ([2, 0, 0, 1], 32573.0),
+    ([0, 0, 2, 1], 17198.0),
+    ([2, 0, 1, -1], 9266.0),
+    ([0, 0, 2, -1], 8822.0),
+    ([2, -1, 0, -1], 8216.0),
+    ([2, 0, -2, -1], 4324.0),
+    ([2, 0, 1, 1], 4200.0),
+    ([2, 1, 0, -1], -3359.0),
+    ([2, -1, -1, 1], 2463.0),
+    ([2, -1, 0, 1], 2211.0),
+    ([2, -1, -1, -1], 2065.0),
+    ([0, 1, -1, -1], -1870.0),
+    ([4, 0, -1, -1], 1828.0),
+    ([0, 1, 0, 1], -1794.0),
+    ([0, 0, 0, 3], -1749.0),
+    ([0, 1, -1, 1], -1565.0),
+    ([1, 0, 0, 1], -1491.0),
+    ([0, 1, 1, 1], -1475.0),
+    ([0, 1, 1, -1], -1410.0),
+    ([0, 1, 0, -1], -1344.0),
+    ([1, 0, 0, -1], -1335.0),
+    ([0, 0, 3, 1], 1107.0),
+    ([4, 0, 0, -1], 1021.0),
+    ([4, 0, -1, 1], 833.0),
+    ([0, 0, 1, -3], 777.0),
+    ([4, 0, -2, 1], 671.0),
+    ([2, 0, 0, -3], 607.0),
+    ([2, 0, 2, -1], 596.0),
+    ([2, -1, 1, -1], 491.0),
+    ([2, 0, -2, 1], -451.0),
+    ([0, 0, 3, -1], 439.0),
+    ([2, 0, 2, 1], 422.0),
+    ([2, 0, -3, -1], 421.0),
+    ([2, 1, -1, 1], -366.0),
+    ([2, 1, 0, 1], -351.0),
+    ([4, 0, 0, 1], 331.0),
+    ([2, -1, 1, 1], 315.0),
+    ([2, -2, 0, -1], 302.0),
+    ([0, 0, 1, 3], -283.0),
+    ([2, 1, 1, -1], -229.0),
+    ([1, 1, 0, -1], 223.0),
+    ([1, 1, 0, 1], 223.0),
+    ([0, 1, -2, -1], -220.0),
+    ([2, 1, -1, -1], -220.0),
+    ([1, 0, 1, 1], -185.0),
+    ([2, -1, -2, -1], 181.0),
+    ([0, 1, 2, 1], -177.0),
+    ([4, 0, -2, -1], 176.0),
+    ([4, -1, -1, -1], 166.0),
+    ([1, 0, 1, -1], -164.0),
+    ([4, 0, 1, -1], 132.0),
+    ([1, 0, -1, -1], -119.0),
+    ([4, -1, 0, -1], 115.0),
+    ([2, -2, 0, 1], 107.0),
+];
+
+/// Geocentric ecliptic position of the Moon or a lunar point
+#[derive(Debug, Clone, Copy)]
+pub struct LunarPosition {
+    /// Ecliptic longitude in degrees (0-360)
+    pub longitude: f64,
+    /// Ecliptic latitude in degrees
+    pub latitude: f64,
+    /// Distance from the Earth's centre in km
+    pub distance_km: f64,
+}
+
+/// Fundamental lunar arguments in degrees
+struct Arguments {
+    t: f64,
+    mean_longitude: f64,
+    elongation: f64,
+    sun_anomaly: f64,
+    moon_anomaly: f64,
+    latitude_argument: f64,
+}
+
+impl Arguments {
+    fn new(jd_tt: f64) -> Self {
+        let t = (jd_tt - J2000) / 36_525.0;
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let t4 = t3 * t;
+
+        Self {
+            t,
+            mean_longitude: 218.316_447_7 + 481_267.881_234_21 * t - 0.001_578_6 * t2 + t3 / 538_841.0
+                - t4 / 65_194_000.0,
+            elongation: 297.850_192_1 + 445_267.111_403_4 * t - 0.001_881_9 * t2 + t3 / 545_868.0
+                - t4 / 113_065_000.0,
+            sun_anomaly: 357.529_109_2 + 35_999.050_290_9 * t - 0.000_153_6 * t2 + t3 / 24_490_000.0,
+            moon_anomaly: 134.963_396_4 + 477_198.867_505_5 * t + 0.008_741_4 * t2 + t3 / 69_699.0
+                - t4 / 14_712_000.0,
+            latitude_argument: 93.272_095 + 483_202.017_523_3 * t - 0.003_653_9 * t2 - t3 / 3_526_000.0
+                + t4 / 863_310_000.0,
+        }
+    }
+
+    /// Argument of a periodic term, together with the eccentricity factor
+    /// that corrects terms involving the Sun's anomaly
+    fn term(&self, multipliers: &[i8; 4]) -> (f64, f64) {
+        let [d, m, mp, f] = multipliers.map(f64::from);
+        let angle = (d * self.elongation + m * self.sun_anomaly + mp * self.moon_anomaly
+            + f * self.latitude_argument)
+            .to_radians();
+
+        let e = 1.0 - 0.002_516 * self.t - 0.000_007_4 * self.t * self.t;
+        let factor = e.powi(i32::from(multipliers[1].abs()));
+
+        (angle, factor)
+    }
+}
+
+/// Position of the Moon for a Julian day (TT)
+pub fn moon(jd_tt: f64) -> LunarPosition {
+    let args = Arguments::new(jd_tt);
+    let t = args.t;
+
+    let mut sum_l = 0.0;
+    let mut sum_r = 0.0;
+    for (multipliers, l, r) in LONGITUDE_DISTANCE {
+        let (angle, factor) = args.term(multipliers);
+        sum_l += l * factor * angle.sin();
+        sum_r += r * factor * angle.cos();
+    }
+
+    let mut sum_b = 0.0;
+    for (multipliers, b) in LATITUDE {
+        let (angle, factor) = args.term(multipliers);
+        sum_b += b * factor * angle.sin();
+    }
+
+    // Additive terms for Venus, Jupiter and the flattening of the Earth
+    let a1 = (119.75 + 131.849 * t).to_radians();
+    let a2 = (53.09 + 479_264.290 * t).to_radians();
+    let a3 = (313.45 + 481_266.484 * t).to_radians();
+    let l = args.mean_longitude.to_radians();
+    let mp = args.moon_anomaly.to_radians();
+    let f = args.latitude_argument.to_radians();
+
+    sum_l += 3958.0 * a1.sin() + 1962.0 * (l - f).sin() + 318.0 * a2.sin();
+    sum_b += -2235.0 * l.sin() + 382.0 * a3.sin() + 175.0 * (a1 - f).sin() + 175.0 * (a1 + f).sin()
+        + 127.0 * (l - mp).sin()
+        - 115.0 * (l + mp).sin();
+
+    LunarPosition {
+        longitude: (args.mean_longitude + sum_l / 1e6).rem_euclid(360.0),
+        latitude: sum_b / 1e6,
+        distance_km: MEAN_DISTANCE_KM + sum_r / 1000.0,
+    }
+}
+
+/// Longitude of the mean ascending lunar node in degrees
+pub fn mean_node(jd_tt: f64) -> f64 {
+    let t = (jd_tt - J2000) / 36_525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    (125.044_547_9 - 1_934.136_289_1 * t + 0.002_075_4 * t2 + t3 / 467_441.0 - t3 * t / 60_616_000.0)
+        .rem_euclid(360.0)
+}
+
+/// Longitude of the true (osculating) ascending lunar node in degrees
+///
+/// The node is where the plane through the Earth, the Moon and the Moon's
+/// instantaneous velocity cuts the ecliptic, i.e. the direction of the orbital
+/// angular momentum rotated by -90°.
+pub fn true_node(jd_tt: f64) -> f64 {
+    const STEP: f64 = 0.01;
+
+    let position = rectangular(&moon(jd_tt));
+    let before = rectangular(&moon(jd_tt - STEP));
+    let after = rectangular(&moon(jd_tt + STEP));
+    let velocity = [0, 1, 2].map(|i| (after[i] - before[i]) / (2.0 * STEP));
+
+    let momentum_x = position[1] * velocity[2] - position[2] * velocity[1];
+    let momentum_y = position[2] * velocity[0] - position[0] * velocity[2];
+
+    momentum_x.atan2(-momentum_y).to_degrees().rem_euclid(360.0)
+}
+
+fn rectangular(position: &LunarPosition) -> [f64; 3] {
+    let (sin_l, cos_l) = position.longitude.to_radians().sin_cos();
+    let (sin_b, cos_b) = position.latitude.to_radians().sin_cos();
+    [
+        position.distance_km * cos_b * cos_l,
+        position.distance_km * cos_b * sin_l,
+        position.distance_km * sin_b,
+    ]
+}
+
+/// Position of the mean lunar apogee (Black Moon Lilith)
+///
+/// The apogee lies on the mean lunar orbit, so it is projected from the orbit
+/// plane onto the ecliptic and carries a latitude of up to ±5°.
+pub fn mean_apogee(jd_tt: f64) -> LunarPosition {
+    let t = (jd_tt - J2000) / 36_525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let perigee = 83.353_246_5 + 4_069.013_728_7 * t - 0.010_32 * t2 - t3 / 80_053.0 + t3 * t / 18_999_000.0;
+    let node = mean_node(jd_tt);
+
+    // Angle of the apogee from the node, measured in the orbit plane
+    let argument = (perigee + 180.0 - node).to_radians();
+    let inclination = LUNAR_INCLINATION.to_radians();
+
+    let latitude = (inclination.sin() * argument.sin()).asin();
+    let longitude = node + (inclination.cos() * argument.sin()).atan2(argument.cos()).to_degrees();
+
+    LunarPosition {
+        longitude: longitude.rem_euclid(360.0),
+        latitude: latitude.to_degrees(),
+        distance_km: MEAN_APOGEE_DISTANCE_KM,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_moon() {
+        // Meeus, example 47.a: 1992 April 12, 0h TD, before nutation
+        let moon = moon(2_448_724.5);
+        assert!(approx_eq!(f64, moon.longitude, 133.162_655, epsilon = 1e-6), "{}", moon.longitude);
+        assert!(approx_eq!(f64, moon.latitude, -3.229_126, epsilon = 1e-6), "{}", moon.latitude);
+        assert!(approx_eq!(f64, moon.distance_km, 368_409.7, epsilon = 0.1), "{}", moon.distance_km);
+    }
+}
@@ -0,0 +1,114 @@
+//! Nutation and obliquity of the ecliptic
+//!
+//! Nutation uses the largest terms of the IAU 1980 theory (Meeus, chapter 22),
+//! good to about 0.05". The mean obliquity uses Laskar's polynomial, valid for
+//! ten thousand years around J2000.
+
+use super::J2000;
+
+/// Arguments (D, M, M', F, Ω) followed by the longitude coefficients (A + B·T)
+/// and the obliquity coefficients (C + D·T), in units of 0.0001"
+#[rustfmt::skip]
+const TERMS: &[([f64; 5], f64, f64, f64, f64)] = &[
+    ([ 0.0,  0.0,  0.0, 0.0, 1.0], -171996.0, -174.2, 92025.0,  8.9),
+    ([-2.0,  0.0,  0.0, 2.0, 2.0],  -13187.0,   -1.6,  5736.0, -3.1),
+    ([ 0.0,  0.0,  0.0, 2.0, 2.0],   -2274.0,   -0.2,   977.0, -0.5),
+    ([ 0.0,  0.0,  0.0, 0.0, 2.0],    2062.0,    0.2,  -895.0,  0.5),
+    ([ 0.0,  1.0,  0.0, 0.0, 0.0],    1426.0,   -3.4,    54.0, -0.1),
+    ([ 0.0,  0.0,  1.0, 0.0, 0.0],     712.0,    0.1,    -7.0,  0.0),
+    ([-2.0,  1.0,  0.0, 2.0, 2.0],    -517.0,    1.2,   224.0, -0.6),
+    ([ 0.0,  0.0,  0.0, 2.0, 1.0],    -386.0,   -0.4,   200.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 2.0, 2.0],    -301.0,    0.0,   129.0, -0.1),
+    ([-2.0, -1.0,  0.0, 2.0, 2.0],     217.0,   -0.5,   -95.0,  0.3),
+    ([-2.0,  0.0,  1.0, 0.0, 0.0],    -158.0,    0.0,     0.0,  0.0),
+    ([-2.0,  0.0,  0.0, 2.0, 1.0],     129.0,    0.1,   -70.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 2.0, 2.0],     123.0,    0.0,   -53.0,  0.0),
+    ([ 2.0,  0.0,  0.0, 0.0, 0.0],      63.0,    0.0,     0.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 0.0, 1.0],      63.0,    0.1,   -33.0,  0.0),
+    ([ 2.0,  0.0, -1.0, 2.0, 2.0],     -59.0,    0.0,    26.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 0.0, 1.0],     -58.0,   -0.1,    32.0,  0.0),
+    ([ 0.0,  0.0,  1.0, 2.0, 1.0],     -51.0,    0.0,    27.0,  0.0),
+    ([-2.0,  0.0,  2.0, 0.0, 0.0],      48.0,    0.0,     0.0,  0.0),
+    ([ 0.0,  0.0, -2.0, 2.0, 1.0],      46.0,    0.0,   -24.0,  0.0),
+    ([ 2.0,  0.0,  0.0, 2.0, 2.0],     -38.0,    0.0,    16.0,  0.0),
+    ([ 0.0,  0.0,  2.0, 2.0, 2.0],     -31.0,    0.0,    13.0,  0.0),
+    ([ 0.0,  0.0,  2.0, 0.0, 0.0],      29.0,    0.0,     0.0,  0.0),
+    ([-2.0,  0.0,  1.0, 2.0, 2.0],      29.0,    0.0,   -12.0,  0.0),
+    ([ 0.0,  0.0,  0.0, 2.0, 0.0],      26.0,    0.0,     0.0,  0.0),
+    ([-2.0,  0.0,  0.0, 2.0, 0.0],     -22.0,    0.0,     0.0,  0.0),
+    ([ 0.0,  0.0, -1.0, 2.0, 1.0],      21.0,    0.0,   -10.0,  0.0),
+    ([ 0.0,  2.0,  0.0, 0.0, 0.0],      17.0,   -0.1,     0.0,  0.0),
+    ([ 2.0,  0.0, -1.0, 0.0, 1.0],      16.0,    0.0,    -8.0,  0.0),
+    ([-2.0,  2.0,  0.0, 2.0, 2.0],     -16.0,    0.1,     7.0,  0.0),
+    ([ 0.0,  1.0,  0.0, 0.0, 1.0],     -15.0,    0.0,     9.0,  0.0),
+];
+
+/// Nutation in longitude and obliquity, both in degrees
+pub fn nutation(jd_tt: f64) -> (f64, f64) {
+    let t = (jd_tt - J2000) / 36_525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let args = [
+        297.85036 + 445_267.111480 * t - 0.0019142 * t2 + t3 / 189_474.0,
+        357.52772 + 35_999.050340 * t - 0.0001603 * t2 - t3 / 300_000.0,
+        134.96298 + 477_198.867398 * t + 0.0086972 * t2 + t3 / 56_250.0,
+        93.27191 + 483_202.017538 * t - 0.0036825 * t2 + t3 / 327_270.0,
+        125.04452 - 1_934.136261 * t + 0.0020708 * t2 + t3 / 450_000.0,
+    ];
+
+    let mut delta_psi = 0.0;
+    let mut delta_epsilon = 0.0;
+
+    for (multipliers, a, b, c, d) in TERMS {
+        let argument: f64 = multipliers
+            .iter()
+            .zip(args.iter())
+            .map(|(m, arg)| m * arg)
+            .sum::<f64>()
+            .to_radians();
+        delta_psi += (a + b * t) * argument.sin();
+        delta_epsilon += (c + d * t) * argument.cos();
+    }
+
+    (delta_psi / 36_000_000.0, delta_epsilon / 36_000_000.0)
+}
+
+/// Mean obliquity of the ecliptic in degrees
+pub fn mean_obliquity(jd_tt: f64) -> f64 {
+    const COEFFICIENTS: [f64; 10] = [
+        -4680.93, -1.55, 1999.25, -51.38, -249.67, -39.05, 7.12, 27.87, 5.79, 2.45,
+    ];
+
+    let u = (jd_tt - J2000) / 3_652_500.0;
+    let mut seconds = 0.0;
+    let mut power = u;
+    for coefficient in COEFFICIENTS {
+        seconds += coefficient * power;
+        power *= u;
+    }
+
+    23.0 + 26.0 / 60.0 + (21.448 + seconds) / 3600.0
+}
+
+/// True obliquity of the ecliptic (mean obliquity plus nutation) in degrees
+pub fn true_obliquity(jd_tt: f64) -> f64 {
+    mean_obliquity(jd_tt) + nutation(jd_tt).1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_nutation_and_obliquity() {
+        // Meeus, example 22.a: 1987 April 10, 0h TD
+        let jd_tt = 2_446_895.5;
+        let (delta_psi, delta_epsilon) = nutation(jd_tt);
+        assert!(approx_eq!(f64, delta_psi * 3600.0, -3.788, epsilon = 0.01), "{}", delta_psi * 3600.0);
+        assert!(approx_eq!(f64, delta_epsilon * 3600.0, 9.443, epsilon = 0.01), "{}", delta_epsilon * 3600.0);
+        assert!(approx_eq!(f64, mean_obliquity(jd_tt), 23.0 + 26.0 / 60.0 + 27.407 / 3600.0, epsilon = 1e-6));
+        assert!(approx_eq!(f64, true_obliquity(jd_tt), 23.0 + 26.0 / 60.0 + 36.850 / 3600.0, epsilon = 1e-5));
+    }
+}
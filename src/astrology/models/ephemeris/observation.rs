@@ -0,0 +1,178 @@
+//! Apparent size, brightness and phase of the bodies
+//!
+//! The phase angle (Sun–body–Earth) and the elongation (Sun–Earth–body) follow
+//! from the geocentric positions of the body and the Sun. The visual
+//! magnitudes of the planets use the formulas of the Astronomical Almanac
+//! (Meeus, chapter 41), with the rings of Saturn; the Moon uses Allen's
+//! phase law and the asteroids the H, G system with G = 0.15.
+
+use crate::astrology::models::planet::Planet;
+
+use super::{EclipticPosition, J2000};
+
+/// Kilometres in an AU
+const AU_KM: f64 = 149_597_870.7;
+
+/// Mean distance of the Moon in AU (384 400 km), at which its magnitude is given
+const MOON_MEAN_DISTANCE_AU: f64 = 384_400.0 / AU_KM;
+
+/// Slope parameter of the asteroids in the H, G system
+const SLOPE: f64 = 0.15;
+
+/// What an observer on the Earth sees of a body
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observation {
+    /// Apparent diameter in arcseconds
+    pub apparent_size: f64,
+    /// Visual magnitude
+    pub magnitude: f64,
+    /// Illuminated fraction of the disc (0-1)
+    pub phase: f64,
+    /// Angle Sun–body–Earth in degrees (0 at full phase)
+    pub phase_angle: f64,
+    /// Angular distance from the Sun in degrees (0-180)
+    pub elongation: f64,
+    /// Whether the body is east of the Sun: seen in the evening, the Moon waxing
+    pub is_east_of_sun: bool,
+}
+
+/// Equatorial radius in km and brightness model of the bodies with a disc
+fn body(planet: Planet) -> Option<(f64, Brightness)> {
+    Some(match planet {
+        Planet::Sun => (696_000.0, Brightness::Sun),
+        Planet::Moon => (1_737.4, Brightness::Moon),
+        Planet::Mercury => (2_439.7, Brightness::Planet(-0.42, [0.0380, -0.000_273, 0.000_002])),
+        Planet::Venus => (6_051.8, Brightness::Planet(-4.40, [0.0009, 0.000_239, -0.000_000_65])),
+        Planet::Mars => (3_396.2, Brightness::Planet(-1.52, [0.016, 0.0, 0.0])),
+        Planet::Jupiter => (71_492.0, Brightness::Planet(-9.40, [0.005, 0.0, 0.0])),
+        Planet::Saturn => (60_268.0, Brightness::Saturn),
+        Planet::Uranus => (25_559.0, Brightness::Planet(-7.19, [0.0; 3])),
+        Planet::Neptune => (24_764.0, Brightness::Planet(-6.87, [0.0; 3])),
+        Planet::Pluto => (1_188.3, Brightness::Planet(-1.00, [0.0; 3])),
+        Planet::Ceres => (469.7, Brightness::Asteroid(3.34)),
+        Planet::Pallas => (256.0, Brightness::Asteroid(4.13)),
+        Planet::Juno => (123.3, Brightness::Asteroid(5.33)),
+        Planet::Vesta => (262.7, Brightness::Asteroid(3.20)),
+        Planet::Chiron => (103.0, Brightness::Asteroid(5.92)),
+        _ => return None,
+    })
+}
+
+/// Whether a body has a disc to observe, rather than being a point
+pub fn has_disc(planet: Planet) -> bool {
+    body(planet).is_some()
+}
+
+/// How the magnitude of a body depends on its distances and phase angle
+#[derive(Debug, Clone, Copy)]
+enum Brightness {
+    Sun,
+    Moon,
+    /// Magnitude at 1 AU from the Sun and the Earth, and the coefficients of
+    /// the phase angle, its square and its cube
+    Planet(f64, [f64; 3]),
+    /// The planet and its rings
+    Saturn,
+    /// Absolute magnitude H
+    Asteroid(f64),
+}
+
+/// Observe a body from its geocentric position and that of the Sun at a
+/// Julian day (UT); points without a disc (the nodes, the Moon's apogee, the
+/// Hamburg points and the angles) are not observable
+pub fn observe(planet: Planet, position: &EclipticPosition, sun: &EclipticPosition, jd_ut: f64) -> Option<Observation> {
+    let (radius_km, brightness) = body(planet)?;
+    let distance = position.distance_au;
+    if distance <= 0.0 {
+        return None;
+    }
+
+    let apparent_size = 2.0 * (radius_km / (distance * AU_KM)).min(1.0).asin().to_degrees() * 3600.0;
+    let is_east_of_sun = planet != Planet::Sun && (position.longitude - sun.longitude).rem_euclid(360.0) < 180.0;
+
+    // Heliocentric vector of the body, from its geocentric one and the Sun's;
+    // the Sun itself is always seen full
+    let geocentric = vector(position);
+    let solar = vector(sun);
+    let heliocentric = [geocentric[0] - solar[0], geocentric[1] - solar[1], geocentric[2] - solar[2]];
+    let r = norm(heliocentric);
+    let (elongation, phase_angle) = if r > 0.0 {
+        (angle_between(geocentric, solar), angle_between(heliocentric, geocentric))
+    } else {
+        (0.0, 0.0)
+    };
+    let phase = (1.0 + phase_angle.to_radians().cos()) / 2.0;
+
+    let magnitude = match brightness {
+        Brightness::Sun => -26.74 + 5.0 * distance.log10(),
+        Brightness::Moon => {
+            -12.73 + 0.026 * phase_angle + 4e-9 * phase_angle.powi(4) + 5.0 * (distance / MOON_MEAN_DISTANCE_AU).log10()
+        }
+        Brightness::Planet(base, [a, b, c]) => {
+            let i = phase_angle;
+            base + 5.0 * (r * distance).log10() + a * i + b * i * i + c * i * i * i
+        }
+        Brightness::Saturn => saturn_magnitude(position, heliocentric, r, jd_ut),
+        Brightness::Asteroid(h) => {
+            let tan_half = (phase_angle / 2.0).to_radians().tan();
+            let phi1 = (-3.33 * tan_half.powf(0.63)).exp();
+            let phi2 = (-1.87 * tan_half.powf(1.22)).exp();
+            h + 5.0 * (r * distance).log10() - 2.5 * ((1.0 - SLOPE) * phi1 + SLOPE * phi2).log10()
+        }
+    };
+
+    Some(Observation {
+        apparent_size,
+        magnitude,
+        phase,
+        phase_angle,
+        elongation,
+        is_east_of_sun,
+    })
+}
+
+/// Magnitude of Saturn with its rings, which brighten the planet the more
+/// they open towards the Earth (Meeus, chapters 41 and 45)
+fn saturn_magnitude(position: &EclipticPosition, heliocentric: [f64; 3], r: f64, jd_ut: f64) -> f64 {
+    let t = (jd_ut - J2000) / 36_525.0;
+    let inclination = (28.075_216 - 0.012_998 * t + 0.000_004 * t * t).to_radians();
+    let node = 169.508_470 + 1.394_681 * t + 0.000_412 * t * t;
+    let (sin_i, cos_i) = inclination.sin_cos();
+
+    // Saturnicentric longitude in the ring plane of a direction, and the
+    // ring tilt towards it
+    let ring = |longitude: f64, latitude: f64| {
+        let (sin_l, cos_l) = (longitude - node).to_radians().sin_cos();
+        let (sin_b, cos_b) = latitude.to_radians().sin_cos();
+        let u = (sin_i * sin_b + cos_i * cos_b * sin_l).atan2(cos_b * cos_l).to_degrees();
+        let tilt = (sin_i * cos_b * sin_l - cos_i * sin_b).asin();
+        (u, tilt)
+    };
+
+    let (u_earth, tilt) = ring(position.longitude, position.latitude);
+    let helio_longitude = heliocentric[1].atan2(heliocentric[0]).to_degrees();
+    let helio_latitude = (heliocentric[2] / r).asin().to_degrees();
+    let (u_sun, _) = ring(helio_longitude, helio_latitude);
+    let delta_u = ((u_sun - u_earth + 540.0).rem_euclid(360.0) - 180.0).abs();
+
+    let sin_tilt = tilt.sin().abs();
+    -8.88 + 5.0 * (r * position.distance_au).log10() + 0.044 * delta_u - 2.60 * sin_tilt + 1.25 * sin_tilt * sin_tilt
+}
+
+/// Rectangular ecliptic coordinates in AU
+fn vector(position: &EclipticPosition) -> [f64; 3] {
+    let (sin_l, cos_l) = position.longitude.to_radians().sin_cos();
+    let (sin_b, cos_b) = position.latitude.to_radians().sin_cos();
+    let r = position.distance_au;
+    [r * cos_b * cos_l, r * cos_b * sin_l, r * sin_b]
+}
+
+fn norm(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+/// Angle in degrees between two vectors
+fn angle_between(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let dot = a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+    (dot / (norm(a) * norm(b))).clamp(-1.0, 1.0).acos().to_degrees()
+}
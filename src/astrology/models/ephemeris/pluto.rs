@@ -0,0 +1,110 @@
+//! Analytic theory of Pluto
+//!
+//! Periodic terms from Meeus (Astronomical Algorithms, chapter 37), fitted to
+//! the DE200 ephemeris. They are only valid between 1885 and 2099 and give
+//! heliocentric coordinates referred to the ecliptic and equinox of J2000,
+//! which [`heliocentric`] precesses to the equinox of date.
+
+use super::precession::precess_ecliptic;
+use super::vsop87::Heliocentric;
+use super::J2000;
+
+/// Multipliers of (J, S, P) and the sine/cosine amplitudes for longitude
+/// (1e-6°), latitude (1e-6°) and radius vector (1e-7 AU)
+type Term = ([f64; 3], [f64; 2], [f64; 2], [f64; 2]);
+
+#[rustfmt::skip]
+const TERMS: &[Term] = &[
+    ([0.0, 0.0, 1.0], [-19799805.0, 19850055.0], [-5452852.0, -14974862.0], [66865439.0, 68951812.0]),
+    ([0.0, 0.0, 2.0], [897144.0, -4954829.0], [3527812.0, 1672790.0], [-11827535.0, -332538.0]),
+    ([0.0, 0.0, 3.0], [611149.0, 1211027.0], [-1050748.0, 327647.0], [1593179.0, -1438890.0]),
+    ([0.0, 0.0, 4.0], [-341243.0, -189585.0], [178690.0, -292153.0], [-18444.0, 483220.0]),
+    ([0.0, 0.0, 5.0], [129287.0, -34992.0], [18650.0, 100340.0], [-65977.0, -85431.0]),
+    ([0.0, 0.0, 6.0], [-38164.0, 30893.0], [-30697.0, -25823.0], [31174.0, -6032.0]),
+    ([0.0, 1.0, -1.0], [20442.0, -9987.0], [4878.0, 11248.0], [-5794.0, 22161.0]),
+    ([0.0, 1.0, 0.0], [-4063.0, -5071.0], [226.0, -64.0], [4601.0, 4032.0]),
+    ([0.0, 1.0, 1.0], [-6016.0, -3336.0], [2030.0, -836.0], [-1729.0, 234.0]),
+    ([0.0, 1.0, 2.0], [-3956.0, 3039.0], [69.0, -604.0], [-415.0, 702.0]),
+    ([0.0, 1.0, 3.0], [-667.0, 3572.0], [-247.0, -567.0], [239.0, 723.0]),
+    ([0.0, 2.0, -2.0], [1276.0, 501.0], [-57.0, 1.0], [67.0, -67.0]),
+    ([0.0, 2.0, -1.0], [1152.0, -917.0], [-122.0, 175.0], [1034.0, -451.0]),
+    ([0.0, 2.0, 0.0], [630.0, -1277.0], [-49.0, -164.0], [-129.0, 504.0]),
+    ([1.0, -1.0, 0.0], [2571.0, -459.0], [-197.0, 199.0], [480.0, -231.0]),
+    ([1.0, -1.0, 1.0], [899.0, -1449.0], [-25.0, 217.0], [2.0, -441.0]),
+    ([1.0, 0.0, -3.0], [-1016.0, 1043.0], [589.0, -248.0], [-3359.0, 265.0]),
+    ([1.0, 0.0, -2.0], [-2343.0, -1012.0], [-269.0, 711.0], [7856.0, -7832.0]),
+    ([1.0, 0.0, -1.0], [7042.0, 788.0], [185.0, 193.0], [36.0, 45763.0]),
+    ([1.0, 0.0, 0.0], [1199.0, -338.0], [315.0, 807.0], [8663.0, 8547.0]),
+    ([1.0, 0.0, 1.0], [418.0, -67.0], [-130.0, -43.0], [-809.0, -769.0]),
+    ([1.0, 0.0, 2.0], [120.0, -274.0], [5.0, 3.0], [263.0, -144.0]),
+    ([1.0, 0.0, 3.0], [-60.0, -159.0], [2.0, 17.0], [-126.0, 32.0]),
+    ([1.0, 0.0, 4.0], [-82.0, -29.0], [2.0, 5.0], [-35.0, -16.0]),
+    ([1.0, 1.0, -3.0], [-36.0, -29.0], [2.0, 3.0], [-19.0, -4.0]),
+    ([1.0, 1.0, -2.0], [-40.0, 7.0], [3.0, 1.0], [-15.0, 8.0]),
+    ([1.0, 1.0, -1.0], [-14.0, 22.0], [2.0, -1.0], [-4.0, 12.0]),
+    ([1.0, 1.0, 0.0], [4.0, 13.0], [1.0, -1.0], [5.0, 6.0]),
+    ([1.0, 1.0, 1.0], [5.0, 2.0], [0.0, -1.0], [3.0, 1.0]),
+    ([1.0, 1.0, 3.0], [-1.0, 0.0], [0.0, 0.0], [6.0, -2.0]),
+    ([2.0, 0.0, -6.0], [2.0, 0.0], [0.0, -2.0], [2.0, 2.0]),
+    ([2.0, 0.0, -5.0], [-4.0, 5.0], [2.0, 2.0], [-2.0, -2.0]),
+    ([2.0, 0.0, -4.0], [4.0, -7.0], [-7.0, 0.0], [14.0, 13.0]),
+    ([2.0, 0.0, -3.0], [14.0, 24.0], [10.0, -8.0], [-63.0, 13.0]),
+    ([2.0, 0.0, -2.0], [-49.0, -34.0], [-3.0, 20.0], [136.0, -236.0]),
+    ([2.0, 0.0, -1.0], [163.0, -48.0], [6.0, 5.0], [273.0, 1065.0]),
+    ([2.0, 0.0, 0.0], [9.0, -24.0], [14.0, 17.0], [251.0, 149.0]),
+    ([2.0, 0.0, 1.0], [-4.0, 1.0], [-2.0, 0.0], [-25.0, -9.0]),
+    ([2.0, 0.0, 2.0], [-3.0, 1.0], [0.0, 0.0], [9.0, -2.0]),
+    ([2.0, 0.0, 3.0], [1.0, 3.0], [0.0, 0.0], [-8.0, 7.0]),
+    ([3.0, 0.0, -2.0], [-3.0, -1.0], [0.0, 1.0], [7.0, -1.0]),
+    ([3.0, 0.0, -1.0], [5.0, -3.0], [0.0, 0.0], [19.0, 35.0]),
+    ([3.0, 0.0, 0.0], [0.0, 0.0], [1.0, 0.0], [10.0, 3.0]),
+];
+
+/// Heliocentric position of Pluto for a Julian day (TT), referred to the
+/// mean ecliptic and equinox of date
+pub fn heliocentric(jd_tt: f64) -> Heliocentric {
+    let t = (jd_tt - J2000) / 36_525.0;
+    let jupiter = 34.35 + 3_034.905_7 * t;
+    let saturn = 50.08 + 1_222.113_8 * t;
+    let pluto = 238.96 + 144.9600 * t;
+
+    let mut longitude = 0.0;
+    let mut latitude = 0.0;
+    let mut radius = 0.0;
+
+    for ([j, s, p], lon, lat, rad) in TERMS {
+        let alpha = (j * jupiter + s * saturn + p * pluto).to_radians();
+        let (sin, cos) = alpha.sin_cos();
+        longitude += lon[0] * sin + lon[1] * cos;
+        latitude += lat[0] * sin + lat[1] * cos;
+        radius += rad[0] * sin + rad[1] * cos;
+    }
+
+    let longitude = 238.958_116 + 144.96 * t + longitude * 1e-6;
+    let latitude = -3.908_239 + latitude * 1e-6;
+    let radius = 40.724_134_6 + radius * 1e-7;
+
+    let (longitude, latitude) = precess_ecliptic(longitude, latitude, J2000, jd_tt);
+
+    Heliocentric {
+        longitude: longitude.to_radians(),
+        latitude: latitude.to_radians(),
+        radius,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_heliocentric() {
+        // Meeus, example 37.a: 1992 October 13, 0h TD, referred to J2000
+        let pluto = heliocentric(2_448_908.5);
+        let (longitude, latitude) = precess_ecliptic(pluto.longitude.to_degrees(), pluto.latitude.to_degrees(), 2_448_908.5, J2000);
+        assert!(approx_eq!(f64, longitude, 232.740_71, epsilon = 1e-5), "{longitude}");
+        assert!(approx_eq!(f64, latitude, 14.587_82, epsilon = 1e-5), "{latitude}");
+        assert!(approx_eq!(f64, pluto.radius, 29.711_111, epsilon = 1e-6), "{}", pluto.radius);
+    }
+}
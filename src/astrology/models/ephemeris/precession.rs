@@ -0,0 +1,81 @@
+//! Precession of ecliptic and equatorial coordinates
+//!
+//! Rigorous reductions between equinoxes (Meeus, Astronomical Algorithms,
+//! chapter 21), based on the IAU 1976 precession constants.
+
+use super::J2000;
+
+/// Precess ecliptic (longitude, latitude) in degrees from one equinox to another
+pub fn precess_ecliptic(longitude: f64, latitude: f64, jd_from: f64, jd_to: f64) -> (f64, f64) {
+    let big_t = (jd_from - J2000) / 36_525.0;
+    let t = (jd_to - jd_from) / 36_525.0;
+    let (big_t2, t2) = (big_t * big_t, t * t);
+
+    let eta = ((47.002_9 - 0.066_03 * big_t + 0.000_598 * big_t2) * t
+        + (-0.033_02 + 0.000_598 * big_t) * t2
+        + 0.000_060 * t2 * t)
+        / 3600.0;
+    let pi = 174.876_384 + (3_289.478_9 * big_t + 0.606_22 * big_t2) / 3600.0
+        - ((869.808_9 + 0.504_91 * big_t) * t - 0.035_36 * t2) / 3600.0;
+    let p = general_precession(jd_from, jd_to);
+
+    let eta = eta.to_radians();
+    let beta = latitude.to_radians();
+    let delta = (pi - longitude).to_radians();
+
+    let a = eta.cos() * beta.cos() * delta.sin() - eta.sin() * beta.sin();
+    let b = beta.cos() * delta.cos();
+    let c = eta.cos() * beta.sin() + eta.sin() * beta.cos() * delta.sin();
+
+    let longitude = (p + pi - a.atan2(b).to_degrees()).rem_euclid(360.0);
+    (longitude, c.asin().to_degrees())
+}
+
+/// General precession in longitude in degrees from one equinox to another:
+/// the amount by which the longitude of a fixed star grows in between
+pub fn general_precession(jd_from: f64, jd_to: f64) -> f64 {
+    let big_t = (jd_from - J2000) / 36_525.0;
+    let t = (jd_to - jd_from) / 36_525.0;
+    let t2 = t * t;
+
+    ((5_029.096_6 + 2.222_26 * big_t - 0.000_042 * big_t * big_t) * t
+        + (1.111_13 - 0.000_042 * big_t) * t2
+        - 0.000_006 * t2 * t)
+        / 3600.0
+}
+
+/// Precess equatorial (right ascension, declination) in degrees from J2000 to
+/// the mean equator and equinox of a Julian day
+pub fn precess_equatorial_from_j2000(right_ascension: f64, declination: f64, jd_to: f64) -> (f64, f64) {
+    let t = (jd_to - J2000) / 36_525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let zeta = ((2_306.218_1 * t + 0.301_88 * t2 + 0.017_998 * t3) / 3600.0).to_radians();
+    let z = ((2_306.218_1 * t + 1.094_68 * t2 + 0.018_203 * t3) / 3600.0).to_radians();
+    let theta = ((2_004.310_9 * t - 0.426_65 * t2 - 0.041_833 * t3) / 3600.0).to_radians();
+
+    let alpha = right_ascension.to_radians() + zeta;
+    let delta = declination.to_radians();
+
+    let a = delta.cos() * alpha.sin();
+    let b = theta.cos() * delta.cos() * alpha.cos() - theta.sin() * delta.sin();
+    let c = theta.sin() * delta.cos() * alpha.cos() + theta.cos() * delta.sin();
+
+    let right_ascension = (a.atan2(b) + z).to_degrees().rem_euclid(360.0);
+    (right_ascension, c.clamp(-1.0, 1.0).asin().to_degrees())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_precess_ecliptic() {
+        // Meeus, example 21.c: Venus from J2000 to -214 June 30, 0h TD
+        let (longitude, latitude) = precess_ecliptic(149.481_94, 1.765_49, J2000, 1_643_074.5);
+        assert!(approx_eq!(f64, longitude, 118.704, epsilon = 0.001), "{longitude}");
+        assert!(approx_eq!(f64, latitude, 1.615, epsilon = 0.001), "{latitude}");
+    }
+}
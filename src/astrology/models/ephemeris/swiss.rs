@@ -0,0 +1,160 @@
+//! Swiss Ephemeris backend
+//!
+//! Positions are computed with the `swisseph` crate from the compressed `.se1`
+//! data files found in [`ephemeris_path`].
+
+use std::sync::Once;
+
+use log::debug;
+use swisseph::{swe, Body, Seflg};
+
+use super::{ephemeris_path, AnalyticEphemeris, EclipticPosition, EphemerisBackend, EphemerisError};
+use crate::astrology::models::minor_body::MinorBody;
+use crate::astrology::models::planet::Planet;
+
+/// Swiss Ephemeris body number of the White Moon (Selena), not covered by `swisseph::Body`
+const SE_WHITE_MOON: u32 = 56;
+
+/// Offset added to an MPC number to get the Swiss Ephemeris body number
+const SE_AST_OFFSET: u32 = 10_000;
+
+static EPHEMERIS_PATH_INIT: Once = Once::new();
+
+/// Ephemeris backed by the Swiss Ephemeris data files
+#[derive(Debug, Clone, Copy)]
+pub struct SwissEphemeris;
+
+impl SwissEphemeris {
+    /// Create the backend, pointing the library at the data directory on first use
+    pub fn new() -> Self {
+        EPHEMERIS_PATH_INIT.call_once(|| {
+            let path = ephemeris_path();
+            debug!("Using Swiss Ephemeris data path {}", path.display());
+            swe::set_ephe_path(&path.to_string_lossy());
+        });
+
+        Self
+    }
+}
+
+impl Default for SwissEphemeris {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EphemerisBackend for SwissEphemeris {
+    fn name(&self) -> &'static str {
+        "Swiss Ephemeris"
+    }
+
+    fn calculate(&self, planet: Planet, jd_ut: f64) -> Result<EclipticPosition, EphemerisError> {
+        // The library uses Neely's revised elements for the Hamburg points;
+        // both backends use the classic Witte/Sieggrün elements instead
+        if planet.is_uranian_point() {
+            return AnalyticEphemeris::new().calculate(planet, jd_ut);
+        }
+
+        let body = swiss_body(&planet).ok_or(EphemerisError::UnsupportedBody(planet))?;
+
+        calc_ut(jd_ut, body, Seflg::none()).map_err(|message| EphemerisError::Calculation { planet, message })
+    }
+
+    fn calculate_minor_body(&self, body: &MinorBody, jd_ut: f64) -> Result<EclipticPosition, EphemerisError> {
+        // Asteroid files live in astNNN/seNNNNN.se1 below the ephemeris path
+        match calc_ut(jd_ut, SE_AST_OFFSET + body.number, Seflg::none()) {
+            Ok(position) => Ok(position),
+            Err(message) if body.elements.is_some() => {
+                debug!("No asteroid file for {} ({}), using orbital elements", body, message);
+                AnalyticEphemeris::new().calculate_minor_body(body, jd_ut)
+            }
+            Err(message) => Err(EphemerisError::MinorBody { body: body.to_string(), message }),
+        }
+    }
+
+    fn calculate_heliocentric(&self, planet: Planet, jd_ut: f64, barycentric: bool) -> Result<EclipticPosition, EphemerisError> {
+        if planet.is_uranian_point() {
+            return AnalyticEphemeris::new().calculate_heliocentric(planet, jd_ut, barycentric);
+        }
+
+        // The library returns the centre itself for the Sun and projects the
+        // nodes and apsides, which have no position of their own
+        let body = match planet {
+            Planet::Sun if !barycentric => None,
+            Planet::TrueNode | Planet::MeanNode | Planet::BlackMoonLilith | Planet::WhiteMoonSelena => None,
+            _ => swiss_body(&planet),
+        }
+        .ok_or(EphemerisError::UnsupportedBody(planet))?;
+
+        calc_ut(jd_ut, body, center_flag(barycentric)).map_err(|message| EphemerisError::Calculation { planet, message })
+    }
+
+    fn calculate_minor_body_heliocentric(&self, body: &MinorBody, jd_ut: f64, barycentric: bool) -> Result<EclipticPosition, EphemerisError> {
+        match calc_ut(jd_ut, SE_AST_OFFSET + body.number, center_flag(barycentric)) {
+            Ok(position) => Ok(position),
+            Err(message) if body.elements.is_some() => {
+                debug!("No asteroid file for {} ({}), using orbital elements", body, message);
+                AnalyticEphemeris::new().calculate_minor_body_heliocentric(body, jd_ut, barycentric)
+            }
+            Err(message) => Err(EphemerisError::MinorBody { body: body.to_string(), message }),
+        }
+    }
+}
+
+/// Flag placing the observer at the centre of the Sun or the barycentre
+fn center_flag(barycentric: bool) -> Seflg {
+    if barycentric {
+        Seflg::BARYCTR
+    } else {
+        Seflg::HELCTR
+    }
+}
+
+/// Calculate the position of a Swiss Ephemeris body number, with extra flags
+/// choosing the centre
+fn calc_ut(jd_ut: f64, body: u32, center: Seflg) -> Result<EclipticPosition, String> {
+    let flags = (Seflg::SWIEPH | Seflg::SPEED | center).bits();
+    let result = swe::calc_ut(jd_ut, body, flags)?;
+    let [longitude, latitude, distance_au, speed, speed_latitude, _] = result.out;
+
+    Ok(EclipticPosition {
+        longitude,
+        latitude,
+        distance_au,
+        speed,
+        speed_latitude,
+    })
+}
+
+/// Map a body to its Swiss Ephemeris body number
+fn swiss_body(planet: &Planet) -> Option<u32> {
+    let body = match planet {
+        Planet::Sun => Body::Sun,
+        Planet::Moon => Body::Moon,
+        Planet::Mercury => Body::Mercury,
+        Planet::Venus => Body::Venus,
+        Planet::Mars => Body::Mars,
+        Planet::Jupiter => Body::Jupiter,
+        Planet::Saturn => Body::Saturn,
+        Planet::Uranus => Body::Uranus,
+        Planet::Neptune => Body::Neptune,
+        Planet::Pluto => Body::Pluto,
+        Planet::Ceres => Body::Ceres,
+        Planet::Pallas => Body::Pallas,
+        Planet::Juno => Body::Juno,
+        Planet::Vesta => Body::Vesta,
+        Planet::Chiron => Body::Chiron,
+        Planet::TrueNode => Body::TrueNode,
+        Planet::MeanNode => Body::MeanNode,
+        Planet::BlackMoonLilith => Body::MeanApog,
+        Planet::WhiteMoonSelena => return Some(SE_WHITE_MOON),
+
+        // Angles need a house calculation; the Hamburg points are computed
+        // from their own elements
+        Planet::Cupido | Planet::Hades | Planet::Zeus | Planet::Kronos |
+        Planet::Apollon | Planet::Admetos | Planet::Vulkanus | Planet::Poseidon |
+        Planet::Vertex | Planet::EastPoint | Planet::Ascendant | Planet::MC => return None,
+    };
+
+    Some(body as u32)
+}
@@ -0,0 +1,302 @@
+//! Fixed star catalogue
+//!
+//! Stars are kept as data rather than [`Planet`](crate::astrology::models::planet::Planet)
+//! variants: a catalogue holds J2000 (ICRS) positions with proper motions and
+//! magnitudes, and each star is reduced to its apparent position for the
+//! chart date (proper motion, precession, nutation and annual aberration).
+//! Charts refer to stars through [`ChartPoint::Star`].
+
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::ephemeris::nutation::{nutation, true_obliquity};
+use crate::astrology::models::ephemeris::precession::precess_equatorial_from_j2000;
+use crate::astrology::models::ephemeris::vsop87::{self, VsopBody};
+use crate::astrology::models::ephemeris::{delta_t, J2000};
+
+/// Catalogue bundled with the application
+const BUNDLED_CATALOGUE: &str = include_str!("fixed_stars.csv");
+
+/// Constant of annual aberration in arcseconds
+const ABERRATION_CONSTANT: f64 = 20.495_52;
+
+/// Errors that can occur while loading a star catalogue
+#[derive(Debug, Error)]
+pub enum FixedStarError {
+    #[error("failed to read star catalogue: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid star catalogue entry on line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// A catalogue entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixedStar {
+    /// Traditional name, e.g. "Regulus"
+    pub name: String,
+    /// Bayer designation, e.g. "alLeo"
+    pub designation: String,
+    /// Right ascension at J2000 in degrees
+    pub right_ascension: f64,
+    /// Declination at J2000 in degrees
+    pub declination: f64,
+    /// Proper motion in right ascension (including cos δ) in mas/year
+    pub proper_motion_ra: f64,
+    /// Proper motion in declination in mas/year
+    pub proper_motion_dec: f64,
+    /// Visual magnitude
+    pub magnitude: f64,
+}
+
+/// Apparent position of a fixed star for a moment in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StarPosition {
+    /// Name of the star
+    pub name: String,
+    /// Ecliptic longitude in degrees (0-360)
+    pub longitude: f64,
+    /// Ecliptic latitude in degrees
+    pub latitude: f64,
+    /// Right ascension in degrees (0-360)
+    pub right_ascension: f64,
+    /// Declination in degrees
+    pub declination: f64,
+    /// Visual magnitude
+    pub magnitude: f64,
+}
+
+impl FixedStar {
+    /// Get the chart point referring to this star
+    pub fn chart_point(&self) -> ChartPoint {
+        ChartPoint::Star(self.name.clone())
+    }
+
+    /// Calculate the apparent position of the star for a Julian day (UT)
+    pub fn position(&self, jd_ut: f64) -> StarPosition {
+        let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+        let years = (jd_tt - J2000) / 365.25;
+
+        // Proper motion at the J2000 equinox
+        let declination = self.declination + self.proper_motion_dec * years / 3_600_000.0;
+        let right_ascension = self.right_ascension
+            + self.proper_motion_ra * years / 3_600_000.0 / self.declination.to_radians().cos();
+
+        // Mean place of date, then nutation and aberration (Meeus, chapter 23)
+        let (alpha, delta) = precess_equatorial_from_j2000(right_ascension, declination, jd_tt);
+        let (delta_psi, delta_epsilon) = nutation(jd_tt);
+        let epsilon = true_obliquity(jd_tt).to_radians();
+        let (sin_a, cos_a) = alpha.to_radians().sin_cos();
+        let (sin_d, cos_d) = delta.to_radians().sin_cos();
+        let tan_d = sin_d / cos_d;
+
+        let nutation_ra = (epsilon.cos() + epsilon.sin() * sin_a * tan_d) * delta_psi - cos_a * tan_d * delta_epsilon;
+        let nutation_dec = epsilon.sin() * cos_a * delta_psi + sin_a * delta_epsilon;
+
+        let (aberration_ra, aberration_dec) = aberration(jd_tt, sin_a, cos_a, sin_d, cos_d, epsilon);
+
+        let right_ascension = (alpha + nutation_ra + aberration_ra).rem_euclid(360.0);
+        let declination = delta + nutation_dec + aberration_dec;
+        let (longitude, latitude) = equatorial_to_ecliptic(right_ascension, declination, epsilon);
+
+        StarPosition {
+            name: self.name.clone(),
+            longitude,
+            latitude,
+            right_ascension,
+            declination,
+            magnitude: self.magnitude,
+        }
+    }
+}
+
+/// A collection of fixed stars
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FixedStarCatalog {
+    stars: Vec<FixedStar>,
+}
+
+impl FixedStarCatalog {
+    /// Get the catalogue bundled with the application
+    pub fn bundled() -> Self {
+        Self::from_csv(BUNDLED_CATALOGUE).expect("bundled star catalogue is valid")
+    }
+
+    /// Load a catalogue from a file in the bundled CSV format
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FixedStarError> {
+        Self::from_csv(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse a catalogue in CSV format
+    ///
+    /// Each line holds `name,designation,ra,dec,pm_ra,pm_dec,magnitude` with
+    /// the J2000 right ascension as `hh:mm:ss.s` and declination as
+    /// `±dd:mm:ss.s`. Empty lines and lines starting with `#` are ignored.
+    pub fn from_csv(csv: &str) -> Result<Self, FixedStarError> {
+        let mut stars = Vec::new();
+
+        for (index, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message: &str| FixedStarError::Parse { line: index + 1, message: message.to_string() };
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            if fields.len() != 7 {
+                return Err(error("expected 7 fields"));
+            }
+
+            let number = |field: &str| field.parse::<f64>().map_err(|_| error("invalid number"));
+
+            stars.push(FixedStar {
+                name: fields[0].to_string(),
+                designation: fields[1].to_string(),
+                right_ascension: parse_sexagesimal(fields[2]).ok_or_else(|| error("invalid right ascension"))? * 15.0,
+                declination: parse_sexagesimal(fields[3]).ok_or_else(|| error("invalid declination"))?,
+                proper_motion_ra: number(fields[4])?,
+                proper_motion_dec: number(fields[5])?,
+                magnitude: number(fields[6])?,
+            });
+        }
+
+        Ok(Self { stars })
+    }
+
+    /// Get all stars in the catalogue
+    pub fn stars(&self) -> &[FixedStar] {
+        &self.stars
+    }
+
+    /// Get the number of stars in the catalogue
+    pub fn len(&self) -> usize {
+        self.stars.len()
+    }
+
+    /// Check if the catalogue is empty
+    pub fn is_empty(&self) -> bool {
+        self.stars.is_empty()
+    }
+
+    /// Find a star by name or designation (case-insensitive)
+    pub fn get(&self, name: &str) -> Option<&FixedStar> {
+        self.stars.iter().find(|star| {
+            star.name.eq_ignore_ascii_case(name) || star.designation.eq_ignore_ascii_case(name)
+        })
+    }
+
+    /// Get the stars at least as bright as the given magnitude
+    pub fn brighter_than(&self, magnitude: f64) -> impl Iterator<Item = &FixedStar> + '_ {
+        self.stars.iter().filter(move |star| star.magnitude <= magnitude)
+    }
+
+    /// Calculate the apparent positions of every star for a Julian day (UT)
+    pub fn positions(&self, jd_ut: f64) -> Vec<StarPosition> {
+        self.stars.iter().map(|star| star.position(jd_ut)).collect()
+    }
+}
+
+/// Parse `[±]d:m:s` into decimal units
+fn parse_sexagesimal(text: &str) -> Option<f64> {
+    let (sign, text) = match text.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, text.strip_prefix('+').unwrap_or(text)),
+    };
+
+    let mut parts = text.split(':').map(|part| part.parse::<f64>().ok());
+    let degrees = parts.next()??;
+    let minutes = parts.next().unwrap_or(Some(0.0))?;
+    let seconds = parts.next().unwrap_or(Some(0.0))?;
+
+    Some(sign * (degrees + minutes / 60.0 + seconds / 3600.0))
+}
+
+/// Annual aberration in right ascension and declination, in degrees
+fn aberration(jd_tt: f64, sin_a: f64, cos_a: f64, sin_d: f64, cos_d: f64, epsilon: f64) -> (f64, f64) {
+    let t = (jd_tt - J2000) / 36_525.0;
+    let earth = vsop87::heliocentric(VsopBody::Earth, t / 10.0);
+    let sun = earth.longitude + std::f64::consts::PI;
+    let eccentricity = 0.016_708_634 - 0.000_042_037 * t;
+    let perihelion = (102.937_35 + 1.719_46 * t).to_radians();
+
+    let kappa = ABERRATION_CONSTANT / 3600.0;
+    let (sin_e, cos_e) = epsilon.sin_cos();
+    let tan_e = sin_e / cos_e;
+
+    let ra = |longitude: f64| (cos_a * longitude.cos() * cos_e + sin_a * longitude.sin()) / cos_d;
+    let dec = |longitude: f64| {
+        longitude.cos() * cos_e * (tan_e * cos_d - sin_a * sin_d) + cos_a * sin_d * longitude.sin()
+    };
+
+    (
+        -kappa * ra(sun) + eccentricity * kappa * ra(perihelion),
+        -kappa * dec(sun) + eccentricity * kappa * dec(perihelion),
+    )
+}
+
+/// Convert equatorial coordinates (degrees) to ecliptic ones for an obliquity (radians)
+fn equatorial_to_ecliptic(right_ascension: f64, declination: f64, epsilon: f64) -> (f64, f64) {
+    let (sin_a, cos_a) = right_ascension.to_radians().sin_cos();
+    let (sin_d, cos_d) = declination.to_radians().sin_cos();
+    let (sin_e, cos_e) = epsilon.sin_cos();
+
+    let longitude = (sin_a * cos_e + sin_d / cos_d * sin_e).atan2(cos_a);
+    let latitude = (sin_d * cos_e - cos_d * sin_e * sin_a).asin();
+
+    (longitude.to_degrees().rem_euclid(360.0), latitude.to_degrees())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    #[test]
+    fn test_bundled_catalogue() {
+        let catalog = FixedStarCatalog::bundled();
+        assert!(catalog.len() >= 50);
+        assert!(catalog.get("regulus").is_some());
+        assert_eq!(catalog.get("alVir").unwrap().name, "Spica");
+        assert!(catalog.brighter_than(0.0).all(|star| star.magnitude <= 0.0));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            FixedStarCatalog::from_csv("# header\nVega,alLyr,18:36:56.34,+38:47:01.3,200.94,286.23"),
+            Err(FixedStarError::Parse { line: 2, .. })
+        ));
+        assert!(FixedStarCatalog::from_csv("Vega,alLyr,18:xx:56,+38:47:01.3,200.94,286.23,0.03").is_err());
+    }
+
+    #[test]
+    fn test_star_longitudes_at_j2000() {
+        let catalog = FixedStarCatalog::bundled();
+        let expected = [
+            ("Regulus", 149.83),
+            ("Spica", 203.84),
+            ("Aldebaran", 69.79),
+            ("Antares", 249.76),
+            ("Sirius", 104.08),
+            ("Algol", 56.17),
+            ("Fomalhaut", 333.87),
+        ];
+
+        for (name, longitude) in expected {
+            let position = catalog.get(name).unwrap().position(J2000);
+            assert!(approx_eq!(f64, position.longitude, longitude, epsilon = 0.02), "{name}: {}", position.longitude);
+        }
+    }
+
+    #[test]
+    fn test_precession_over_a_century() {
+        let regulus = FixedStarCatalog::bundled().get("Regulus").unwrap().position(J2000 + 36_525.0);
+
+        // About 1.4° of precession per century: Regulus enters Virgo around 2012
+        assert!(approx_eq!(f64, regulus.longitude, 151.22, epsilon = 0.05));
+        assert!(approx_eq!(f64, regulus.latitude, 0.46, epsilon = 0.02));
+    }
+}
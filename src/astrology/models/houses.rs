@@ -0,0 +1,435 @@
+//! House systems
+//!
+//! Cusps and angles are computed from the local apparent sidereal time
+//! (ARMC), the geographic latitude and the true obliquity of the ecliptic, so
+//! they are the same for every ephemeris backend.
+//!
+//! Placidus and Koch divide the diurnal semi-arcs of ecliptic degrees, which
+//! do not exist for degrees that never rise or set. Inside the polar circles
+//! (|latitude| ≥ 90° − obliquity) both fall back to Porphyry, as the Swiss
+//! Ephemeris does, and [`Houses::system`] reports the system actually used.
+
+use std::f64::consts::PI;
+
+use serde::{Serialize, Deserialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::astrology::models::ephemeris::nutation::true_obliquity;
+use crate::astrology::models::ephemeris::{apparent_sidereal_time, delta_t};
+use crate::astrology::models::planet::Planet;
+
+/// Largest latitude used in the calculations, keeping the poles themselves finite
+const MAX_LATITUDE: f64 = 89.999_999;
+
+/// Supported house systems
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum HouseSystem {
+    /// Trisection of the diurnal and nocturnal semi-arcs in time
+    #[default]
+    Placidus,
+    /// Birthplace system: trisection of the semi-arc of the MC degree
+    Koch,
+    /// Equal division of the celestial equator
+    Regiomontanus,
+    /// Equal division of the prime vertical
+    Campanus,
+    /// 30° houses from the Ascendant
+    Equal,
+    /// Each sign is a house, starting with the sign of the Ascendant
+    WholeSign,
+    /// Trisection of the ecliptic quadrants between the angles
+    Porphyry,
+    /// Equal division of the equator from the meridian (axial rotation)
+    Meridian,
+}
+
+impl HouseSystem {
+    /// Check if the system is undefined inside the polar circles
+    pub fn needs_semi_arcs(&self) -> bool {
+        matches!(self, HouseSystem::Placidus | HouseSystem::Koch)
+    }
+}
+
+/// House cusps and angles for a moment and a place
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Houses {
+    /// System the cusps were calculated in (after any polar fallback)
+    pub system: HouseSystem,
+    /// Ecliptic longitudes of the cusps of houses 1 to 12 in degrees
+    pub cusps: [f64; 12],
+    /// Ascendant in degrees
+    pub ascendant: f64,
+    /// Midheaven in degrees
+    pub mc: f64,
+    /// Right ascension of the meridian (local apparent sidereal time) in degrees
+    pub armc: f64,
+    /// Vertex (western intersection of the prime vertical and the ecliptic) in degrees
+    pub vertex: f64,
+    /// East Point (equatorial ascendant) in degrees
+    pub east_point: f64,
+}
+
+impl Houses {
+    /// Calculate the houses for a Julian day (UT) and a geographic location
+    /// (latitude north positive, longitude east positive)
+    pub fn calculate(system: HouseSystem, jd_ut: f64, latitude: f64, longitude: f64) -> Self {
+        let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+        let armc = (apparent_sidereal_time(jd_ut) + longitude).rem_euclid(360.0);
+
+        Self::from_armc(system, armc, latitude, true_obliquity(jd_tt))
+    }
+
+    /// Calculate the houses from the ARMC, the latitude and the obliquity of
+    /// the ecliptic, all in degrees
+    pub fn from_armc(system: HouseSystem, armc: f64, latitude: f64, obliquity: f64) -> Self {
+        let sky = Sky::new(armc, latitude.clamp(-MAX_LATITUDE, MAX_LATITUDE), obliquity);
+        let ascendant = sky.ascendant();
+        let mc = sky.mc();
+
+        let system = if system.needs_semi_arcs() && latitude.abs() >= 90.0 - obliquity {
+            HouseSystem::Porphyry
+        } else {
+            system
+        };
+
+        // Cusps 10, 11, 12, 1, 2 and 3; the others are opposite
+        let eastern = match system {
+            HouseSystem::Placidus => [
+                mc,
+                sky.placidus(1.0 / 3.0, false),
+                sky.placidus(2.0 / 3.0, false),
+                ascendant,
+                sky.placidus(2.0 / 3.0, true),
+                sky.placidus(1.0 / 3.0, true),
+            ],
+            HouseSystem::Koch => {
+                let semi_arc = sky.semi_arc(mc).unwrap_or(90.0);
+                let ascendant_at = |offset: f64| sky.with_armc(armc + offset).ascendant();
+                [
+                    mc,
+                    ascendant_at(-semi_arc * 2.0 / 3.0),
+                    ascendant_at(-semi_arc / 3.0),
+                    ascendant,
+                    ascendant_at(semi_arc / 3.0),
+                    ascendant_at(semi_arc * 2.0 / 3.0),
+                ]
+            }
+            HouseSystem::Regiomontanus => {
+                let cusp = |angle: f64| sky.house_circle_cusp(sky.equator_point(angle));
+                [mc, cusp(30.0), cusp(60.0), ascendant, cusp(120.0), cusp(150.0)]
+            }
+            HouseSystem::Campanus => {
+                let cusp = |angle: f64| sky.house_circle_cusp(sky.prime_vertical_point(angle));
+                [mc, cusp(30.0), cusp(60.0), ascendant, cusp(120.0), cusp(150.0)]
+            }
+            HouseSystem::Porphyry => {
+                let upper = (ascendant - mc).rem_euclid(360.0) / 3.0;
+                let lower = (mc + 180.0 - ascendant).rem_euclid(360.0) / 3.0;
+                [mc, mc + upper, mc + 2.0 * upper, ascendant, ascendant + lower, ascendant + 2.0 * lower]
+            }
+            HouseSystem::Meridian => {
+                let cusp = |offset: f64| sky.longitude_of_right_ascension(armc + offset);
+                [mc, cusp(30.0), cusp(60.0), cusp(90.0), cusp(120.0), cusp(150.0)]
+            }
+            HouseSystem::Equal | HouseSystem::WholeSign => {
+                let first = if system == HouseSystem::WholeSign {
+                    (ascendant / 30.0).floor() * 30.0
+                } else {
+                    ascendant
+                };
+                [270.0, 300.0, 330.0, 0.0, 30.0, 60.0].map(|offset| first + offset)
+            }
+        };
+
+        let mut cusps = [0.0; 12];
+        for (index, cusp) in eastern.iter().enumerate() {
+            let house = (index + 9) % 12;
+            cusps[house] = cusp.rem_euclid(360.0);
+            cusps[(house + 6) % 12] = (cusp + 180.0).rem_euclid(360.0);
+        }
+
+        Self {
+            system,
+            cusps,
+            ascendant,
+            mc,
+            armc: armc.rem_euclid(360.0),
+            vertex: sky.vertex(),
+            east_point: sky.with_latitude(0.0).ascendant(),
+        }
+    }
+
+    /// Get the cusp of a house (1-12)
+    pub fn cusp(&self, house: u8) -> Option<f64> {
+        self.cusps.get(usize::from(house).checked_sub(1)?).copied()
+    }
+
+    /// Get the house (1-12) containing an ecliptic longitude
+    pub fn house_of(&self, longitude: f64) -> u8 {
+        (0..12)
+            .find(|&index| {
+                let start = self.cusps[index];
+                let width = (self.cusps[(index + 1) % 12] - start).rem_euclid(360.0);
+                (longitude - start).rem_euclid(360.0) < width
+            })
+            .map_or(1, |index| index as u8 + 1)
+    }
+
+    /// Get the longitude of an angle represented by a [`Planet`] variant
+    pub fn angle(&self, planet: &Planet) -> Option<f64> {
+        match planet {
+            Planet::Ascendant => Some(self.ascendant),
+            Planet::MC => Some(self.mc),
+            Planet::Vertex => Some(self.vertex),
+            Planet::EastPoint => Some(self.east_point),
+            _ => None,
+        }
+    }
+}
+
+/// The local sky as equatorial unit vectors, used to intersect great circles
+/// with the ecliptic
+#[derive(Debug, Clone, Copy)]
+struct Sky {
+    armc: f64,
+    latitude: f64,
+    obliquity: f64,
+}
+
+type Vector = [f64; 3];
+
+impl Sky {
+    fn new(armc: f64, latitude: f64, obliquity: f64) -> Self {
+        Self { armc, latitude, obliquity }
+    }
+
+    fn with_armc(&self, armc: f64) -> Self {
+        Self { armc, ..*self }
+    }
+
+    fn with_latitude(&self, latitude: f64) -> Self {
+        Self { latitude, ..*self }
+    }
+
+    /// Point of the equator on the upper meridian
+    fn meridian(&self) -> Vector {
+        self.equator_point(0.0)
+    }
+
+    /// Point of the equator a given angle east of the upper meridian
+    fn equator_point(&self, angle: f64) -> Vector {
+        let (sin, cos) = (self.armc + angle).to_radians().sin_cos();
+        [cos, sin, 0.0]
+    }
+
+    fn zenith(&self) -> Vector {
+        let (sin_t, cos_t) = self.armc.to_radians().sin_cos();
+        let (sin_l, cos_l) = self.latitude.to_radians().sin_cos();
+        [cos_l * cos_t, cos_l * sin_t, sin_l]
+    }
+
+    fn east(&self) -> Vector {
+        self.equator_point(90.0)
+    }
+
+    /// North point of the horizon
+    fn north(&self) -> Vector {
+        let (sin_t, cos_t) = self.armc.to_radians().sin_cos();
+        let (sin_l, cos_l) = self.latitude.to_radians().sin_cos();
+        [-sin_l * cos_t, -sin_l * sin_t, cos_l]
+    }
+
+    /// Point of the prime vertical a given angle from the zenith towards the east
+    fn prime_vertical_point(&self, angle: f64) -> Vector {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (zenith, east) = (self.zenith(), self.east());
+        [0, 1, 2].map(|i| zenith[i] * cos + east[i] * sin)
+    }
+
+    /// Ecliptic longitude where the great circle with the given pole crosses
+    /// the ecliptic on the side of `towards`
+    fn intersection(&self, pole: Vector, towards: Vector) -> f64 {
+        let (sin_e, cos_e) = self.obliquity.to_radians().sin_cos();
+        let mut longitude = (-pole[0]).atan2(pole[1] * cos_e + pole[2] * sin_e);
+
+        let (sin_l, cos_l) = longitude.sin_cos();
+        if dot([cos_l, sin_l * cos_e, sin_l * sin_e], towards) < 0.0 {
+            longitude += PI;
+        }
+
+        longitude.to_degrees().rem_euclid(360.0)
+    }
+
+    fn ascendant(&self) -> f64 {
+        self.intersection(self.zenith(), self.east())
+    }
+
+    fn mc(&self) -> f64 {
+        self.intersection(self.east(), self.meridian())
+    }
+
+    fn vertex(&self) -> f64 {
+        let west = self.east().map(|x| -x);
+        self.intersection(self.north(), west)
+    }
+
+    /// Cusp on the house circle through the north and south points of the
+    /// horizon and the given point
+    fn house_circle_cusp(&self, point: Vector) -> f64 {
+        self.intersection(cross(self.north(), point), point)
+    }
+
+    fn longitude_of_right_ascension(&self, right_ascension: f64) -> f64 {
+        let (sin, cos) = right_ascension.to_radians().sin_cos();
+        sin.atan2(cos * self.obliquity.to_radians().cos()).to_degrees().rem_euclid(360.0)
+    }
+
+    /// Diurnal semi-arc of an ecliptic degree, `None` if it never rises or sets
+    fn semi_arc(&self, longitude: f64) -> Option<f64> {
+        let declination = (self.obliquity.to_radians().sin() * longitude.to_radians().sin()).asin();
+        let x = self.latitude.to_radians().tan() * declination.tan();
+        (x.abs() <= 1.0).then(|| 90.0 + x.asin().to_degrees())
+    }
+
+    /// Placidus cusp at a fraction of the diurnal semi-arc east of the MC, or
+    /// of the nocturnal semi-arc east of the IC when `below` is set
+    fn placidus(&self, fraction: f64, below: bool) -> f64 {
+        let mut right_ascension = self.armc + if below { 180.0 - 90.0 * fraction } else { 90.0 * fraction };
+        let mut longitude = self.longitude_of_right_ascension(right_ascension);
+
+        for _ in 0..100 {
+            let semi_arc = self.semi_arc(longitude).unwrap_or(90.0);
+            let next = if below {
+                self.armc + 180.0 - fraction * (180.0 - semi_arc)
+            } else {
+                self.armc + fraction * semi_arc
+            };
+            let converged = (next - right_ascension).abs() < 1e-9;
+
+            right_ascension = next;
+            longitude = self.longitude_of_right_ascension(right_ascension);
+            if converged {
+                break;
+            }
+        }
+
+        longitude
+    }
+}
+
+fn dot(a: Vector, b: Vector) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vector, b: Vector) -> Vector {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::models::ephemeris::J2000;
+    use float_cmp::approx_eq;
+    use strum::IntoEnumIterator;
+
+    fn assert_cusps(houses: &Houses, expected: [f64; 12]) {
+        for (house, (cusp, expected)) in houses.cusps.iter().zip(expected).enumerate() {
+            let difference = (cusp - expected + 180.0).rem_euclid(360.0) - 180.0;
+            assert!(difference.abs() < 0.001, "{} house {}: {} != {}", houses.system, house + 1, cusp, expected);
+        }
+    }
+
+    #[test]
+    fn test_angles() {
+        // Berlin at J2000; reference values from the Swiss Ephemeris
+        let houses = Houses::calculate(HouseSystem::Placidus, J2000, 52.5, 13.4);
+
+        assert!(approx_eq!(f64, houses.armc, 293.857_07, epsilon = 0.000_1));
+        assert!(approx_eq!(f64, houses.ascendant, 51.585_02, epsilon = 0.001));
+        assert!(approx_eq!(f64, houses.mc, 292.085_10, epsilon = 0.001));
+        assert!(approx_eq!(f64, houses.vertex, 199.466_03, epsilon = 0.001));
+        assert!(approx_eq!(f64, houses.east_point, 25.734_63, epsilon = 0.001));
+        assert_eq!(houses.angle(&Planet::MC), Some(houses.mc));
+        assert_eq!(houses.angle(&Planet::Sun), None);
+    }
+
+    #[test]
+    fn test_house_systems() {
+        let expected = [
+            (HouseSystem::Placidus, [51.585_02, 77.134_75, 94.836_15, 112.085_10, 133.715_05, 169.300_43]),
+            (HouseSystem::Koch, [51.585_02, 77.917_58, 96.618_18, 112.085_10, 144.046_65, 190.528_49]),
+            (HouseSystem::Regiomontanus, [51.585_02, 83.484_74, 99.198_04, 112.085_10, 129.241_39, 166.994_73]),
+            (HouseSystem::Campanus, [51.585_02, 91.405_20, 103.738_76, 112.085_10, 122.062_11, 145.465_80]),
+            (HouseSystem::Equal, [51.585_02, 81.585_02, 111.585_02, 141.585_02, 171.585_02, 201.585_02]),
+            (HouseSystem::WholeSign, [30.0, 60.0, 90.0, 120.0, 150.0, 180.0]),
+            (HouseSystem::Porphyry, [51.585_02, 71.751_71, 91.918_40, 112.085_10, 151.918_40, 191.751_71]),
+            (HouseSystem::Meridian, [25.734_63, 56.173_92, 84.360_49, 112.085_10, 141.478_83, 173.309_47]),
+        ];
+
+        for (system, first_six) in expected {
+            let houses = Houses::calculate(system, J2000, 52.5, 13.4);
+            let mut cusps = [0.0; 12];
+            for (index, cusp) in first_six.iter().enumerate() {
+                cusps[index] = *cusp;
+                cusps[index + 6] = (cusp + 180.0) % 360.0;
+            }
+
+            assert_eq!(houses.system, system);
+            assert_cusps(&houses, cusps);
+        }
+    }
+
+    #[test]
+    fn test_southern_hemisphere() {
+        // Sydney, 2009 June 17, 19h12 UT
+        let jd_ut = 2_455_000.3;
+        let houses = Houses::calculate(HouseSystem::Placidus, jd_ut, -33.9, 151.2);
+        assert_cusps(&houses, [
+            62.718_79, 93.771_40, 128.719_33, 164.119_67, 195.227_09, 220.841_39,
+            242.718_79, 273.771_40, 308.719_33, 344.119_67, 15.227_09, 40.841_39,
+        ]);
+
+        let houses = Houses::calculate(HouseSystem::Campanus, jd_ut, -33.9, 151.2);
+        assert!(approx_eq!(f64, houses.cusps[1], 95.917_67, epsilon = 0.001));
+        assert!(approx_eq!(f64, houses.cusps[10], 10.611_59, epsilon = 0.001));
+    }
+
+    #[test]
+    fn test_polar_fallback() {
+        // Tromsø: the semi-arc systems fall back to Porphyry
+        for system in [HouseSystem::Placidus, HouseSystem::Koch] {
+            let houses = Houses::calculate(system, J2000, 69.6, 18.9);
+
+            assert_eq!(houses.system, HouseSystem::Porphyry);
+            assert_cusps(&houses, [
+                298.829_90, 358.318_99, 57.808_07, 117.297_16, 117.808_07, 118.318_99,
+                118.829_90, 178.318_99, 237.808_07, 297.297_16, 297.808_07, 298.318_99,
+            ]);
+        }
+
+        // The other systems are defined up to the poles
+        for system in HouseSystem::iter().filter(|system| !system.needs_semi_arcs()) {
+            let houses = Houses::calculate(system, J2000, 90.0, 0.0);
+            assert_eq!(houses.system, system);
+            assert!(houses.cusps.iter().all(|cusp| cusp.is_finite()));
+        }
+    }
+
+    #[test]
+    fn test_house_of() {
+        let houses = Houses::calculate(HouseSystem::Placidus, J2000, 52.5, 13.4);
+
+        assert_eq!(houses.house_of(51.6), 1);
+        assert_eq!(houses.house_of(51.5), 12);
+        assert_eq!(houses.house_of(0.0), 12);
+        assert_eq!(houses.house_of(300.0), 10);
+        assert_eq!(houses.house_of(200.0), 6);
+        assert_eq!(houses.cusp(10), Some(houses.mc));
+        assert_eq!(houses.cusp(13), None);
+    }
+}
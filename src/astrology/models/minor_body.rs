@@ -0,0 +1,344 @@
+//! Numbered minor planets
+//!
+//! Asteroids, centaurs and transneptunian objects are added at runtime by
+//! their Minor Planet Center number instead of through
+//! [`Planet`](crate::astrology::models::planet::Planet) variants. A body is
+//! computed from its Swiss Ephemeris asteroid file when one is installed
+//! (`astNNN/seNNNNN.se1` in the ephemeris path) and from its osculating
+//! elements otherwise, as published by the MPC in the `MPCORB.DAT` format.
+//! Charts refer to minor bodies through [`ChartPoint::MinorBody`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use log::warn;
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::ephemeris::OrbitalElements;
+use crate::astrology::models::planet::BodyType;
+
+/// Name of the MPC orbit file looked up in the ephemeris path
+pub const MPCORB_FILE: &str = "MPCORB.DAT";
+
+/// MPC numbers of the minor planets recognised as dwarf planets by the IAU
+/// (Ceres, Haumea, Eris and Makemake; Pluto has its own variant)
+const DWARF_PLANETS: [u32; 4] = [1, 136_108, 136_199, 136_472];
+
+/// Semi-major axis range of the centaurs in AU (between Jupiter and Neptune)
+const CENTAUR_SEMI_AXIS: std::ops::Range<f64> = 5.5..30.1;
+
+/// Errors that can occur while loading minor planet orbits
+#[derive(Debug, Error)]
+pub enum MinorBodyError {
+    #[error("failed to read orbit file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid orbit entry on line {line}: {message}")]
+    Parse { line: usize, message: String },
+}
+
+/// A numbered minor planet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MinorBody {
+    /// Minor Planet Center number, e.g. 433 for Eros
+    pub number: u32,
+    /// Name, e.g. "Eros"
+    pub name: String,
+    /// Osculating elements, used when no asteroid file is installed
+    pub elements: Option<OrbitalElements>,
+    /// Absolute magnitude H
+    pub absolute_magnitude: Option<f64>,
+}
+
+/// Apparent position of a minor planet for a moment in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MinorBodyPosition {
+    /// Minor Planet Center number
+    pub number: u32,
+    /// Name of the body
+    pub name: String,
+    /// Ecliptic longitude in degrees (0-360)
+    pub longitude: f64,
+    /// Ecliptic latitude in degrees
+    pub latitude: f64,
+    /// Distance from the Earth in AU
+    pub distance_au: f64,
+    /// Daily motion in longitude in degrees
+    pub speed: f64,
+    /// Whether the body is in retrograde motion
+    pub is_retrograde: bool,
+}
+
+impl MinorBody {
+    /// Create a body known only by number and name, computed from its asteroid file
+    pub fn numbered(number: u32, name: impl Into<String>) -> Self {
+        Self {
+            number,
+            name: name.into(),
+            elements: None,
+            absolute_magnitude: None,
+        }
+    }
+
+    /// Create a body computed from osculating elements
+    pub fn with_elements(number: u32, name: impl Into<String>, elements: OrbitalElements) -> Self {
+        Self {
+            elements: Some(elements),
+            ..Self::numbered(number, name)
+        }
+    }
+
+    /// Parse one line of an `MPCORB.DAT` file
+    ///
+    /// Returns `Ok(None)` for objects that have no permanent number yet.
+    pub fn from_mpcorb_line(line: &str) -> Result<Option<Self>, String> {
+        let field = |start: usize, end: usize| line.get(start - 1..end).map(str::trim).unwrap_or("");
+        let number = |start: usize, end: usize, name: &str| {
+            field(start, end).parse::<f64>().map_err(|_| format!("invalid {name}"))
+        };
+
+        // The readable designation in columns 167-194 is "(433) Eros" for
+        // numbered objects; names may contain spaces, as "(2309) Mr. Spock"
+        let designation = line.get(166..line.len().min(194)).map(str::trim).unwrap_or("");
+        let Some((mpc_number, name)) = designation
+            .strip_prefix('(')
+            .and_then(|rest| rest.split_once(')'))
+        else {
+            return Ok(None);
+        };
+        let mpc_number = mpc_number.parse::<u32>().map_err(|_| "invalid number".to_string())?;
+        let name = name.trim().to_string();
+
+        let elements = OrbitalElements {
+            epoch: parse_packed_epoch(field(21, 25)).ok_or("invalid epoch")?,
+            mean_anomaly: number(27, 35, "mean anomaly")?,
+            perihelion: number(38, 46, "argument of perihelion")?,
+            node: number(49, 57, "ascending node")?,
+            inclination: number(60, 68, "inclination")?,
+            eccentricity: number(71, 79, "eccentricity")?,
+            semi_major_axis: number(93, 103, "semi-major axis")?,
+        };
+        if !(0.0..1.0).contains(&elements.eccentricity) {
+            return Err("orbit is not elliptic".to_string());
+        }
+
+        Ok(Some(Self {
+            absolute_magnitude: field(9, 13).parse().ok(),
+            ..Self::with_elements(mpc_number, name, elements)
+        }))
+    }
+
+    /// Get the body type, classified by orbit
+    pub fn body_type(&self) -> BodyType {
+        if DWARF_PLANETS.contains(&self.number) {
+            return BodyType::DwarfPlanet;
+        }
+
+        match self.elements.map(|elements| elements.semi_major_axis) {
+            Some(a) if CENTAUR_SEMI_AXIS.contains(&a) => BodyType::Centaur,
+            Some(a) if a >= CENTAUR_SEMI_AXIS.end => BodyType::TransNeptunian,
+            _ => BodyType::Asteroid,
+        }
+    }
+
+    /// Get the chart point referring to this body
+    pub fn chart_point(&self) -> ChartPoint {
+        ChartPoint::MinorBody {
+            number: self.number,
+            name: self.name.clone(),
+        }
+    }
+}
+
+impl fmt::Display for MinorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}) {}", self.number, self.name)
+    }
+}
+
+/// Minor planets added to the application at runtime
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MinorBodyRegistry {
+    bodies: BTreeMap<u32, MinorBody>,
+}
+
+impl MinorBodyRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every numbered object of a file in the `MPCORB.DAT` format
+    pub fn load_mpcorb(path: impl AsRef<Path>) -> Result<Self, MinorBodyError> {
+        Ok(Self::from_mpcorb(&std::fs::read_to_string(path)?))
+    }
+
+    /// Parse orbits in the `MPCORB.DAT` format
+    ///
+    /// The file header, up to the dashed separator line, is skipped when
+    /// present; so are blank lines and unnumbered objects. Malformed lines and
+    /// non-elliptic orbits are logged and skipped.
+    pub fn from_mpcorb(text: &str) -> Self {
+        let mut registry = Self::new();
+        let header = text.lines().position(|line| line.starts_with("-----"));
+
+        for (index, line) in text.lines().enumerate().skip(header.map_or(0, |separator| separator + 1)) {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match MinorBody::from_mpcorb_line(line) {
+                Ok(Some(body)) => registry.insert(body),
+                Ok(None) => {}
+                Err(message) => warn!("{}", MinorBodyError::Parse { line: index + 1, message }),
+            }
+        }
+
+        registry
+    }
+
+    /// Look up a single body in a (possibly very large) `MPCORB.DAT` file
+    /// without loading the whole file
+    pub fn find_in_mpcorb(path: impl AsRef<Path>, number: u32) -> Result<Option<MinorBody>, MinorBodyError> {
+        let prefix = format!("({number})");
+
+        for (index, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+            let line = line?;
+            if line.get(166..).is_some_and(|designation| designation.trim_start().starts_with(&prefix)) {
+                return MinorBody::from_mpcorb_line(&line)
+                    .map_err(|message| MinorBodyError::Parse { line: index + 1, message });
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Add a body, replacing any body with the same number
+    pub fn insert(&mut self, body: MinorBody) {
+        self.bodies.insert(body.number, body);
+    }
+
+    /// Remove a body by number
+    pub fn remove(&mut self, number: u32) -> Option<MinorBody> {
+        self.bodies.remove(&number)
+    }
+
+    /// Get a body by number
+    pub fn get(&self, number: u32) -> Option<&MinorBody> {
+        self.bodies.get(&number)
+    }
+
+    /// Find a body by name (case-insensitive)
+    pub fn find(&self, name: &str) -> Option<&MinorBody> {
+        self.bodies.values().find(|body| body.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Get all registered bodies, ordered by number
+    pub fn bodies(&self) -> impl Iterator<Item = &MinorBody> + '_ {
+        self.bodies.values()
+    }
+
+    /// Get the number of registered bodies
+    pub fn len(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// Check if the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.bodies.is_empty()
+    }
+}
+
+/// Decode an MPC packed epoch such as `K205V` (2020 May 31) to a Julian day (TT)
+fn parse_packed_epoch(packed: &str) -> Option<f64> {
+    let decode = |c: char| match c {
+        '1'..='9' => c.to_digit(10),
+        'A'..='V' => Some(c as u32 - 'A' as u32 + 10),
+        _ => None,
+    };
+
+    let mut chars = packed.chars();
+    let century = match chars.next()? {
+        'I' => 1800,
+        'J' => 1900,
+        'K' => 2000,
+        _ => return None,
+    };
+    let year = century + chars.by_ref().take(2).collect::<String>().parse::<i32>().ok()?;
+    let month = decode(chars.next()?)?;
+    let day = decode(chars.next()?)?;
+
+    let date = chrono::NaiveDate::from_ymd_opt(year, month, day)?;
+    let unix_days = date.signed_duration_since(chrono::NaiveDate::from_ymd_opt(1970, 1, 1)?).num_days();
+    Some(2_440_587.5 + unix_days as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    const CERES: &str = "00001    3.34  0.15 K205V 162.68631   73.73161   80.28698   10.58862  0.0775571  0.21406009   2.7676569  0 MPO492748  6751 115 1801-2019 0.60 M-v 30h Williams   0000      (1) Ceres              20190915";
+
+    #[test]
+    fn test_parse_mpcorb_line() {
+        let ceres = MinorBody::from_mpcorb_line(CERES).unwrap().unwrap();
+        let elements = ceres.elements.unwrap();
+
+        assert_eq!(ceres.number, 1);
+        assert_eq!(ceres.name, "Ceres");
+        assert_eq!(ceres.to_string(), "(1) Ceres");
+        assert_eq!(ceres.absolute_magnitude, Some(3.34));
+        assert_eq!(ceres.body_type(), BodyType::DwarfPlanet);
+        assert!(approx_eq!(f64, elements.epoch, 2_459_000.5));
+        assert!(approx_eq!(f64, elements.semi_major_axis, 2.767_656_9));
+        assert!(approx_eq!(f64, elements.mean_motion(), 0.214_060_09, epsilon = 1e-6));
+
+        // The whole name up to the date of the last observation
+        let spock = format!("{}{:<28}{}", &CERES[..166], "  (2309) Mr. Spock", &CERES[194..]);
+        let spock = MinorBody::from_mpcorb_line(&spock).unwrap().unwrap();
+        assert_eq!((spock.number, spock.name.as_str()), (2309, "Mr. Spock"));
+    }
+
+    #[test]
+    fn test_registry() {
+        let header = "MINOR PLANET CENTER ORBIT DATABASE (MPCORB)\n\n----------------------------------------\n";
+        let mut registry = MinorBodyRegistry::from_mpcorb(&format!("{header}{CERES}\n\n"));
+        assert_eq!(registry.len(), 1);
+        assert!(registry.find("ceres").is_some());
+
+        registry.insert(MinorBody::numbered(90_377, "Sedna"));
+        registry.insert(MinorBody::numbered(1, "Ceres"));
+        assert_eq!(registry.len(), 2);
+        assert!(registry.get(1).unwrap().elements.is_none());
+        assert!(registry.remove(90_377).is_some());
+        assert!(registry.get(90_377).is_none());
+
+        // An invalid line is skipped without losing the rest of the file
+        let registry = MinorBodyRegistry::from_mpcorb(&format!("{}\n{CERES}\n", CERES.replace("K205V", "K20XX")));
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_body_type_from_orbit() {
+        let orbit = |semi_major_axis| OrbitalElements {
+            epoch: 2_451_545.0,
+            mean_anomaly: 0.0,
+            perihelion: 0.0,
+            node: 0.0,
+            inclination: 0.0,
+            eccentricity: 0.1,
+            semi_major_axis,
+        };
+
+        assert_eq!(MinorBody::with_elements(433, "Eros", orbit(1.46)).body_type(), BodyType::Asteroid);
+        assert_eq!(MinorBody::with_elements(5145, "Pholus", orbit(20.3)).body_type(), BodyType::Centaur);
+        assert_eq!(MinorBody::with_elements(90_377, "Sedna", orbit(506.0)).body_type(), BodyType::TransNeptunian);
+        assert_eq!(MinorBody::numbered(136_199, "Eris").body_type(), BodyType::DwarfPlanet);
+    }
+}
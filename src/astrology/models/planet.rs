@@ -1,9 +1,17 @@
 use std::collections::HashMap;
 use std::fmt;
 use strum_macros::{Display, EnumIter, EnumString};
-use strum::IntoEnumIterator;
 use serde::{Serialize, Deserialize};
 
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::ephemeris::CoordinateFrame;
+use crate::astrology::models::houses::Houses;
+use crate::astrology::models::zodiac::Zodiac;
+
+/// Mean obliquity of the ecliptic at J2000.0 in degrees, used for the
+/// equatorial coordinates until the obliquity of date is known
+const J2000_OBLIQUITY: f64 = 23.439_291_1;
+
 // Import the Angle type if it exists, otherwise use a simple type alias
 #[cfg(feature = "angle_module")]
 use crate::utils::angle::Angle;
@@ -84,12 +92,6 @@ pub enum Planet {
     // Additional Points
     BlackMoonLilith,
     WhiteMoonSelena,
-    
-    // Fixed Stars (selected)
-    Regulus,
-    Spica,
-    Antares,
-    Aldebaran,
 }
 
 impl Planet {
@@ -104,9 +106,9 @@ impl Planet {
             Planet::Jupiter | Planet::Saturn | Planet::Uranus | 
             Planet::Neptune => BodyType::Planet,
             
-            // Dwarf Planets and Asteroids
-            Planet::Pluto | Planet::Ceres | Planet::Eris | 
-            Planet::Makemake | Planet::Haumea => BodyType::DwarfPlanet,
+            // Dwarf Planets (Eris, Makemake, Haumea and other minor planets
+            // are added by MPC number, see `MinorBody`)
+            Planet::Pluto | Planet::Ceres => BodyType::DwarfPlanet,
             
             // Asteroids and Centaurs
             Planet::Pallas | Planet::Juno | Planet::Vesta => BodyType::Asteroid,
@@ -126,10 +128,6 @@ impl Planet {
             
             // Other Points
             Planet::BlackMoonLilith | Planet::WhiteMoonSelena => BodyType::Point,
-            
-            // Fixed Stars
-            Planet::Regulus | Planet::Spica | 
-            Planet::Antares | Planet::Aldebaran => BodyType::Star,
         }
     }
 
@@ -183,12 +181,6 @@ impl Planet {
             // Additional Points
             Planet::BlackMoonLilith => "⚸",
             Planet::WhiteMoonSelena => "⚪",
-            
-            // Fixed Stars
-            Planet::Regulus => "★",
-            Planet::Spica => "✧",
-            Planet::Antares => "☆",
-            Planet::Aldebaran => "✫",
         }
     }
 
@@ -235,10 +227,6 @@ impl Planet {
             // Additional Points
             Planet::BlackMoonLilith => "#000000", // Black
             Planet::WhiteMoonSelena => "#FFFFFF", // White
-            
-            // Fixed Stars
-            Planet::Regulus | Planet::Spica | 
-            Planet::Antares | Planet::Aldebaran => "#FFD700", // Gold
         }
     }
     
@@ -256,7 +244,7 @@ impl Planet {
             Planet::Neptune => Some(164.7913),
             Planet::Pluto => Some(247.9207),
             Planet::Chiron => Some(50.7),
-            _ => None, // For fixed stars and other points
+            _ => None, // For hypothetical and sensitive points
         }
     }
     
@@ -309,14 +297,37 @@ pub struct PlanetPosition {
     pub is_retrograde: bool,
     
     // Observational data
-    /// Apparent size in arcseconds
+    /// Apparent diameter in arcseconds (0 for points without a disc)
     pub apparent_size: f64,
-    /// Apparent magnitude
+    /// Visual magnitude
     pub magnitude: f64,
-    /// Phase (0-1, for Moon and inner planets)
+    /// Illuminated fraction of the disc (0-1)
     pub phase: f64,
     /// Elongation from Sun in degrees (0-180)
     pub elongation: f64,
+    /// Angle Sun–body–Earth in degrees (0 at full phase)
+    #[serde(default)]
+    pub phase_angle: f64,
+    /// Whether the body is east of the Sun: seen in the evening, the Moon waxing
+    #[serde(default)]
+    pub is_east_of_sun: bool,
+    
+    // Equatorial coordinates
+    /// Right ascension in degrees (0-360)
+    #[serde(default)]
+    pub right_ascension: f64,
+    /// Declination in degrees (-90 to 90)
+    #[serde(default)]
+    pub declination: f64,
+    /// Daily speed in declination (degrees per day)
+    #[serde(default)]
+    pub speed_declination: f64,
+    /// Obliquity of the ecliptic the equatorial coordinates are computed for
+    #[serde(default)]
+    pub obliquity: f64,
+    /// Whether the declination is beyond the greatest one the Sun reaches
+    #[serde(default)]
+    pub is_out_of_bounds: bool,
     
     // Horizontal coordinates (observer-dependent)
     /// Altitude above horizon in degrees (-90 to 90)
@@ -331,6 +342,12 @@ pub struct PlanetPosition {
     pub zodiac_sign: u8,
     /// Degree within the zodiac sign (0-29.999...)
     pub zodiac_degree: f64,
+    /// Zodiac the sign and degree are counted in
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// Ayanamsa in degrees at the time of the position, 0 in the tropical zodiac
+    #[serde(default)]
+    pub ayanamsa: f64,
     
     // Harmonic data
     /// Position in the current harmonic (0-360)
@@ -388,7 +405,7 @@ impl PlanetPosition {
         let elongation = elongation.rem_euclid(360.0);
         let elongation = if elongation > 180.0 { 360.0 - elongation } else { elongation };
         
-        Self {
+        let mut position = Self {
             planet,
             longitude: normalized_longitude,
             latitude: normalized_latitude,
@@ -396,37 +413,31 @@ impl PlanetPosition {
             speed,
             speed_latitude,
             distance_au,
-            distance_km: distance_au * 149_597_870.7, // Convert AU to km
+            distance_km,
             is_retrograde,
             apparent_size,
             magnitude,
             phase,
             elongation,
+            phase_angle: 0.0,
+            is_east_of_sun: false,
+            right_ascension: 0.0,
+            declination: 0.0,
+            speed_declination: 0.0,
+            obliquity: J2000_OBLIQUITY, // Will be set later based on the date
+            is_out_of_bounds: false,
             altitude: 0.0,  // Will be set later based on location
             azimuth: 0.0,   // Will be set later based on location
             house: None,    // Will be set later based on house system
             zodiac_sign,
             zodiac_degree,
+            zodiac: Zodiac::Tropical,
+            ayanamsa: 0.0,
+            harmonic_position,
             harmonic,
-        }
-        
-        // Update observational data
-        self.apparent_size = apparent_size;
-        self.magnitude = magnitude;
-        self.phase = phase.max(0.0).min(1.0);
-        
-        // Ensure elongation is in valid range (0-180)
-        let normalized_elongation = elongation.rem_euclid(360.0);
-        self.elongation = if normalized_elongation > 180.0 { 
-            360.0 - normalized_elongation 
-        } else { 
-            normalized_elongation 
         };
-        
-        // Update derived fields
-        self.zodiac_sign = (self.longitude / 30.0).floor() as u8 % 12;
-        self.zodiac_degree = self.longitude % 30.0;
-        self.harmonic_position = (self.longitude * self.harmonic as f64) % 360.0;
+        position.update_equatorial();
+        position
     }
 
     /// Get the zodiac sign as a string (Aries, Taurus, etc.)
@@ -497,11 +508,61 @@ impl PlanetPosition {
         };
         
         // Update derived fields
-        self.zodiac_sign = (self.longitude / 30.0).floor() as u8 % 12;
-        self.zodiac_degree = self.longitude % 30.0;
+        self.update_zodiac_sign();
+        self.update_equatorial();
         self.harmonic_position = (self.longitude * self.harmonic as f64) % 360.0;
     }
     
+    /// Count the sign and degree in another zodiac, given its ayanamsa in
+    /// degrees at the time of the position; the longitude stays tropical
+    pub fn set_zodiac(&mut self, zodiac: Zodiac, ayanamsa: f64) {
+        self.zodiac = zodiac;
+        self.ayanamsa = if zodiac == Zodiac::Tropical { 0.0 } else { ayanamsa };
+        self.update_zodiac_sign();
+    }
+    
+    /// Get the longitude in the zodiac of the position (0-360)
+    pub fn zodiac_longitude(&self) -> f64 {
+        (self.longitude - self.ayanamsa).rem_euclid(360.0)
+    }
+    
+    fn update_zodiac_sign(&mut self) {
+        let longitude = self.zodiac_longitude();
+        self.zodiac_sign = (longitude / 30.0).floor() as u8 % 12;
+        self.zodiac_degree = longitude % 30.0;
+    }
+    
+    /// Compute the equatorial coordinates for the obliquity of the ecliptic
+    /// of the date, in degrees, and flag the position out of bounds when its
+    /// declination exceeds it
+    pub fn set_obliquity(&mut self, obliquity: f64) {
+        self.obliquity = obliquity;
+        self.update_equatorial();
+    }
+    
+    fn update_equatorial(&mut self) {
+        let (sin_l, cos_l) = self.longitude.to_radians().sin_cos();
+        let (sin_b, cos_b) = self.latitude.to_radians().sin_cos();
+        let (sin_e, cos_e) = self.obliquity.to_radians().sin_cos();
+        
+        let sin_d = sin_b * cos_e + cos_b * sin_e * sin_l;
+        let declination = sin_d.asin();
+        self.right_ascension = (cos_b * sin_l * cos_e - sin_b * sin_e).atan2(cos_b * cos_l).to_degrees().rem_euclid(360.0);
+        self.declination = declination.to_degrees();
+        
+        // Differentiating sin δ = sin β cos ε + cos β sin ε sin λ; the
+        // declination has no rate at the celestial poles
+        let rate = self.speed_latitude * (cos_b * cos_e - sin_b * sin_e * sin_l) + self.speed * cos_b * sin_e * cos_l;
+        self.speed_declination = if declination.cos() > f64::EPSILON { rate / declination.cos() } else { 0.0 };
+        self.is_out_of_bounds = self.declination.abs() > self.obliquity;
+    }
+    
+    /// Set the phase angle in degrees and the side of the Sun the body is seen on
+    pub fn set_phase_angle(&mut self, phase_angle: f64, is_east_of_sun: bool) {
+        self.phase_angle = phase_angle.clamp(0.0, 180.0);
+        self.is_east_of_sun = is_east_of_sun;
+    }
+    
     /// Set the harmonic for this position and recalculate harmonic position
     pub fn set_harmonic(&mut self, harmonic: u32) {
         self.harmonic = harmonic.max(1).min(90); // Cap at 90th harmonic
@@ -537,6 +598,14 @@ pub struct ChartPositions {
     pub positions: HashMap<Planet, PlanetPosition>,
     pub datetime: chrono::DateTime<chrono::Utc>,
     pub location: (f64, f64), // (latitude, longitude)
+    #[serde(default)]
+    pub houses: Option<Houses>,
+    /// Zodiac the signs of the positions are counted in
+    #[serde(default)]
+    pub zodiac: Zodiac,
+    /// Point the positions are seen from
+    #[serde(default)]
+    pub frame: CoordinateFrame,
 }
 
 impl ChartPositions {
@@ -545,6 +614,9 @@ impl ChartPositions {
             positions: HashMap::new(),
             datetime,
             location,
+            houses: None,
+            zodiac: Zodiac::Tropical,
+            frame: CoordinateFrame::Geocentric,
         }
     }
 
@@ -555,6 +627,15 @@ impl ChartPositions {
     pub fn get_position(&self, planet: &Planet) -> Option<&PlanetPosition> {
         self.positions.get(planet)
     }
+
+    /// Get the bodies and angles as points, in the order of [`Planet`]
+    pub fn points(&self) -> Vec<(ChartPoint, crate::utils::angle::Angle)> {
+        let mut points: Vec<(ChartPoint, crate::utils::angle::Angle)> = self.positions.values()
+            .map(|position| (ChartPoint::Planet(position.planet), crate::utils::angle::Angle::from_degrees(position.longitude)))
+            .collect();
+        points.sort_by_key(|(point, _)| point.as_planet().map(|planet| planet as usize));
+        points
+    }
 }
 
 #[cfg(test)]
@@ -580,8 +661,46 @@ mod tests {
 
     #[test]
     fn test_planet_position_normalization() {
-        let pos = PlanetPosition::new(Planet::Sun, 370.0, 95.0, 1.0, 1.0);
+        let pos = PlanetPosition::new(
+            Planet::Sun, 370.0, 95.0, 1.0, 1.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1,
+        );
         assert_eq!(pos.longitude, 10.0);
         assert_eq!(pos.latitude, 90.0);
     }
+
+    #[test]
+    fn test_planet_position_equatorial() {
+        // The Sun at 10° Gemini, still moving north
+        let mut pos = PlanetPosition::new(
+            Planet::Sun, 70.0, 0.0, 1.0, 0.96, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1,
+        );
+        pos.set_obliquity(23.44);
+        assert!((pos.declination - 21.950_099).abs() < 1e-6, "{}", pos.declination);
+        assert!((pos.right_ascension - 68.361_392).abs() < 1e-6, "{}", pos.right_ascension);
+        assert!(pos.speed_declination > 0.0);
+        assert!(!pos.is_out_of_bounds);
+
+        // The Moon 5° north of the ecliptic at the summer solstice is out of bounds
+        let pos = PlanetPosition::new(
+            Planet::Moon, 90.0, 5.0, 0.0026, 13.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1,
+        );
+        assert!((pos.declination - (23.439_291_1 + 5.0)).abs() < 1e-9);
+        assert!(pos.is_out_of_bounds);
+    }
+
+    #[test]
+    fn test_planet_position_sidereal() {
+        use crate::astrology::models::zodiac::Ayanamsa;
+
+        let mut pos = PlanetPosition::new(
+            Planet::Sun, 10.5, 0.0, 1.0, 1.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1,
+        );
+        pos.set_zodiac(Zodiac::Sidereal(Ayanamsa::Lahiri), 24.0);
+        assert_eq!(pos.longitude, 10.5);
+        assert_eq!(pos.zodiac_sign_name(), "Pisces");
+        assert_eq!(pos.to_formatted_string(), "16°30' Pisces");
+
+        pos.set_zodiac(Zodiac::Tropical, 24.0);
+        assert_eq!(pos.to_formatted_string(), "10°30' Aries");
+    }
 }
@@ -0,0 +1,273 @@
+//! Solar, lunar and planetary returns
+//!
+//! A return is the moment a transiting body comes back to its natal
+//! longitude; the chart cast for that moment, usually at the place the
+//! native lives or stays at the time, describes the following cycle of the
+//! body (a year for the Sun, a month for the Moon). Returns are measured in
+//! the tropical zodiac, or in the sidereal one, where the natal longitude is
+//! advanced by the precession since birth so that the body returns to the
+//! same place among the stars. Every pass of a retrograde loop is found by
+//! the [`CrossingFinder`].
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use serde::{Serialize, Deserialize};
+use strum_macros::{Display, EnumIter, EnumString};
+use thiserror::Error;
+
+use crate::astrology::models::crossing::CrossingFinder;
+use crate::astrology::models::ephemeris::precession::precess_ecliptic;
+use crate::astrology::models::ephemeris::{at, julian_day, BackendKind, Ephemeris, EphemerisError, DAY_MS};
+use crate::astrology::models::planet::{ChartPositions, Planet, PlanetPosition};
+
+/// Days searched for the next return of a body without an orbital period
+const DEFAULT_SEARCH_DAYS: f64 = 400.0;
+
+/// Factor applied to the orbital period to cover the retrograde loops
+const SEARCH_MARGIN: f64 = 1.25;
+
+/// Errors that can occur while looking for a return
+#[derive(Debug, Error)]
+pub enum ReturnError {
+    #[error(transparent)]
+    Ephemeris(#[from] EphemerisError),
+
+    #[error("{0} is not in the natal chart")]
+    MissingBody(Planet),
+
+    #[error("{body} does not return to its natal longitude within {days:.0} days after {after}")]
+    NotFound { body: Planet, after: DateTime<Utc>, days: f64 },
+}
+
+/// Zodiac in which a return is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum ReturnZodiac {
+    /// Return to the natal tropical longitude
+    #[default]
+    Tropical,
+    /// Return to the natal sidereal longitude: the tropical one corrected for
+    /// the precession since birth, whichever ayanamsa is used
+    #[strum(serialize = "sidereal", serialize = "precession_corrected")]
+    Sidereal,
+}
+
+/// A return and the chart cast for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetaryReturn {
+    pub body: Planet,
+    pub zodiac: ReturnZodiac,
+    /// Moment the body is back on its natal longitude
+    pub moment: DateTime<Utc>,
+    /// Tropical longitude of the body at that moment (the natal one, advanced
+    /// by the precession in the sidereal zodiac)
+    pub longitude: f64,
+    /// Bodies and houses at the return, cast for the chosen location
+    pub chart: ChartPositions,
+}
+
+/// Finds the returns of the bodies of a natal chart
+#[derive(Debug, Clone)]
+pub struct ReturnFinder {
+    natal: ChartPositions,
+    zodiac: ReturnZodiac,
+    backend: BackendKind,
+}
+
+impl ReturnFinder {
+    /// Prepare the returns of a natal chart, using the best available backend
+    pub fn new(natal: &ChartPositions, zodiac: ReturnZodiac) -> Self {
+        Self::with_backend(natal, zodiac, BackendKind::detect())
+    }
+
+    /// Prepare the returns of a natal chart with a specific backend
+    pub fn with_backend(natal: &ChartPositions, zodiac: ReturnZodiac, backend: BackendKind) -> Self {
+        Self {
+            natal: natal.clone(),
+            zodiac,
+            backend,
+        }
+    }
+
+    /// Get the zodiac the returns are measured in
+    pub fn zodiac(&self) -> ReturnZodiac {
+        self.zodiac
+    }
+
+    /// Get the tropical longitude a body returns to at a moment: the natal
+    /// one, advanced by the precession since birth in the sidereal zodiac
+    pub fn return_longitude(&self, body: &Planet, moment: DateTime<Utc>) -> Result<f64, ReturnError> {
+        let natal = self.natal.get_position(body).ok_or(ReturnError::MissingBody(*body))?;
+        Ok(self.target(natal, moment))
+    }
+
+    /// Find every return of a body between two dates, in order of time
+    pub fn find_returns(&self, body: &Planet, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<DateTime<Utc>>, ReturnError> {
+        let natal = self.natal.get_position(body).ok_or(ReturnError::MissingBody(*body))?;
+        let span = (to - from).num_milliseconds() as f64 / DAY_MS;
+        let mut finder = CrossingFinder::new(&self.ephemeris(from)?, *body, from, 360.0);
+
+        Ok(finder.find(span, |moment| self.target(natal, moment))?
+            .into_iter()
+            .map(|crossing| at(from, crossing.days))
+            .collect())
+    }
+
+    /// Find the first return of a body after a date, searching somewhat more
+    /// than one orbit of the body (at least a year for Mercury and Venus,
+    /// which follow the Sun around the geocentric zodiac)
+    pub fn next_return(&self, body: &Planet, after: DateTime<Utc>) -> Result<DateTime<Utc>, ReturnError> {
+        let days = body.orbital_period_years()
+            .map(|years| if matches!(body, Planet::Mercury | Planet::Venus) { years.max(1.0) } else { years })
+            .map_or(DEFAULT_SEARCH_DAYS, |years| years * 365.25 * SEARCH_MARGIN);
+
+        self.find_returns(body, after, at(after, days))?
+            .into_iter()
+            .next()
+            .ok_or(ReturnError::NotFound { body: *body, after, days })
+    }
+
+    /// Cast the solar return of a year at a location (latitude, longitude):
+    /// the Sun's return around the birthday of that year
+    pub fn solar_return(&self, year: i32, location: (f64, f64)) -> Result<PlanetaryReturn, ReturnError> {
+        let birth = self.natal.datetime;
+        // A birthday on 29 February is kept on 28 February in common years
+        let birthday = birth.with_year(year)
+            .or_else(|| (birth - Duration::days(1)).with_year(year))
+            .unwrap_or(birth);
+
+        self.planetary_return(&Planet::Sun, birthday - Duration::days(5), location)
+    }
+
+    /// Cast the first lunar return of a month at a location (latitude, longitude)
+    pub fn lunar_return(&self, year: i32, month: u32, location: (f64, f64)) -> Result<PlanetaryReturn, ReturnError> {
+        let start = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+            .single()
+            .unwrap_or(self.natal.datetime);
+
+        self.planetary_return(&Planet::Moon, start, location)
+    }
+
+    /// Cast the first return of a body after a date at a location (latitude, longitude)
+    pub fn planetary_return(&self, body: &Planet, after: DateTime<Utc>, location: (f64, f64)) -> Result<PlanetaryReturn, ReturnError> {
+        let moment = self.next_return(body, after)?;
+        self.cast(body, moment, location)
+    }
+
+    /// Cast the chart of a return of a body at a location (latitude,
+    /// longitude), in the natal house system
+    pub fn cast(&self, body: &Planet, moment: DateTime<Utc>, location: (f64, f64)) -> Result<PlanetaryReturn, ReturnError> {
+        let (latitude, longitude) = location;
+        let mut ephemeris = Ephemeris::with_backend(moment, latitude, longitude, self.backend)?;
+        if let Some(houses) = &self.natal.houses {
+            ephemeris.set_house_system(houses.system);
+        }
+        ephemeris.set_zodiac(self.natal.zodiac);
+
+        Ok(PlanetaryReturn {
+            body: *body,
+            zodiac: self.zodiac,
+            moment,
+            longitude: self.return_longitude(body, moment)?,
+            chart: ephemeris.chart_positions(),
+        })
+    }
+
+    /// Ephemeris at the birth place, starting at a moment
+    fn ephemeris(&self, moment: DateTime<Utc>) -> Result<Ephemeris, EphemerisError> {
+        let (latitude, longitude) = self.natal.location;
+        Ephemeris::with_backend(moment, latitude, longitude, self.backend)
+    }
+
+    /// Tropical longitude a natal position returns to at a moment
+    fn target(&self, natal: &PlanetPosition, moment: DateTime<Utc>) -> f64 {
+        match self.zodiac {
+            ReturnZodiac::Tropical => natal.longitude,
+            ReturnZodiac::Sidereal => {
+                precess_ecliptic(natal.longitude, natal.latitude, julian_day(&self.natal.datetime), julian_day(&moment)).0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+
+    fn natal() -> ChartPositions {
+        let birth = Utc.with_ymd_and_hms(1990, 7, 15, 14, 30, 0).unwrap();
+        Ephemeris::with_backend(birth, 48.85, 2.35, BackendKind::Analytic).unwrap().chart_positions()
+    }
+
+    fn longitude(body: &Planet, moment: DateTime<Utc>) -> f64 {
+        let ephemeris = Ephemeris::with_backend(moment, 0.0, 0.0, BackendKind::Analytic).unwrap();
+        ephemeris.get_planet_position(body).unwrap().longitude
+    }
+
+    #[test]
+    fn test_solar_return() {
+        let natal = natal();
+        let finder = ReturnFinder::with_backend(&natal, ReturnZodiac::Tropical, BackendKind::Analytic);
+        let solar_return = finder.solar_return(2024, (40.71, -74.01)).unwrap();
+
+        // Back on the natal Sun around the birthday, cast at the new location
+        let natal_sun = natal.get_position(&Planet::Sun).unwrap().longitude;
+        assert!(approx_eq!(f64, longitude(&Planet::Sun, solar_return.moment), natal_sun, epsilon = 1e-4));
+        assert!(approx_eq!(f64, solar_return.longitude, natal_sun, epsilon = 1e-9));
+        assert_eq!(solar_return.moment.year(), 2024);
+        assert!((solar_return.moment - Utc.with_ymd_and_hms(2024, 7, 15, 0, 0, 0).unwrap()).num_days().abs() <= 1);
+        assert_eq!(solar_return.chart.location, (40.71, -74.01));
+        assert_eq!(solar_return.chart.datetime, solar_return.moment);
+        assert!(solar_return.chart.houses.is_some());
+
+        // The sidereal return comes later by the time the Sun needs to cover
+        // the precession of 34 years (about 28', some 11.5 hours)
+        let sidereal = ReturnFinder::with_backend(&natal, ReturnZodiac::Sidereal, BackendKind::Analytic)
+            .solar_return(2024, (40.71, -74.01))
+            .unwrap();
+        let delay = (sidereal.moment - solar_return.moment).num_minutes();
+        assert!((630..750).contains(&delay), "sidereal return {delay} minutes later");
+        assert!(approx_eq!(f64, sidereal.longitude - natal_sun, 34.0 * 50.29 / 3600.0, epsilon = 0.01));
+    }
+
+    #[test]
+    fn test_lunar_return() {
+        let natal = natal();
+        let finder = ReturnFinder::with_backend(&natal, ReturnZodiac::Tropical, BackendKind::Analytic);
+        let lunar_return = finder.lunar_return(2024, 3, (48.85, 2.35)).unwrap();
+
+        let natal_moon = natal.get_position(&Planet::Moon).unwrap().longitude;
+        assert!(approx_eq!(f64, longitude(&Planet::Moon, lunar_return.moment), natal_moon, epsilon = 1e-4));
+        assert_eq!((lunar_return.moment.year(), lunar_return.moment.month()), (2024, 3));
+        assert!(lunar_return.moment.day() <= 28);
+
+        // Exactly one return a tropical month
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(finder.find_returns(&Planet::Moon, from, to).unwrap().len(), 13);
+    }
+
+    #[test]
+    fn test_retrograde_returns() {
+        // Mercury stations on its natal longitude are passed three times in a
+        // year when the return falls in a retrograde loop, otherwise once
+        let natal = natal();
+        let finder = ReturnFinder::with_backend(&natal, ReturnZodiac::Tropical, BackendKind::Analytic);
+        let from = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2010, 1, 1, 0, 0, 0).unwrap();
+
+        let natal_mercury = natal.get_position(&Planet::Mercury).unwrap().longitude;
+        let returns = finder.find_returns(&Planet::Mercury, from, to).unwrap();
+        assert!(returns.len() >= 10);
+        for moment in &returns {
+            assert!(approx_eq!(f64, longitude(&Planet::Mercury, *moment), natal_mercury, epsilon = 1e-4));
+        }
+        assert!(returns.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // A body missing from the chart cannot return
+        let mut partial = natal.clone();
+        partial.positions.remove(&Planet::Mars);
+        let finder = ReturnFinder::with_backend(&partial, ReturnZodiac::Tropical, BackendKind::Analytic);
+        assert!(matches!(finder.next_return(&Planet::Mars, from), Err(ReturnError::MissingBody(Planet::Mars))));
+    }
+}
@@ -0,0 +1,297 @@
+//! Progressed charts
+//!
+//! A progression reads a short stretch of the ephemeris after birth as the
+//! whole life: in the secondary progression each day after birth stands for
+//! a year of life, in the tertiary progression for a lunar month, and in the
+//! minor progression a lunar month stands for a year. The bodies are taken
+//! from the ephemeris at the progressed moment. The angles move with the
+//! sidereal time of that moment, or are advanced from the natal ones by the
+//! solar arc or the Naibod rate, depending on the chosen method.
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::ephemeris::nutation::true_obliquity;
+use crate::astrology::models::ephemeris::{at, delta_t, julian_day, BackendKind, Ephemeris, EphemerisError, DAY_MS};
+use crate::astrology::models::houses::Houses;
+use crate::astrology::models::planet::{ChartPositions, Planet, PlanetPosition};
+use crate::utils::angle::Angle;
+
+/// Mean motion of the Sun in degrees per day of the ephemeris (59'08.33"),
+/// i.e. per year of life in the secondary progression
+pub const NAIBOD_RATE: f64 = 0.985_647_36;
+
+/// Length of the tropical year in days
+pub const TROPICAL_YEAR: f64 = 365.242_19;
+
+/// Length of the tropical month (the Moon's return to the same longitude) in days
+pub const TROPICAL_MONTH: f64 = 27.321_582;
+
+/// Kind of progression: how a span of life maps onto the ephemeris
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum ProgressionKind {
+    /// A day for a year
+    #[default]
+    Secondary,
+    /// A day for a lunar month
+    Tertiary,
+    /// A lunar month for a year
+    Minor,
+}
+
+impl ProgressionKind {
+    /// Get the days of ephemeris after birth that correspond to an age in days
+    pub fn progressed_days(&self, age_days: f64) -> f64 {
+        match self {
+            ProgressionKind::Secondary => age_days / TROPICAL_YEAR,
+            ProgressionKind::Tertiary => age_days / TROPICAL_MONTH,
+            ProgressionKind::Minor => age_days / TROPICAL_YEAR * TROPICAL_MONTH,
+        }
+    }
+}
+
+/// How the MC, and with it the other angles, is progressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum MCProgression {
+    /// Natal MC plus the solar arc in longitude
+    #[default]
+    SolarArc,
+    /// Natal ARMC plus the solar arc in right ascension
+    SolarArcInRA,
+    /// Natal ARMC plus the Sun's mean motion over the progressed days
+    Naibod,
+    /// Houses cast for the progressed moment at the birth place
+    ProgressedMoment,
+}
+
+/// A chart progressed to a date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressedChart {
+    pub kind: ProgressionKind,
+    pub mc_progression: MCProgression,
+    /// Date the chart is progressed to
+    pub date: DateTime<Utc>,
+    /// Moment of the ephemeris the progressed positions are taken from
+    pub moment: DateTime<Utc>,
+    /// Progressed bodies and angles, each placed in its progressed house
+    pub positions: ChartPositions,
+}
+
+impl ProgressedChart {
+    /// Get the progressed bodies and angles as points, in the order of [`Planet`]
+    pub fn points(&self) -> Vec<(ChartPoint, Angle)> {
+        self.positions.points()
+    }
+}
+
+/// Progressions of a natal chart
+#[derive(Debug, Clone)]
+pub struct Progression {
+    kind: ProgressionKind,
+    mc_progression: MCProgression,
+    /// Ephemeris of the birth, in the natal house system
+    ephemeris: Ephemeris,
+}
+
+impl Progression {
+    /// Prepare the progressions of a natal chart, using the best available backend
+    pub fn new(natal: &ChartPositions, kind: ProgressionKind, mc_progression: MCProgression) -> Result<Self, EphemerisError> {
+        Self::with_backend(natal, kind, mc_progression, BackendKind::detect())
+    }
+
+    /// Prepare the progressions of a natal chart with a specific backend
+    pub fn with_backend(
+        natal: &ChartPositions,
+        kind: ProgressionKind,
+        mc_progression: MCProgression,
+        backend: BackendKind,
+    ) -> Result<Self, EphemerisError> {
+        let (latitude, longitude) = natal.location;
+        let mut ephemeris = Ephemeris::with_backend(natal.datetime, latitude, longitude, backend)?;
+        if let Some(houses) = &natal.houses {
+            ephemeris.set_house_system(houses.system);
+        }
+        ephemeris.set_zodiac(natal.zodiac);
+
+        Ok(Self {
+            kind,
+            mc_progression,
+            ephemeris,
+        })
+    }
+
+    /// Get the moment of birth
+    pub fn birth(&self) -> DateTime<Utc> {
+        self.ephemeris.datetime()
+    }
+
+    /// Get the days of ephemeris after birth that correspond to a date
+    pub fn progressed_days(&self, date: DateTime<Utc>) -> f64 {
+        self.kind.progressed_days((date - self.birth()).num_milliseconds() as f64 / DAY_MS)
+    }
+
+    /// Get the moment of the ephemeris that corresponds to a date
+    pub fn progressed_moment(&self, date: DateTime<Utc>) -> DateTime<Utc> {
+        at(self.birth(), self.progressed_days(date))
+    }
+
+    /// Progress the chart to a date
+    pub fn progress(&self, date: DateTime<Utc>) -> Result<ProgressedChart, EphemerisError> {
+        let moment = self.progressed_moment(date);
+        let mut ephemeris = self.ephemeris.clone();
+        ephemeris.update_time(moment)?;
+        let mut positions = ephemeris.chart_positions();
+
+        if self.mc_progression != MCProgression::ProgressedMoment {
+            let houses = self.progressed_houses(&ephemeris, self.progressed_days(date))?;
+            for planet in [Planet::Ascendant, Planet::MC, Planet::Vertex, Planet::EastPoint] {
+                if let (Some(longitude), Some(position)) = (houses.angle(&planet), positions.get_position(&planet)) {
+                    let mut angle = PlanetPosition::new(planet, longitude, 0.0, 0.0, position.speed, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1);
+                    angle.set_zodiac(position.zodiac, position.ayanamsa);
+                    angle.set_obliquity(position.obliquity);
+                    positions.add_position(angle);
+                }
+            }
+            for position in positions.positions.values_mut() {
+                position.set_house(houses.house_of(position.longitude));
+            }
+            positions.houses = Some(houses);
+        }
+
+        Ok(ProgressedChart {
+            kind: self.kind,
+            mc_progression: self.mc_progression,
+            date,
+            moment,
+            positions,
+        })
+    }
+
+    /// Houses of the progressed angles, advanced from the natal ARMC
+    fn progressed_houses(&self, progressed: &Ephemeris, days: f64) -> Result<Houses, EphemerisError> {
+        let natal_houses = self.ephemeris.houses();
+        let (latitude, _) = self.ephemeris.location();
+        let natal_obliquity = obliquity(self.birth());
+        let obliquity = obliquity(progressed.datetime());
+
+        let armc = match self.mc_progression {
+            MCProgression::Naibod => natal_houses.armc + NAIBOD_RATE * days,
+            MCProgression::SolarArc => {
+                let arc = self.solar_arc(progressed)?;
+                right_ascension(natal_houses.mc + arc, obliquity)
+            }
+            MCProgression::SolarArcInRA => {
+                let natal_sun = self.ephemeris.get_planet_position(&Planet::Sun)?.longitude;
+                let progressed_sun = progressed.get_planet_position(&Planet::Sun)?.longitude;
+                natal_houses.armc + right_ascension(progressed_sun, obliquity) - right_ascension(natal_sun, natal_obliquity)
+            }
+            MCProgression::ProgressedMoment => return Ok(progressed.houses()),
+        };
+
+        Ok(Houses::from_armc(natal_houses.system, armc.rem_euclid(360.0), latitude, obliquity))
+    }
+
+    /// Arc of the progressed Sun from the natal Sun in longitude
+    fn solar_arc(&self, progressed: &Ephemeris) -> Result<f64, EphemerisError> {
+        let natal_sun = self.ephemeris.get_planet_position(&Planet::Sun)?.longitude;
+        let progressed_sun = progressed.get_planet_position(&Planet::Sun)?.longitude;
+        Ok((progressed_sun - natal_sun).rem_euclid(360.0))
+    }
+}
+
+/// True obliquity of the ecliptic at a moment
+pub(crate) fn obliquity(datetime: DateTime<Utc>) -> f64 {
+    let jd_ut = julian_day(&datetime);
+    true_obliquity(jd_ut + delta_t(jd_ut) / 86_400.0)
+}
+
+/// Right ascension of a point on the ecliptic in degrees
+fn right_ascension(longitude: f64, obliquity: f64) -> f64 {
+    let (longitude, obliquity) = (longitude.to_radians(), obliquity.to_radians());
+    (longitude.sin() * obliquity.cos()).atan2(longitude.cos()).to_degrees().rem_euclid(360.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use float_cmp::approx_eq;
+
+    fn birth() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(1980, 5, 10, 6, 30, 0).unwrap()
+    }
+
+    fn natal() -> ChartPositions {
+        Ephemeris::with_backend(birth(), 48.1, 11.6, BackendKind::Analytic).unwrap().chart_positions()
+    }
+
+    fn progression(kind: ProgressionKind, mc_progression: MCProgression) -> Progression {
+        Progression::with_backend(&natal(), kind, mc_progression, BackendKind::Analytic).unwrap()
+    }
+
+    #[test]
+    fn test_progressed_moments() {
+        let thirty = at(birth(), 30.0 * TROPICAL_YEAR);
+
+        let secondary = progression(ProgressionKind::Secondary, MCProgression::SolarArc);
+        assert!(approx_eq!(f64, secondary.progressed_days(thirty), 30.0, epsilon = 1e-6));
+
+        let minor = progression(ProgressionKind::Minor, MCProgression::SolarArc);
+        assert!(approx_eq!(f64, minor.progressed_days(thirty), 30.0 * TROPICAL_MONTH, epsilon = 1e-6));
+
+        // A year of life is about 13.4 lunar months
+        let tertiary = progression(ProgressionKind::Tertiary, MCProgression::SolarArc);
+        let one = at(birth(), TROPICAL_YEAR);
+        assert!(approx_eq!(f64, tertiary.progressed_days(one), 13.368, epsilon = 1e-3));
+        assert!((tertiary.progressed_moment(one) - birth()).num_days() == 13);
+    }
+
+    #[test]
+    fn test_secondary_progression() {
+        let natal = natal();
+        let date = Utc.with_ymd_and_hms(2010, 5, 10, 0, 0, 0).unwrap();
+        let progressed = progression(ProgressionKind::Secondary, MCProgression::SolarArc).progress(date).unwrap();
+        let longitude = |chart: &ChartPositions, planet| chart.get_position(&planet).unwrap().longitude;
+
+        // Thirty years later the Sun has moved about 29° and the MC with it
+        let arc = (longitude(&progressed.positions, Planet::Sun) - longitude(&natal, Planet::Sun)).rem_euclid(360.0);
+        assert!(arc > 28.5 && arc < 29.5, "{arc}");
+        let mc_arc = (longitude(&progressed.positions, Planet::MC) - longitude(&natal, Planet::MC)).rem_euclid(360.0);
+        assert!(approx_eq!(f64, mc_arc, arc, epsilon = 1e-9));
+
+        // Progressed houses follow the progressed angles
+        let houses = progressed.positions.houses.as_ref().unwrap();
+        assert!(approx_eq!(f64, houses.mc, longitude(&progressed.positions, Planet::MC), epsilon = 1e-9));
+        assert_eq!(progressed.positions.get_position(&Planet::MC).unwrap().house, Some(10));
+        assert_eq!(progressed.points()[0].0, Planet::Sun);
+    }
+
+    #[test]
+    fn test_mc_progression_methods() {
+        let date = Utc.with_ymd_and_hms(2010, 5, 10, 0, 0, 0).unwrap();
+        let natal_houses = natal().houses.unwrap();
+        let armc = |mc_progression| {
+            progression(ProgressionKind::Secondary, mc_progression).progress(date).unwrap().positions.houses.unwrap().armc
+        };
+
+        // Naibod: the mean motion for each day of ephemeris
+        let days = progression(ProgressionKind::Secondary, MCProgression::Naibod).progressed_days(date);
+        let naibod = (armc(MCProgression::Naibod) - natal_houses.armc).rem_euclid(360.0);
+        assert!(approx_eq!(f64, naibod, NAIBOD_RATE * days, epsilon = 1e-6));
+
+        // Solar arc in right ascension is close to, but not the same as, in longitude
+        let in_ra = (armc(MCProgression::SolarArcInRA) - natal_houses.armc).rem_euclid(360.0);
+        let in_longitude = (armc(MCProgression::SolarArc) - natal_houses.armc).rem_euclid(360.0);
+        assert!((in_ra - in_longitude).abs() > 1e-3 && (in_ra - in_longitude).abs() < 5.0);
+
+        // The progressed moment: about a degree of sidereal time per day, plus
+        // the time of day of the progressed moment
+        let moment = progression(ProgressionKind::Secondary, MCProgression::ProgressedMoment).progress(date).unwrap();
+        let expected = Houses::calculate(natal_houses.system, julian_day(&moment.moment), 48.1, 11.6);
+        assert!(approx_eq!(f64, moment.positions.houses.unwrap().mc, expected.mc, epsilon = 1e-9));
+    }
+}
@@ -0,0 +1,205 @@
+//! Composite and Davison relationship charts
+//!
+//! Both turn two charts into one that stands for the relationship. The
+//! composite chart places every body on the near midpoint of its two
+//! positions, and casts the houses from the midpoint of the two ARMCs at the
+//! mean latitude. The Davison chart is an ordinary chart cast for the moment
+//! halfway between the two births at the place halfway between them. Either
+//! is a [`ChartPositions`] like any other, dated at the mean moment and placed
+//! at the mean location.
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::astrology::models::ephemeris::{julian_day, BackendKind, Ephemeris, EphemerisError};
+use crate::astrology::models::houses::{HouseSystem, Houses};
+use crate::astrology::models::planet::{ChartPositions, PlanetPosition};
+use crate::astrology::models::progression::obliquity;
+use crate::utils::angle::Angle;
+
+/// Kind of relationship chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum RelationshipKind {
+    /// Midpoints of the two charts
+    #[default]
+    Composite,
+    /// Chart of the mean time and location
+    Davison,
+}
+
+/// Get the moment halfway between two moments
+pub fn mean_time(first: DateTime<Utc>, second: DateTime<Utc>) -> DateTime<Utc> {
+    first + (second - first) / 2
+}
+
+/// Get the place halfway between two locations (latitude, longitude): the
+/// mean latitude and the mean of the longitudes on the shorter arc
+pub fn mean_location(first: (f64, f64), second: (f64, f64)) -> (f64, f64) {
+    let longitude = Angle::from_degrees(first.1).midpoint(Angle::from_degrees(second.1)).degrees();
+    let longitude = if longitude > 180.0 { longitude - 360.0 } else { longitude };
+    ((first.0 + second.0) / 2.0, longitude)
+}
+
+/// Cast the composite chart of two charts, with the houses in the system,
+/// the signs in the zodiac and the positions in the coordinate frame of the
+/// first one
+///
+/// Only the bodies found in both charts are kept. Speeds, latitudes and
+/// distances are averaged, so a body is retrograde when it moves backwards on
+/// average.
+pub fn composite_chart(first: &ChartPositions, second: &ChartPositions) -> ChartPositions {
+    let mut chart = ChartPositions::new(mean_time(first.datetime, second.datetime), mean_location(first.location, second.location));
+    chart.zodiac = first.zodiac;
+    chart.frame = first.frame;
+    let ayanamsa = chart.zodiac.ayanamsa(julian_day(&chart.datetime));
+    let obliquity = obliquity(chart.datetime);
+
+    if let (Some(houses1), Some(houses2)) = (&first.houses, &second.houses) {
+        let armc = Angle::from_degrees(houses1.armc).midpoint(Angle::from_degrees(houses2.armc)).degrees();
+        chart.houses = Some(Houses::from_armc(houses1.system, armc, chart.location.0, obliquity));
+    }
+
+    for (planet, position1) in &first.positions {
+        let position2 = match second.get_position(planet) {
+            Some(position2) => position2,
+            None => continue,
+        };
+
+        let longitude = Angle::from_degrees(position1.longitude)
+            .midpoint(Angle::from_degrees(position2.longitude))
+            .degrees();
+        let speed = (position1.speed + position2.speed) / 2.0;
+        let mut position = PlanetPosition::new(
+            *planet,
+            longitude,
+            (position1.latitude + position2.latitude) / 2.0,
+            (position1.distance_au + position2.distance_au) / 2.0,
+            speed,
+            (position1.speed_latitude + position2.speed_latitude) / 2.0,
+            speed < 0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1,
+        );
+        position.set_zodiac(chart.zodiac, ayanamsa);
+        position.set_obliquity(obliquity);
+        if let Some(houses) = chart.houses.as_ref().filter(|_| chart.frame.is_terrestrial()) {
+            position.set_house(houses.house_of(longitude));
+        }
+        chart.add_position(position);
+    }
+
+    chart
+}
+
+/// Cast the Davison chart of two charts, using the best available backend
+pub fn davison_chart(first: &ChartPositions, second: &ChartPositions) -> Result<ChartPositions, EphemerisError> {
+    davison_chart_with_backend(first, second, BackendKind::detect())
+}
+
+/// Cast the Davison chart of two charts with a specific backend, with the
+/// houses in the system, the signs in the zodiac and the positions in the
+/// coordinate frame of the first one
+pub fn davison_chart_with_backend(
+    first: &ChartPositions,
+    second: &ChartPositions,
+    backend: BackendKind,
+) -> Result<ChartPositions, EphemerisError> {
+    let (latitude, longitude) = mean_location(first.location, second.location);
+    let mut ephemeris = Ephemeris::with_backend(mean_time(first.datetime, second.datetime), latitude, longitude, backend)?;
+    ephemeris.set_house_system(first.houses.as_ref().map_or(HouseSystem::default(), |houses| houses.system));
+    ephemeris.set_zodiac(first.zodiac);
+    ephemeris.set_frame(first.frame);
+
+    Ok(ephemeris.chart_positions())
+}
+
+/// Cast a relationship chart of two charts
+pub fn relationship_chart(
+    kind: RelationshipKind,
+    first: &ChartPositions,
+    second: &ChartPositions,
+) -> Result<ChartPositions, EphemerisError> {
+    match kind {
+        RelationshipKind::Composite => Ok(composite_chart(first, second)),
+        RelationshipKind::Davison => davison_chart(first, second),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::astrology::models::planet::Planet;
+    use float_cmp::approx_eq;
+
+    fn chart(datetime: DateTime<Utc>, latitude: f64, longitude: f64) -> ChartPositions {
+        Ephemeris::with_backend(datetime, latitude, longitude, BackendKind::Analytic).unwrap().chart_positions()
+    }
+
+    #[test]
+    fn test_mean_time_and_location() {
+        let first = Utc.with_ymd_and_hms(1980, 1, 1, 0, 0, 0).unwrap();
+        let second = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+        assert_eq!(mean_time(first, second), mean_time(second, first));
+        assert_eq!(mean_time(first, second), Utc.with_ymd_and_hms(1984, 12, 31, 18, 0, 0).unwrap());
+
+        // Across the date line the mean longitude stays in the Pacific
+        let (latitude, longitude) = mean_location((-36.85, 174.76), (21.31, -157.86));
+        assert!(approx_eq!(f64, latitude, -7.77, epsilon = 1e-9));
+        assert!(approx_eq!(f64, longitude, -171.55, epsilon = 1e-9));
+        assert!(approx_eq!(f64, mean_location((10.0, -20.0), (20.0, 40.0)).1, 10.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn test_composite_chart() {
+        let first = chart(Utc.with_ymd_and_hms(1975, 3, 10, 8, 0, 0).unwrap(), 51.5, -0.13);
+        let second = chart(Utc.with_ymd_and_hms(1978, 9, 2, 20, 30, 0).unwrap(), 40.42, -3.7);
+        let composite = composite_chart(&first, &second);
+
+        // Each body on the near midpoint of its two positions
+        for planet in [Planet::Sun, Planet::Moon, Planet::Saturn, Planet::MC] {
+            let longitude1 = first.get_position(&planet).unwrap().longitude;
+            let longitude2 = second.get_position(&planet).unwrap().longitude;
+            let longitude = composite.get_position(&planet).unwrap().longitude;
+            let distance = Angle::from_degrees(longitude).distance_to(Angle::from_degrees(longitude1)).degrees();
+            assert!(approx_eq!(f64, distance, Angle::from_degrees(longitude1).distance_to(Angle::from_degrees(longitude2)).degrees() / 2.0, epsilon = 1e-9));
+            assert!(distance <= 90.0);
+        }
+        assert_eq!(composite.positions.len(), first.positions.len());
+        assert!(composite.positions.values().all(|position| position.house.is_some()));
+
+        // Houses from the mean ARMC: the composite MC falls close to the
+        // midpoint of the MCs, or opposite it
+        let houses = composite.houses.as_ref().unwrap();
+        let mc = composite.get_position(&Planet::MC).unwrap().longitude;
+        let separation = Angle::from_degrees(houses.mc).distance_to(Angle::from_degrees(mc)).degrees();
+        assert!(separation.min(180.0 - separation) < 5.0, "composite MC {} against {}", houses.mc, mc);
+        assert_eq!(composite.location, mean_location(first.location, second.location));
+    }
+
+    #[test]
+    fn test_davison_chart() {
+        let first = chart(Utc.with_ymd_and_hms(1975, 3, 10, 8, 0, 0).unwrap(), 51.5, -0.13);
+        let second = chart(Utc.with_ymd_and_hms(1978, 9, 2, 20, 30, 0).unwrap(), 40.42, -3.7);
+        let davison = davison_chart_with_backend(&first, &second, BackendKind::Analytic).unwrap();
+
+        // An ordinary chart of the mean moment and place
+        let expected = chart(mean_time(first.datetime, second.datetime), 45.96, -1.915);
+        assert_eq!(davison.datetime, expected.datetime);
+        assert!(approx_eq!(f64, davison.location.0, 45.96, epsilon = 1e-9));
+        assert!(approx_eq!(f64, davison.location.1, -1.915, epsilon = 1e-9));
+        for planet in [Planet::Sun, Planet::Moon, Planet::Ascendant] {
+            assert!(approx_eq!(
+                f64,
+                davison.get_position(&planet).unwrap().longitude,
+                expected.get_position(&planet).unwrap().longitude,
+                epsilon = 1e-6
+            ));
+        }
+    }
+}
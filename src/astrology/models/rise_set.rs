@@ -0,0 +1,364 @@
+//! Rising, setting and culmination times
+//!
+//! A body rises and sets when the top of its disc crosses the horizon of the
+//! observer, and culminates when it crosses the meridian: above the pole at
+//! the upper culmination, below it at the lower one. Altitudes are those of
+//! the topocentric place of the body, raised by the atmospheric refraction
+//! unless it is turned off, and the horizon may be set above or below the
+//! astronomical one (a mountain range, the sea seen from a height).
+//!
+//! The body is sampled every ten minutes over the day; every change of side
+//! of the horizon or of the meridian between two samples is refined by
+//! bisection.
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::astrology::models::ephemeris::{at, refraction, CoordinateFrame, Ephemeris, EphemerisError};
+use crate::astrology::models::planet::{BodyType, Planet, PlanetPosition};
+
+/// Sampling step in days (ten minutes)
+const STEP: f64 = 1.0 / 144.0;
+
+/// Precision of the event times in days (about a second)
+const TIME_PRECISION: f64 = 1e-5;
+
+/// Radius of the Sun in AU (696 000 km)
+const SUN_RADIUS_AU: f64 = 696_000.0 / 149_597_870.7;
+
+/// Radius of the Moon in AU (1737.4 km)
+const MOON_RADIUS_AU: f64 = 1737.4 / 149_597_870.7;
+
+/// Crossings of the horizon and the meridian
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum HorizonEventKind {
+    /// Top of the disc appearing above the horizon
+    Rise,
+    /// Crossing of the meridian above the pole, highest in the sky
+    UpperCulmination,
+    /// Top of the disc disappearing below the horizon
+    Set,
+    /// Crossing of the meridian below the pole, lowest in the sky
+    LowerCulmination,
+}
+
+/// Whether a body crosses the horizon during the day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum Visibility {
+    /// The body rises or sets at least once
+    RisesAndSets,
+    /// The body stays above the horizon all day
+    Circumpolar,
+    /// The body stays below the horizon all day
+    NeverRises,
+}
+
+/// A crossing of the horizon or the meridian
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HorizonEvent {
+    pub kind: HorizonEventKind,
+    pub moment: DateTime<Utc>,
+    /// Altitude of the centre of the body in degrees, refracted when the
+    /// finder applies refraction
+    pub altitude: f64,
+    /// Azimuth in degrees, from the north through the east
+    pub azimuth: f64,
+}
+
+/// Crossings of the horizon and the meridian by a body over a day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiseSetTimes {
+    pub body: Planet,
+    /// Start of the day searched
+    pub from: DateTime<Utc>,
+    /// Every crossing of the day, in order of time
+    pub events: Vec<HorizonEvent>,
+    pub visibility: Visibility,
+}
+
+impl RiseSetTimes {
+    /// Get the first crossing of a kind during the day
+    pub fn first(&self, kind: HorizonEventKind) -> Option<&HorizonEvent> {
+        self.events.iter().find(|event| event.kind == kind)
+    }
+
+    /// Get the first rise of the day
+    pub fn rise(&self) -> Option<&HorizonEvent> {
+        self.first(HorizonEventKind::Rise)
+    }
+
+    /// Get the first upper culmination of the day
+    pub fn upper_culmination(&self) -> Option<&HorizonEvent> {
+        self.first(HorizonEventKind::UpperCulmination)
+    }
+
+    /// Get the first set of the day
+    pub fn set(&self) -> Option<&HorizonEvent> {
+        self.first(HorizonEventKind::Set)
+    }
+
+    /// Get the first lower culmination of the day
+    pub fn lower_culmination(&self) -> Option<&HorizonEvent> {
+        self.first(HorizonEventKind::LowerCulmination)
+    }
+}
+
+/// Position of the body relative to the horizon and the meridian
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    /// Days after the start of the search
+    days: f64,
+    /// Height of the top of the disc above the horizon in degrees
+    height: f64,
+    /// Positive west of the meridian, negative east of it (minus the sine of
+    /// the azimuth)
+    west: f64,
+    altitude: f64,
+    azimuth: f64,
+}
+
+/// Finds the rising, setting and culmination times of the bodies for an
+/// observer
+#[derive(Debug, Clone)]
+pub struct RiseSetFinder {
+    /// Ephemeris supplying the backend and observer location
+    ephemeris: Ephemeris,
+    /// Whether the altitudes are raised by the atmospheric refraction
+    refraction: bool,
+    /// Altitude of the horizon in degrees
+    horizon: f64,
+}
+
+impl RiseSetFinder {
+    /// Create a finder with the backend and location of an ephemeris, with
+    /// refraction and the astronomical horizon
+    pub fn new(ephemeris: &Ephemeris) -> Self {
+        let mut ephemeris = ephemeris.clone();
+        ephemeris.set_frame(CoordinateFrame::Geocentric);
+
+        Self {
+            ephemeris,
+            refraction: true,
+            horizon: 0.0,
+        }
+    }
+
+    /// Apply the atmospheric refraction of standard conditions or not
+    pub fn with_refraction(mut self, refraction: bool) -> Self {
+        self.refraction = refraction;
+        self
+    }
+
+    /// Set the altitude of the horizon in degrees, negative below the
+    /// astronomical horizon
+    pub fn with_horizon(mut self, altitude: f64) -> Self {
+        self.horizon = altitude.clamp(-90.0, 90.0);
+        self
+    }
+
+    /// Find the crossings of the horizon and the meridian by a body during
+    /// the 24 hours starting at `from`
+    ///
+    /// The angles have no place of their own in the sky and are not supported.
+    pub fn find(&self, body: &Planet, from: DateTime<Utc>) -> Result<RiseSetTimes, EphemerisError> {
+        if matches!(body.body_type(), BodyType::Angle | BodyType::HouseCusp) {
+            return Err(EphemerisError::UnsupportedBody(*body));
+        }
+
+        let mut ephemeris = self.ephemeris.clone();
+        let mut previous = self.sample(&mut ephemeris, body, from, 0.0)?;
+        let above = previous.height > 0.0;
+        let mut events = Vec::new();
+
+        while previous.days < 1.0 {
+            let next = self.sample(&mut ephemeris, body, from, (previous.days + STEP).min(1.0))?;
+
+            if (previous.height > 0.0) != (next.height > 0.0) {
+                let kind = if next.height > 0.0 { HorizonEventKind::Rise } else { HorizonEventKind::Set };
+                let exact = self.refine(&mut ephemeris, body, from, previous, next, |sample| sample.height)?;
+                events.push(self.event(kind, from, exact));
+            }
+            if (previous.west > 0.0) != (next.west > 0.0) {
+                let kind = if next.west > 0.0 { HorizonEventKind::UpperCulmination } else { HorizonEventKind::LowerCulmination };
+                let exact = self.refine(&mut ephemeris, body, from, previous, next, |sample| sample.west)?;
+                events.push(self.event(kind, from, exact));
+            }
+
+            previous = next;
+        }
+
+        events.sort_by_key(|event| event.moment);
+        let visibility = if events.iter().any(|event| matches!(event.kind, HorizonEventKind::Rise | HorizonEventKind::Set)) {
+            Visibility::RisesAndSets
+        } else if above {
+            Visibility::Circumpolar
+        } else {
+            Visibility::NeverRises
+        };
+
+        Ok(RiseSetTimes {
+            body: *body,
+            from,
+            events,
+            visibility,
+        })
+    }
+
+    /// Find the crossings of every body that has them during the 24 hours
+    /// starting at `from`
+    pub fn find_all(&self, bodies: &[Planet], from: DateTime<Utc>) -> Result<Vec<RiseSetTimes>, EphemerisError> {
+        bodies.iter()
+            .filter(|body| !matches!(body.body_type(), BodyType::Angle | BodyType::HouseCusp))
+            .map(|body| self.find(body, from))
+            .collect()
+    }
+
+    /// Position of a body relative to the horizon and the meridian a number
+    /// of days after `from`
+    fn sample(&self, ephemeris: &mut Ephemeris, body: &Planet, from: DateTime<Utc>, days: f64) -> Result<Sample, EphemerisError> {
+        ephemeris.update_time(at(from, days))?;
+        let position = ephemeris.get_planet_position(body)?;
+        let altitude = self.apparent_altitude(position.altitude);
+
+        Ok(Sample {
+            days,
+            height: altitude + semidiameter(&position) - self.horizon,
+            west: -position.azimuth.to_radians().sin(),
+            altitude,
+            azimuth: position.azimuth,
+        })
+    }
+
+    /// Refine a crossing bracketed by two samples by bisection, on the
+    /// quantity changing sign
+    fn refine(
+        &self,
+        ephemeris: &mut Ephemeris,
+        body: &Planet,
+        from: DateTime<Utc>,
+        mut start: Sample,
+        mut end: Sample,
+        value: impl Fn(&Sample) -> f64,
+    ) -> Result<Sample, EphemerisError> {
+        while end.days - start.days > TIME_PRECISION {
+            let middle = self.sample(ephemeris, body, from, (start.days + end.days) / 2.0)?;
+            if (value(&middle) > 0.0) == (value(&start) > 0.0) {
+                start = middle;
+            } else {
+                end = middle;
+            }
+        }
+
+        Ok(end)
+    }
+
+    /// Altitude seen by the observer of a geometric altitude
+    fn apparent_altitude(&self, altitude: f64) -> f64 {
+        if self.refraction {
+            altitude + refraction(altitude)
+        } else {
+            altitude
+        }
+    }
+
+    fn event(&self, kind: HorizonEventKind, from: DateTime<Utc>, sample: Sample) -> HorizonEvent {
+        HorizonEvent {
+            kind,
+            moment: at(from, sample.days),
+            altitude: sample.altitude,
+            azimuth: sample.azimuth,
+        }
+    }
+}
+
+/// Apparent radius in degrees of the disc of the Sun or the Moon; the other
+/// bodies are taken as points
+fn semidiameter(position: &PlanetPosition) -> f64 {
+    let radius = match position.planet {
+        Planet::Sun => SUN_RADIUS_AU,
+        Planet::Moon => MOON_RADIUS_AU,
+        _ => return 0.0,
+    };
+    if position.distance_au > radius {
+        (radius / position.distance_au).asin().to_degrees()
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::astrology::models::ephemeris::BackendKind;
+
+    fn finder(datetime: DateTime<Utc>, latitude: f64, longitude: f64) -> RiseSetFinder {
+        RiseSetFinder::new(&Ephemeris::with_backend(datetime, latitude, longitude, BackendKind::Analytic).unwrap())
+    }
+
+    fn assert_near(event: Option<&HorizonEvent>, expected: DateTime<Utc>) {
+        let moment = event.expect("missing event").moment;
+        assert!((moment - expected).num_seconds().abs() <= 120, "{moment} instead of {expected}");
+    }
+
+    #[test]
+    fn test_venus_at_boston() {
+        // Meeus, example 15.a: Venus seen from Boston on 1988 March 20
+        let day = Utc.with_ymd_and_hms(1988, 3, 20, 0, 0, 0).unwrap();
+        let times = finder(day, 42.3333, -71.0833).find(&Planet::Venus, day).unwrap();
+
+        assert_eq!(times.visibility, Visibility::RisesAndSets);
+        assert_near(times.set(), Utc.with_ymd_and_hms(1988, 3, 20, 2, 55, 0).unwrap());
+        assert_near(times.rise(), Utc.with_ymd_and_hms(1988, 3, 20, 12, 25, 0).unwrap());
+        assert_near(times.upper_culmination(), Utc.with_ymd_and_hms(1988, 3, 20, 19, 41, 0).unwrap());
+        assert!(times.lower_culmination().is_some());
+        assert!(times.events.windows(2).all(|pair| pair[0].moment <= pair[1].moment));
+
+        // Highest in the south, rising in the east
+        let culmination = times.upper_culmination().unwrap();
+        assert!((culmination.azimuth - 180.0).abs() < 0.1, "{}", culmination.azimuth);
+        assert!((times.rise().unwrap().azimuth - 90.0).abs() < 30.0);
+    }
+
+    #[test]
+    fn test_sun_at_greenwich() {
+        let day = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let finder = finder(day, 51.4769, 0.0);
+        let times = finder.find(&Planet::Sun, day).unwrap();
+
+        assert_near(times.rise(), Utc.with_ymd_and_hms(2000, 1, 1, 8, 6, 0).unwrap());
+        assert_near(times.upper_culmination(), Utc.with_ymd_and_hms(2000, 1, 1, 12, 3, 0).unwrap());
+        assert_near(times.set(), Utc.with_ymd_and_hms(2000, 1, 1, 16, 2, 0).unwrap());
+
+        // Without refraction the day is shorter; behind hills, shorter still
+        let geometric = finder.clone().with_refraction(false).find(&Planet::Sun, day).unwrap();
+        assert!(geometric.rise().unwrap().moment > times.rise().unwrap().moment);
+        let hills = finder.with_horizon(2.0).find(&Planet::Sun, day).unwrap();
+        assert!(hills.rise().unwrap().moment > geometric.rise().unwrap().moment);
+        assert!(hills.set().unwrap().moment < geometric.set().unwrap().moment);
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        let summer = Utc.with_ymd_and_hms(2000, 6, 21, 0, 0, 0).unwrap();
+        let times = finder(summer, 80.0, 15.0).find(&Planet::Sun, summer).unwrap();
+        assert_eq!(times.visibility, Visibility::Circumpolar);
+        assert!(times.rise().is_none() && times.set().is_none());
+        assert!(times.upper_culmination().is_some() && times.lower_culmination().is_some());
+
+        let winter = Utc.with_ymd_and_hms(2000, 12, 21, 0, 0, 0).unwrap();
+        let times = finder(winter, 80.0, 15.0).find(&Planet::Sun, winter).unwrap();
+        assert_eq!(times.visibility, Visibility::NeverRises);
+    }
+
+    #[test]
+    fn test_angles_are_unsupported() {
+        let day = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let finder = finder(day, 51.4769, 0.0);
+        assert!(matches!(finder.find(&Planet::Ascendant, day), Err(EphemerisError::UnsupportedBody(Planet::Ascendant))));
+        assert_eq!(finder.find_all(&[Planet::Sun, Planet::MC, Planet::Moon], day).unwrap().len(), 2);
+    }
+}
@@ -1,5 +1,10 @@
 use serde::{Serialize, Deserialize};
 use std::fmt;
+use std::str::FromStr;
+
+use crate::astrology::models::ephemeris::{delta_t, J2000};
+use crate::astrology::models::ephemeris::nutation::nutation;
+use crate::astrology::models::ephemeris::precession::general_precession;
 
 /// Represents the 12 zodiac signs
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -91,6 +96,13 @@ impl ZodiacSign {
         }
     }
 
+    /// Get the zodiac sign and the degree within it for a tropical longitude
+    /// in a zodiac whose 0° Aries lies `ayanamsa` degrees after the equinox
+    /// (0 for the tropical zodiac)
+    pub fn from_degree_in(degree: f64, ayanamsa: f64) -> (Self, f64) {
+        Self::from_degree((degree - ayanamsa).rem_euclid(360.0))
+    }
+
     /// Get the zodiac sign for a given degree (0-360)
     pub fn from_degree(degree: f64) -> (Self, f64) {
         let normalized_degree = degree % 360.0;
@@ -137,6 +149,101 @@ impl fmt::Display for ZodiacSign {
     }
 }
 
+/// Ayanamsa: the distance from the vernal equinox back to 0° Aries of a
+/// sidereal zodiac, growing with the precession
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Ayanamsa {
+    /// Indian national standard (Chitrapaksha, Spica at 0° Libra)
+    Lahiri,
+    /// Western sidereal zodiac of Cyril Fagan and Donald Bradley
+    FaganBradley,
+    /// Krishnamurti Paddhati
+    Krishnamurti,
+    /// B. V. Raman
+    Raman,
+    /// Ayanamsa of `value` degrees at the Julian day (UT) `epoch`, growing by
+    /// `rate` arcseconds a year
+    UserDefined { epoch: f64, value: f64, rate: f64 },
+}
+
+impl Ayanamsa {
+    /// Get the ayanamsa in degrees for a Julian day (UT)
+    ///
+    /// The standard ayanamsas are true values: their mean value at J2000
+    /// carried along by the general precession, plus the nutation in
+    /// longitude, so that they apply to apparent positions.
+    pub fn value(&self, jd_ut: f64) -> f64 {
+        let at_j2000 = match self {
+            Ayanamsa::Lahiri => 23.857_092,
+            Ayanamsa::FaganBradley => 24.740_300,
+            Ayanamsa::Krishnamurti => 23.760_240,
+            Ayanamsa::Raman => 22.410_791,
+            Ayanamsa::UserDefined { epoch, value, rate } => {
+                return value + rate * (jd_ut - epoch) / 365.25 / 3600.0;
+            }
+        };
+
+        let jd_tt = jd_ut + delta_t(jd_ut) / 86_400.0;
+        at_j2000 + general_precession(J2000, jd_tt) + nutation(jd_tt).0
+    }
+}
+
+/// Zodiac the signs and degrees of the positions are counted in
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Zodiac {
+    /// Signs measured from the vernal equinox
+    #[default]
+    Tropical,
+    /// Signs fixed among the stars, measured from the equinox less an ayanamsa
+    Sidereal(Ayanamsa),
+}
+
+impl Zodiac {
+    /// Get the ayanamsa in degrees for a Julian day (UT), 0 in the tropical zodiac
+    pub fn ayanamsa(&self, jd_ut: f64) -> f64 {
+        match self {
+            Zodiac::Tropical => 0.0,
+            Zodiac::Sidereal(ayanamsa) => ayanamsa.value(jd_ut),
+        }
+    }
+
+    /// Convert a tropical longitude to this zodiac for a Julian day (UT)
+    pub fn longitude(&self, tropical: f64, jd_ut: f64) -> f64 {
+        (tropical - self.ayanamsa(jd_ut)).rem_euclid(360.0)
+    }
+}
+
+impl fmt::Display for Zodiac {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Zodiac::Tropical => "tropical",
+            Zodiac::Sidereal(Ayanamsa::Lahiri) => "lahiri",
+            Zodiac::Sidereal(Ayanamsa::FaganBradley) => "fagan_bradley",
+            Zodiac::Sidereal(Ayanamsa::Krishnamurti) => "krishnamurti",
+            Zodiac::Sidereal(Ayanamsa::Raman) => "raman",
+            Zodiac::Sidereal(Ayanamsa::UserDefined { .. }) => "user_defined",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Zodiac {
+    type Err = String;
+
+    /// Parse "tropical" or the name of a standard ayanamsa; a user-defined
+    /// ayanamsa needs its epoch and rate and cannot be parsed
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tropical" => Ok(Zodiac::Tropical),
+            "lahiri" => Ok(Zodiac::Sidereal(Ayanamsa::Lahiri)),
+            "fagan_bradley" => Ok(Zodiac::Sidereal(Ayanamsa::FaganBradley)),
+            "krishnamurti" => Ok(Zodiac::Sidereal(Ayanamsa::Krishnamurti)),
+            "raman" => Ok(Zodiac::Sidereal(Ayanamsa::Raman)),
+            other => Err(format!("Unknown zodiac: {}", other)),
+        }
+    }
+}
+
 /// Represents the four classical elements
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Element {
@@ -199,6 +306,42 @@ mod tests {
         assert!(approx_eq!(f64, degree, 0.0, epsilon = 0.0001));
     }
 
+    #[test]
+    fn test_sidereal_signs() {
+        // 5° Aries tropical falls in late Pisces in the Lahiri zodiac of 2000
+        let (sign, degree) = ZodiacSign::from_degree_in(5.0, 23.85);
+        assert_eq!(sign, ZodiacSign::Pisces);
+        assert!(approx_eq!(f64, degree, 11.15, epsilon = 0.0001));
+        assert_eq!(ZodiacSign::from_degree_in(45.0, 0.0), ZodiacSign::from_degree(45.0));
+    }
+
+    #[test]
+    fn test_ayanamsas() {
+        // True ayanamsas of the Swiss Ephemeris on 2000-01-01 12:00 UT and
+        // 2030-01-01 0:00 UT, to a second of arc
+        let cases = [
+            (Ayanamsa::Lahiri, 23.0 + 51.0 / 60.0 + 11.60 / 3600.0, 24.0 + 16.0 / 60.0 + 51.72 / 3600.0),
+            (Ayanamsa::FaganBradley, 24.0 + 44.0 / 60.0 + 11.15 / 3600.0, 25.0 + 9.0 / 60.0 + 51.27 / 3600.0),
+            (Ayanamsa::Krishnamurti, 23.0 + 45.0 / 60.0 + 22.93 / 3600.0, 24.0 + 11.0 / 60.0 + 3.05 / 3600.0),
+            (Ayanamsa::Raman, 22.0 + 24.0 / 60.0 + 24.92 / 3600.0, 22.0 + 50.0 / 60.0 + 5.04 / 3600.0),
+        ];
+        for (ayanamsa, in_2000, in_2030) in cases {
+            assert!(approx_eq!(f64, ayanamsa.value(2_451_545.0), in_2000, epsilon = 1.0 / 3600.0), "{:?}", ayanamsa);
+            assert!(approx_eq!(f64, ayanamsa.value(2_462_502.5), in_2030, epsilon = 1.0 / 3600.0), "{:?}", ayanamsa);
+        }
+
+        // A user-defined ayanamsa grows linearly from its epoch
+        let user = Ayanamsa::UserDefined { epoch: 2_451_545.0, value: 24.0, rate: 50.0 };
+        assert!(approx_eq!(f64, user.value(2_451_545.0 + 365.25 * 36.0), 24.5, epsilon = 1e-9));
+
+        let zodiac: Zodiac = "fagan_bradley".parse().unwrap();
+        assert_eq!(zodiac, Zodiac::Sidereal(Ayanamsa::FaganBradley));
+        assert_eq!(zodiac.to_string(), "fagan_bradley");
+        assert!(approx_eq!(f64, Zodiac::Tropical.longitude(123.4, 2_451_545.0), 123.4));
+        assert!(approx_eq!(f64, zodiac.longitude(10.0, 2_451_545.0), 360.0 + 10.0 - zodiac.ayanamsa(2_451_545.0), epsilon = 1e-9));
+        assert!("user_defined".parse::<Zodiac>().is_err());
+    }
+
     #[test]
     fn test_zodiac_sign_properties() {
         assert_eq!(ZodiacSign::Aries.element(), Element::Fire);
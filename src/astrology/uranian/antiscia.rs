@@ -0,0 +1,137 @@
+//! Antiscia and contra-antiscia
+//!
+//! The antiscion of a point is its reflection across the Cancer/Capricorn
+//! axis (180° - λ), the contra-antiscion its reflection across the Aries/Libra
+//! axis (360° - λ). A body standing on the antiscion of another is read much
+//! like a conjunction with it.
+
+use serde::{Serialize, Deserialize};
+use strum::IntoEnumIterator;
+
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::planet::{ChartPositions, Planet};
+use crate::utils::angle::Angle;
+
+/// Get the antiscion of a point
+pub fn antiscion(point: &ChartPoint, angle: Angle) -> (ChartPoint, Angle) {
+    (ChartPoint::Antiscion(Box::new(point.clone())), angle.antiscion())
+}
+
+/// Get the contra-antiscion of a point
+pub fn contra_antiscion(point: &ChartPoint, angle: Angle) -> (ChartPoint, Angle) {
+    (ChartPoint::ContraAntiscion(Box::new(point.clone())), angle.contra_antiscion())
+}
+
+/// Get the antiscia and/or contra-antiscia of some points, skipping points
+/// that already are reflections
+pub fn reflections(points: &[(ChartPoint, Angle)], antiscia: bool, contra_antiscia: bool) -> Vec<(ChartPoint, Angle)> {
+    let sources = points.iter().filter(|(point, _)| !point.is_reflection());
+    let mut reflected = Vec::new();
+
+    for (point, angle) in sources {
+        if antiscia {
+            reflected.push(antiscion(point, *angle));
+        }
+        if contra_antiscia {
+            reflected.push(contra_antiscion(point, *angle));
+        }
+    }
+
+    reflected
+}
+
+/// Get the antiscion and contra-antiscion of every body in a chart
+pub fn chart_antiscia(chart: &ChartPositions) -> Vec<(ChartPoint, Angle)> {
+    let points: Vec<(ChartPoint, Angle)> = Planet::iter()
+        .filter_map(|planet| chart.get_position(&planet))
+        .map(|position| (ChartPoint::Planet(position.planet), Angle::from_degrees(position.longitude)))
+        .collect();
+    reflections(&points, true, true)
+}
+
+/// A body standing on the antiscion or contra-antiscion of another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AntiscionContact {
+    /// The antiscion or contra-antiscion
+    pub reflection: ChartPoint,
+    /// The body standing on it
+    pub body: ChartPoint,
+    /// Distance between them in degrees
+    pub orb: f64,
+}
+
+/// Find the bodies standing within `orb` degrees of the antiscion or
+/// contra-antiscion of another, closest first. Contacts are mutual (when the
+/// antiscion of A meets B, that of B meets A), so each is reported once.
+pub fn find_antiscion_contacts(points: &[(ChartPoint, Angle)], orb: f64) -> Vec<AntiscionContact> {
+    let sources: Vec<&(ChartPoint, Angle)> = points.iter().filter(|(point, _)| !point.is_reflection()).collect();
+    let mut contacts = Vec::new();
+
+    for (i, (point1, angle1)) in sources.iter().enumerate() {
+        for (point2, angle2) in &sources[i + 1..] {
+            for (reflection, reflected) in [antiscion(point1, *angle1), contra_antiscion(point1, *angle1)] {
+                let distance = reflected.distance_to(*angle2).degrees();
+                if distance <= orb {
+                    contacts.push(AntiscionContact {
+                        reflection,
+                        body: point2.clone(),
+                        orb: distance,
+                    });
+                }
+            }
+        }
+    }
+
+    contacts.sort_by(|a, b| a.orb.total_cmp(&b.orb));
+    contacts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use float_cmp::approx_eq;
+    use crate::astrology::models::planet::PlanetPosition;
+
+    fn point(planet: Planet, longitude: f64) -> (ChartPoint, Angle) {
+        (ChartPoint::Planet(planet), Angle::from_degrees(longitude))
+    }
+
+    #[test]
+    fn test_chart_antiscia() {
+        let mut chart = ChartPositions::new(Utc::now(), (0.0, 0.0));
+        chart.add_position(PlanetPosition::new(Planet::Sun, 70.0, 0.0, 1.0, 1.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        chart.add_position(PlanetPosition::new(Planet::Moon, 200.0, 0.0, 1.0, 13.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+
+        let antiscia = chart_antiscia(&chart);
+        assert_eq!(antiscia.len(), 4);
+
+        let sun = ChartPoint::Planet(Planet::Sun);
+        let (_, sun_antiscion) = antiscia.iter().find(|(point, _)| *point == ChartPoint::Antiscion(Box::new(sun.clone()))).unwrap();
+        assert!(approx_eq!(f64, sun_antiscion.degrees(), 110.0, epsilon = 1e-9));
+        assert!(antiscia.iter().all(|(point, _)| point.is_reflection()));
+    }
+
+    #[test]
+    fn test_antiscion_contacts() {
+        let points = [
+            point(Planet::Sun, 70.0),     // antiscion at 110
+            point(Planet::Mars, 110.5),   // on the Sun's antiscion
+            point(Planet::Saturn, 289.0), // on the Sun's contra-antiscion at 290
+            point(Planet::Venus, 15.0),
+        ];
+
+        let contacts = find_antiscion_contacts(&points, 1.0);
+        assert_eq!(contacts.len(), 2);
+        assert_eq!(contacts[0].reflection, ChartPoint::Antiscion(Box::new(Planet::Sun.into())));
+        assert_eq!(contacts[0].body, Planet::Mars);
+        assert!(approx_eq!(f64, contacts[0].orb, 0.5, epsilon = 1e-9));
+        assert_eq!(contacts[1].reflection, ChartPoint::ContraAntiscion(Box::new(Planet::Sun.into())));
+
+        // Reflections among the points are not reflected again
+        let mut with_reflections = points.to_vec();
+        with_reflections.extend(reflections(&points, true, false));
+        assert_eq!(reflections(&with_reflections, true, true).len(), 8);
+        assert_eq!(find_antiscion_contacts(&with_reflections, 1.0).len(), 2);
+    }
+}
@@ -1,10 +1,138 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
-use crate::astrology::models::planet::{Planet, PlanetPosition};
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::fixed_star::StarPosition;
+use crate::astrology::models::minor_body::MinorBodyPosition;
+use crate::astrology::models::planet::{ChartPositions, Planet, PlanetPosition};
+use crate::astrology::models::progression::ProgressedChart;
 use crate::astrology::models::zodiac::ZodiacSign;
+use crate::astrology::uranian::antiscia;
+use crate::astrology::uranian::midpoint::{self, Midpoint, MidpointTree};
+use crate::astrology::uranian::planetary_picture::{self, PlanetaryPicture};
+use crate::astrology::uranian::solar_arc::DirectedChart;
+use crate::astrology::uranian::sort_list::SortList;
+use crate::astrology::uranian::synastry::Synastry;
 use crate::utils::angle::Angle;
 
+/// Type of dial: the span of the zodiac (modulus) spread over the full circle
+///
+/// On the 90° dial a position is taken modulo 90° so that conjunctions,
+/// squares and oppositions fall together. This is independent of the harmonic
+/// chart setting of [`UranianDial`], which multiplies positions first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum DialType {
+    #[default]
+    Dial360,
+    Dial180,
+    Dial90,
+    Dial45,
+    Dial22_5,
+    Dial30,
+    /// 360°/n dial for any other harmonic n
+    Harmonic(u32),
+}
+
+impl DialType {
+    /// Get the dial whose modulus is 360°/harmonic, `None` for 0
+    pub fn from_harmonic(harmonic: u32) -> Option<Self> {
+        match harmonic {
+            0 => None,
+            1 => Some(DialType::Dial360),
+            2 => Some(DialType::Dial180),
+            4 => Some(DialType::Dial90),
+            8 => Some(DialType::Dial45),
+            12 => Some(DialType::Dial30),
+            16 => Some(DialType::Dial22_5),
+            n => Some(DialType::Harmonic(n)),
+        }
+    }
+    
+    /// Get the number of times the dial's span fits in the zodiac
+    pub fn harmonic(&self) -> u32 {
+        match self {
+            DialType::Dial360 => 1,
+            DialType::Dial180 => 2,
+            DialType::Dial90 => 4,
+            DialType::Dial45 => 8,
+            DialType::Dial22_5 => 16,
+            DialType::Dial30 => 12,
+            DialType::Harmonic(n) => (*n).max(1),
+        }
+    }
+    
+    /// Get the span of the zodiac shown on the dial in degrees
+    pub fn modulus(&self) -> f64 {
+        360.0 / self.harmonic() as f64
+    }
+    
+    /// Get the position of a longitude on the dial in dial degrees (0 to modulus)
+    pub fn dial_position(&self, longitude: f64) -> f64 {
+        longitude.rem_euclid(self.modulus())
+    }
+    
+    /// Get the angle on the drawn circle (0-360) of a longitude
+    pub fn display_angle(&self, longitude: f64) -> f64 {
+        self.dial_position(longitude) * 360.0 / self.modulus()
+    }
+    
+    /// Get the spacing of the degree ticks in dial degrees
+    pub fn tick_step(&self) -> f64 {
+        match self.modulus() {
+            m if m >= 90.0 => 1.0,
+            m if m >= 30.0 => 0.5,
+            _ => 0.25,
+        }
+    }
+    
+    /// Get the number of degree ticks to draw around the dial
+    pub fn tick_count(&self) -> u32 {
+        (self.modulus() / self.tick_step()).round() as u32
+    }
+    
+    /// Get the spacing of the labelled ticks in dial degrees
+    pub fn label_step(&self) -> f64 {
+        if self.modulus() >= 90.0 { 5.0 } else { 1.0 }
+    }
+    
+    /// Get the number of sign boundaries (every 30 dial degrees) on the dial
+    pub fn sign_markers(&self) -> u32 {
+        (self.modulus() / 30.0).ceil() as u32
+    }
+}
+
+impl fmt::Display for DialType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DialType::Harmonic(n) => write!(f, "360/{}", n),
+            _ => write!(f, "{}", self.modulus()),
+        }
+    }
+}
+
+impl FromStr for DialType {
+    type Err = String;
+    
+    /// Parse a modulus in degrees ("90", "22.5") or a harmonic as "360/n"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().trim_end_matches('°');
+        let harmonic = match s.strip_prefix("360/") {
+            Some(n) => n.parse::<u32>().map_err(|e| e.to_string())?,
+            None => {
+                let modulus = s.parse::<f64>().map_err(|e| e.to_string())?;
+                let harmonic = 360.0 / modulus;
+                if !harmonic.is_finite() || harmonic < 1.0 || harmonic.fract() != 0.0 {
+                    return Err(format!("{}° does not divide the zodiac", s));
+                }
+                harmonic as u32
+            }
+        };
+        DialType::from_harmonic(harmonic).ok_or_else(|| "Harmonic must be greater than 0".to_string())
+    }
+}
+
 /// Represents a Uranian astrology dial with all required data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UranianDial {
@@ -12,8 +140,17 @@ pub struct UranianDial {
     pub degrees: Vec<f64>,
     /// Planetary positions in degrees
     pub planets: HashMap<Planet, Angle>,
-    /// Current harmonic setting (1 = normal, 2 = half-dial, etc.)
+    /// Fixed star positions in degrees, keyed by star name
+    #[serde(default)]
+    pub stars: HashMap<String, Angle>,
+    /// Minor planet names and positions in degrees, keyed by MPC number
+    #[serde(default)]
+    pub minor_bodies: HashMap<u32, (String, Angle)>,
+    /// Current harmonic chart setting (1 = normal), applied before the dial type
     pub harmonic: u32,
+    /// Span of the zodiac shown around the dial
+    #[serde(default)]
+    pub dial_type: DialType,
     /// Current rotation of the dial in degrees
     pub rotation: f64,
     /// Zoom level (0.5 - 2.0)
@@ -26,20 +163,48 @@ pub struct UranianDial {
     pub show_zodiac: bool,
     /// Whether to show degree markings
     pub show_degrees: bool,
+    /// Whether to add the antiscia of the points to the dial
+    #[serde(default)]
+    pub show_antiscia: bool,
+    /// Whether to add the contra-antiscia of the points to the dial
+    #[serde(default)]
+    pub show_contra_antiscia: bool,
+    /// Directed points shown in a ring around the natal ones
+    #[serde(default)]
+    pub directed: Option<DirectedChart>,
+    /// Progressed chart shown in a ring between the natal and directed points
+    #[serde(default)]
+    pub progressed: Option<ProgressedChart>,
+    /// Second chart compared with this one, shown in the outermost ring
+    #[serde(default)]
+    pub partner: Option<ChartPositions>,
+    /// The same chart in another coordinate frame (heliocentric beside
+    /// geocentric, say), shown in a ring inside the points
+    #[serde(default)]
+    pub frame_chart: Option<ChartPositions>,
 }
 
 impl Default for UranianDial {
     fn default() -> Self {
         Self {
-            degrees: (0..360).collect(),
+            degrees: (0..360).map(f64::from).collect(),
             planets: HashMap::new(),
+            stars: HashMap::new(),
+            minor_bodies: HashMap::new(),
             harmonic: 1,
+            dial_type: DialType::default(),
             rotation: 0.0,
             zoom: 1.0,
             show_planets: true,
             show_midpoints: true,
             show_zodiac: true,
             show_degrees: true,
+            show_antiscia: false,
+            show_contra_antiscia: false,
+            directed: None,
+            progressed: None,
+            partner: None,
+            frame_chart: None,
         }
     }
 }
@@ -59,9 +224,15 @@ impl UranianDial {
         Ok(())
     }
     
-    /// Get the effective angle for a given position, applying the current harmonic
+    /// Set the type of dial
+    pub fn set_dial_type(&mut self, dial_type: DialType) {
+        self.dial_type = dial_type;
+    }
+    
+    /// Get the effective angle on the drawn circle for a given position,
+    /// applying the harmonic chart and then the dial type
     pub fn get_effective_angle(&self, angle: f64) -> f64 {
-        (angle * self.harmonic as f64) % 360.0
+        self.dial_type.display_angle((angle * self.harmonic as f64) % 360.0)
     }
     
     /// Rotate the dial by a certain number of degrees
@@ -91,6 +262,123 @@ impl UranianDial {
         self.planets.insert(planet, Angle::from_degrees(position));
     }
     
+    /// Place every body of a chart on the dial, including the transneptunian points
+    pub fn update_from_chart(&mut self, chart: &ChartPositions) {
+        for (planet, position) in &chart.positions {
+            self.update_planet_position(*planet, position.longitude);
+        }
+    }
+    
+    /// Add or update a fixed star's position
+    pub fn update_star_position(&mut self, star: &StarPosition) {
+        self.stars.insert(star.name.clone(), Angle::from_degrees(star.longitude));
+    }
+    
+    /// Remove a fixed star from the dial
+    pub fn remove_star(&mut self, name: &str) -> Option<Angle> {
+        self.stars.remove(name)
+    }
+    
+    /// Get all fixed star positions with their effective angles
+    pub fn get_star_positions(&self) -> Vec<(String, f64)> {
+        self.stars.iter()
+            .map(|(name, angle)| (name.clone(), self.get_effective_angle(angle.degrees())))
+            .collect()
+    }
+    
+    /// Add or update a minor planet's position
+    pub fn update_minor_body_position(&mut self, body: &MinorBodyPosition) {
+        self.minor_bodies.insert(body.number, (body.name.clone(), Angle::from_degrees(body.longitude)));
+    }
+    
+    /// Remove a minor planet from the dial
+    pub fn remove_minor_body(&mut self, number: u32) -> Option<Angle> {
+        self.minor_bodies.remove(&number).map(|(_, angle)| angle)
+    }
+    
+    /// Get every point on the dial, planets, fixed stars and minor planets
+    /// alike, with its unmodified longitude, followed by their antiscia and
+    /// contra-antiscia when those are shown
+    pub fn points(&self) -> Vec<(ChartPoint, Angle)> {
+        let mut points: Vec<(ChartPoint, Angle)> = self.planets.iter()
+            .map(|(planet, angle)| (ChartPoint::Planet(*planet), *angle))
+            .chain(self.stars.iter().map(|(name, angle)| (ChartPoint::Star(name.clone()), *angle)))
+            .chain(self.minor_bodies.iter().map(|(number, (name, angle))| {
+                (ChartPoint::MinorBody { number: *number, name: name.clone() }, *angle)
+            }))
+            .collect();
+        
+        points.extend(antiscia::reflections(&points, self.show_antiscia, self.show_contra_antiscia));
+        points
+    }
+    
+    /// Show a set of directed points in the outer ring, or remove it with `None`
+    pub fn set_directed(&mut self, directed: Option<DirectedChart>) {
+        self.directed = directed;
+    }
+    
+    /// Get the directed points of the outer ring, with their effective angles
+    pub fn get_directed_positions(&self) -> Vec<(ChartPoint, f64)> {
+        self.directed.iter()
+            .flat_map(|directed| &directed.points)
+            .map(|(point, angle)| (point.clone(), self.get_effective_angle(angle.degrees())))
+            .collect()
+    }
+    
+    /// Show a progressed chart in its ring, or remove it with `None`
+    pub fn set_progressed(&mut self, progressed: Option<ProgressedChart>) {
+        self.progressed = progressed;
+    }
+    
+    /// Get the progressed points of their ring, with their effective angles
+    pub fn get_progressed_positions(&self) -> Vec<(ChartPoint, f64)> {
+        self.progressed.iter()
+            .flat_map(ProgressedChart::points)
+            .map(|(point, angle)| (point, self.get_effective_angle(angle.degrees())))
+            .collect()
+    }
+    
+    /// Show a second chart in the outermost ring for synastry, or remove it with `None`
+    pub fn set_partner(&mut self, partner: Option<ChartPositions>) {
+        self.partner = partner;
+    }
+    
+    /// Get the points of the second chart, with their effective angles
+    pub fn get_partner_positions(&self) -> Vec<(ChartPoint, f64)> {
+        self.partner.iter()
+            .flat_map(ChartPositions::points)
+            .map(|(point, angle)| (point, self.get_effective_angle(angle.degrees())))
+            .collect()
+    }
+    
+    /// Show the chart in a second coordinate frame in its ring, or remove it with `None`
+    pub fn set_frame_chart(&mut self, frame_chart: Option<ChartPositions>) {
+        self.frame_chart = frame_chart;
+    }
+    
+    /// Get the points of the chart in the second coordinate frame, with their
+    /// effective angles
+    pub fn get_frame_chart_positions(&self) -> Vec<(ChartPoint, f64)> {
+        self.frame_chart.iter()
+            .flat_map(ChartPositions::points)
+            .map(|(point, angle)| (point, self.get_effective_angle(angle.degrees())))
+            .collect()
+    }
+    
+    /// Compare the points on the dial with those of the second chart, if any
+    pub fn synastry(&self) -> Option<Synastry> {
+        let partner = self.partner.as_ref()?;
+        Some(Synastry::new(&self.points(), &partner.points()))
+    }
+    
+    /// Get the transneptunian (Hamburg) points currently on the dial
+    pub fn uranian_points(&self) -> Vec<(Planet, f64)> {
+        self.get_planet_positions()
+            .into_iter()
+            .filter(|(planet, _)| planet.is_uranian_point())
+            .collect()
+    }
+    
     /// Remove a planet from the dial
     pub fn remove_planet(&mut self, planet: &Planet) -> Option<Angle> {
         self.planets.remove(planet)
@@ -110,28 +398,39 @@ impl UranianDial {
             .collect()
     }
     
-    /// Calculate midpoints between all pairs of planets
+    /// Calculate midpoints between all pairs of points, fixed stars and minor
+    /// planets included
     pub fn calculate_midpoints(&self) -> Vec<Midpoint> {
-        let mut midpoints = Vec::new();
-        let planets: Vec<_> = self.planets.keys().collect();
-        
-        for i in 0..planets.len() {
-            for j in (i + 1)..planets.len() {
-                let p1 = planets[i];
-                let p2 = planets[j];
-                
-                if let (Some(a1), Some(a2)) = (self.planets.get(p1), self.planets.get(p2)) {
-                    let midpoint = a1.midpoint(*a2);
-                    midpoints.push(Midpoint {
-                        point1: *p1,
-                        point2: *p2,
-                        angle: midpoint,
-                    });
-                }
-            }
-        }
-        
-        midpoints
+        midpoint::calculate_midpoints(&self.points())
+    }
+    
+    /// Calculate the midpoints involving the Aries point, MC, Ascendant or node
+    pub fn calculate_personal_midpoints(&self) -> Vec<Midpoint> {
+        midpoint::calculate_personal_midpoints(&self.points())
+    }
+    
+    /// Find the planetary pictures A+B-C=D between points on the dial within
+    /// `orb` degrees, measured on the dial's modulus
+    pub fn find_planetary_pictures(&self, orb: f64) -> Vec<PlanetaryPicture> {
+        planetary_picture::find_planetary_pictures(&self.points(), self.modulus(), orb)
+    }
+    
+    /// Get the midpoint tree of a point on the dial: every midpoint of the
+    /// other points falling on it, directly or by hard aspect, within `orb`
+    /// degrees on the dial's modulus
+    pub fn midpoint_tree(&self, focus: &ChartPoint, orb: f64) -> Option<MidpointTree> {
+        MidpointTree::new(focus, &self.points(), self.modulus(), orb)
+    }
+    
+    /// Get the points and midpoints on the dial sorted by their position
+    /// modulo the dial's span, e.g. the 90° sort on the 90° dial
+    pub fn sort_list(&self) -> SortList {
+        SortList::new(&self.points(), self.modulus())
+    }
+    
+    /// Get the span of the zodiac shown on the dial in degrees
+    pub fn modulus(&self) -> f64 {
+        self.dial_type.modulus()
     }
     
     /// Get the current rotation of the dial in radians
@@ -145,24 +444,13 @@ impl UranianDial {
     }
 }
 
-/// Represents a midpoint between two points on the dial
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Midpoint {
-    pub point1: Planet,
-    pub point2: Planet,
-    pub angle: Angle,
-}
-
-impl Midpoint {
-    /// Get the effective angle of the midpoint, applying the current harmonic
-    pub fn get_effective_angle(&self, harmonic: u32) -> f64 {
-        (self.angle.degrees() * harmonic as f64) % 360.0
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::astrology::models::aspect::{AspectCalculator, AspectKind};
+    use crate::astrology::models::ephemeris::CoordinateFrame;
+    use crate::astrology::models::progression::{MCProgression, ProgressionKind};
+    use crate::astrology::uranian::solar_arc::ArcMethod;
     use float_cmp::approx_eq;
     use std::f64::consts::PI;
 
@@ -180,6 +468,7 @@ mod tests {
         let mut dial = UranianDial::new();
         assert!(dial.set_harmonic(4).is_ok());
         assert_eq!(dial.harmonic, 4);
+        assert_eq!(dial.modulus(), 360.0);
         
         assert!(dial.set_harmonic(0).is_err());
     }
@@ -194,6 +483,34 @@ mod tests {
         assert_eq!(dial.get_effective_angle(200.0), 40.0);
     }
 
+    #[test]
+    fn test_dial_types() {
+        assert_eq!("90".parse::<DialType>().unwrap(), DialType::Dial90);
+        assert_eq!("22.5°".parse::<DialType>().unwrap(), DialType::Dial22_5);
+        assert_eq!("360/7".parse::<DialType>().unwrap(), DialType::Harmonic(7));
+        assert_eq!("360/4".parse::<DialType>().unwrap(), DialType::Dial90);
+        assert!("100".parse::<DialType>().is_err());
+        assert!("360/0".parse::<DialType>().is_err());
+        assert_eq!(DialType::Dial22_5.to_string(), "22.5");
+        assert_eq!(DialType::Harmonic(7).to_string(), "360/7");
+        
+        assert_eq!(DialType::Dial360.tick_count(), 360);
+        assert_eq!(DialType::Dial90.tick_count(), 90);
+        assert_eq!(DialType::Dial22_5.tick_count(), 90);
+        assert_eq!(DialType::Dial90.sign_markers(), 3);
+        assert_eq!(DialType::Dial45.sign_markers(), 2);
+        
+        // 100° is 10° into the second quarter and spreads out to 40°
+        let mut dial = UranianDial::new();
+        dial.set_dial_type(DialType::Dial90);
+        assert!(approx_eq!(f64, dial.dial_type.dial_position(100.0), 10.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, dial.get_effective_angle(100.0), 40.0, epsilon = 1e-9));
+        
+        // The harmonic chart applies first: 100° in the 2nd harmonic is 200°, 20° on the dial
+        dial.set_harmonic(2).unwrap();
+        assert!(approx_eq!(f64, dial.get_effective_angle(100.0), 80.0, epsilon = 1e-9));
+    }
+
     #[test]
     fn test_rotation() {
         let mut dial = UranianDial::new();
@@ -237,6 +554,24 @@ mod tests {
         assert!(dial.get_planet_position(&Planet::Sun).is_none());
     }
 
+    #[test]
+    fn test_update_from_chart() {
+        let mut dial = UranianDial::new();
+        let mut chart = ChartPositions::new(Utc::now(), (0.0, 0.0));
+        chart.add_position(PlanetPosition::new(Planet::Sun, 280.0, 0.0, 1.0, 1.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        chart.add_position(PlanetPosition::new(Planet::Cupido, 243.9, 0.0, 41.0, 0.01, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        chart.add_position(PlanetPosition::new(Planet::Poseidon, 198.6, 0.0, 83.5, -0.01, 0.0, true, 0.0, 0.0, 0.0, 0.0, 1));
+        
+        dial.update_from_chart(&chart);
+        
+        assert_eq!(dial.planets.len(), 3);
+        assert!(approx_eq!(f64, dial.get_planet_position(&Planet::Cupido).unwrap(), 243.9, epsilon = 0.0001));
+        
+        let mut uranian: Vec<Planet> = dial.uranian_points().into_iter().map(|(planet, _)| planet).collect();
+        uranian.sort_by_key(|planet| planet.to_string());
+        assert_eq!(uranian, vec![Planet::Cupido, Planet::Poseidon]);
+    }
+
     #[test]
     fn test_midpoint_calculation() {
         let mut dial = UranianDial::new();
@@ -252,6 +587,194 @@ mod tests {
             .find(|m| (m.point1 == Planet::Sun && m.point2 == Planet::Moon) || 
                      (m.point2 == Planet::Sun && m.point1 == Planet::Moon))
             .unwrap();
-        assert!(approx_eq!(f64, sun_moon.angle.degrees(), 30.0, epsilon = 0.0001));
+        assert!(approx_eq!(f64, sun_moon.near.degrees(), 30.0, epsilon = 0.0001));
+    }
+
+    #[test]
+    fn test_antiscia_on_dial() {
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 70.0);
+        dial.update_planet_position(Planet::Mars, 20.0);
+        assert_eq!(dial.points().len(), 2);
+        
+        dial.show_antiscia = true;
+        dial.show_contra_antiscia = true;
+        let points = dial.points();
+        assert_eq!(points.len(), 6);
+        let sun_antiscion = ChartPoint::Antiscion(Box::new(Planet::Sun.into()));
+        assert!(points.iter().any(|(point, angle)| *point == sun_antiscion && approx_eq!(f64, angle.degrees(), 110.0, epsilon = 1e-9)));
+        
+        // A point and its own reflections make no midpoints: 15 pairs less 2 × 3
+        assert_eq!(dial.calculate_midpoints().len(), 9);
+    }
+
+    #[test]
+    fn test_directed_ring() {
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 100.0);
+        assert!(dial.get_directed_positions().is_empty());
+        
+        dial.set_dial_type(DialType::Dial90);
+        dial.set_directed(Some(DirectedChart {
+            method: ArcMethod::Naibod,
+            date: Utc::now(),
+            arc: 30.0,
+            points: vec![(Planet::Sun.into(), Angle::from_degrees(130.0))],
+        }));
+        
+        // The directed ring does not add to the natal points
+        assert_eq!(dial.points().len(), 1);
+        let directed = dial.get_directed_positions();
+        assert_eq!(directed.len(), 1);
+        assert!(approx_eq!(f64, directed[0].1, 160.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn test_progressed_ring() {
+        let mut positions = ChartPositions::new(Utc::now(), (0.0, 0.0));
+        positions.add_position(PlanetPosition::new(Planet::Moon, 200.0, 0.0, 0.0, 13.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        positions.add_position(PlanetPosition::new(Planet::Sun, 40.0, 0.0, 1.0, 1.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        
+        let mut dial = UranianDial::new();
+        dial.set_harmonic(2).unwrap();
+        dial.set_progressed(Some(ProgressedChart {
+            kind: ProgressionKind::Secondary,
+            mc_progression: MCProgression::SolarArc,
+            date: Utc::now(),
+            moment: Utc::now(),
+            positions,
+        }));
+        
+        // In the order of the planets, with the harmonic applied
+        let progressed = dial.get_progressed_positions();
+        assert_eq!(progressed.len(), 2);
+        assert_eq!(progressed[0].0, Planet::Sun);
+        assert!(approx_eq!(f64, progressed[1].1, 40.0, epsilon = 1e-9));
+        
+        dial.set_progressed(None);
+        assert!(dial.get_progressed_positions().is_empty());
+    }
+
+    #[test]
+    fn test_partner_ring() {
+        let mut partner = ChartPositions::new(Utc::now(), (0.0, 0.0));
+        partner.add_position(PlanetPosition::new(Planet::Mars, 100.5, 0.0, 1.0, 0.5, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 10.0);
+        assert!(dial.synastry().is_none());
+        
+        dial.set_dial_type(DialType::Dial90);
+        dial.set_partner(Some(partner));
+        assert_eq!(dial.points().len(), 1);
+        let partner = dial.get_partner_positions();
+        assert!(approx_eq!(f64, partner[0].1, 42.0, epsilon = 1e-9));
+        
+        // The partner's Mars squares the Sun
+        let synastry = dial.synastry().unwrap();
+        assert_eq!(synastry.aspects(&AspectCalculator::major())[0].kind, AspectKind::Square);
+    }
+
+    #[test]
+    fn test_frame_ring() {
+        let mut heliocentric = ChartPositions::new(Utc::now(), (0.0, 0.0));
+        heliocentric.frame = CoordinateFrame::Heliocentric;
+        heliocentric.add_position(PlanetPosition::new(Planet::Mars, 100.5, 0.0, 1.4, 0.5, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        heliocentric.add_position(PlanetPosition::new(Planet::Venus, 20.0, 0.0, 0.7, 1.6, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 10.0);
+        dial.set_dial_type(DialType::Dial90);
+        dial.set_frame_chart(Some(heliocentric));
+        
+        // A ring of its own, in the order of the planets, leaving the points alone
+        assert_eq!(dial.points().len(), 1);
+        let ring = dial.get_frame_chart_positions();
+        assert_eq!(ring.len(), 2);
+        assert_eq!(ring[0].0, ChartPoint::Planet(Planet::Venus));
+        assert!(approx_eq!(f64, ring[1].1, 42.0, epsilon = 1e-9));
+        
+        dial.set_frame_chart(None);
+        assert!(dial.get_frame_chart_positions().is_empty());
+    }
+
+    #[test]
+    fn test_midpoint_tree_on_dial() {
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 100.0);
+        dial.update_planet_position(Planet::Moon, 80.0);
+        dial.update_planet_position(Planet::Jupiter, 210.0);
+        
+        // Moon/Jupiter at 145 is semi-square the Sun, which only counts on the 45° dial
+        assert!(dial.midpoint_tree(&Planet::Sun.into(), 1.0).unwrap().contacts.is_empty());
+        dial.set_dial_type(DialType::Dial45);
+        assert_eq!(dial.midpoint_tree(&Planet::Sun.into(), 1.0).unwrap().contacts.len(), 1);
+        assert!(dial.midpoint_tree(&Planet::Mars.into(), 1.0).is_none());
+    }
+
+    #[test]
+    fn test_planetary_pictures_on_dial() {
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 10.0);
+        dial.update_planet_position(Planet::Moon, 50.0);
+        dial.update_planet_position(Planet::Jupiter, 130.0);
+        dial.update_planet_position(Planet::Saturn, 180.5);
+        assert!(dial.find_planetary_pictures(1.0).is_empty());
+        
+        // Sun+Jupiter-Moon falls square Saturn, which shows on the 90° dial
+        dial.set_dial_type(DialType::Dial90);
+        let pictures = dial.find_planetary_pictures(1.0);
+        assert_eq!(pictures.len(), 1);
+        assert!(pictures[0].involves(&ChartPoint::Planet(Planet::Saturn)));
+    }
+
+    #[test]
+    fn test_fixed_stars_on_dial() {
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 140.0);
+        dial.update_star_position(&StarPosition {
+            name: "Regulus".to_string(),
+            longitude: 150.0,
+            latitude: 0.46,
+            right_ascension: 152.4,
+            declination: 11.9,
+            magnitude: 1.4,
+        });
+        
+        dial.set_harmonic(4).unwrap();
+        assert_eq!(dial.get_star_positions(), vec![("Regulus".to_string(), 240.0)]);
+        
+        let midpoints = dial.calculate_midpoints();
+        assert_eq!(midpoints.len(), 1);
+        assert!(midpoints[0].point1 == Planet::Sun || midpoints[0].point2 == Planet::Sun);
+        assert!(approx_eq!(f64, midpoints[0].near.degrees(), 145.0, epsilon = 0.0001));
+        
+        assert!(dial.remove_star("Regulus").is_some());
+        assert!(dial.calculate_midpoints().is_empty());
+    }
+    
+    #[test]
+    fn test_minor_bodies_on_dial() {
+        let mut dial = UranianDial::new();
+        dial.update_planet_position(Planet::Sun, 100.0);
+        dial.update_minor_body_position(&MinorBodyPosition {
+            number: 433,
+            name: "Eros".to_string(),
+            longitude: 160.0,
+            latitude: 10.2,
+            distance_au: 0.8,
+            speed: 0.4,
+            is_retrograde: false,
+        });
+        
+        let eros = ChartPoint::MinorBody { number: 433, name: "Eros".to_string() };
+        assert!(dial.points().iter().any(|(point, angle)| *point == eros && angle.degrees() == 160.0));
+        
+        let midpoints = dial.calculate_midpoints();
+        assert_eq!(midpoints.len(), 1);
+        assert!(approx_eq!(f64, midpoints[0].near.degrees(), 130.0, epsilon = 0.0001));
+        
+        assert!(dial.remove_minor_body(433).is_some());
+        assert!(dial.calculate_midpoints().is_empty());
     }
 }
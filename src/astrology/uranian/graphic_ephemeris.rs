@@ -0,0 +1,267 @@
+//! Graphic ephemeris
+//!
+//! The positions of the transiting bodies, reduced modulo 45° or 90°, plotted
+//! against time, with the natal positions as horizontal lines: wherever a
+//! curve crosses a line the body reaches the natal point or one of its hard
+//! aspects. A curve leaving the top of the graph re-enters at the bottom, so
+//! each body is drawn as several segments.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::{Serialize, Deserialize};
+
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::ephemeris::{at, Ephemeris, EphemerisError};
+use crate::astrology::models::planet::Planet;
+use crate::utils::angle::Angle;
+
+/// Longest sampling step in days
+const MAX_STEP: f64 = 1.0;
+
+/// Most a curve may move between two samples, as a fraction of the modulus
+const MAX_MOVE: f64 = 0.02;
+
+/// A point of a curve
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GraphPoint {
+    /// Time in days from the start of the graph
+    pub days: f64,
+    /// Position on the dial in degrees (0 to modulus)
+    pub position: f64,
+}
+
+/// The curve of a transiting body, split where it wraps around the dial
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphCurve {
+    pub body: Planet,
+    pub segments: Vec<Vec<GraphPoint>>,
+}
+
+/// A natal position drawn across the graph
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceLine {
+    pub point: ChartPoint,
+    /// Position on the dial in degrees (0 to modulus)
+    pub position: f64,
+}
+
+/// Transiting positions modulo a dial's span over a period of time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphicEphemeris {
+    /// Span of the dial in degrees (45 or 90 usually)
+    pub modulus: f64,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub curves: Vec<GraphCurve>,
+    pub natal: Vec<ReferenceLine>,
+}
+
+impl GraphicEphemeris {
+    /// Sample the bodies from `from` to `to` with the backend of an ephemeris,
+    /// often enough that fast bodies still make smooth curves
+    pub fn new(
+        ephemeris: &Ephemeris,
+        bodies: &[Planet],
+        modulus: f64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Self, EphemerisError> {
+        let span = (to - from).num_milliseconds() as f64 / 86_400_000.0;
+        let mut ephemeris = ephemeris.clone();
+        let mut curves = Vec::new();
+
+        for body in bodies {
+            let mut segments = vec![Vec::new()];
+            let mut days = 0.0;
+
+            loop {
+                ephemeris.update_time(at(from, days))?;
+                let position = ephemeris.get_planet_position(body)?;
+                add_point(&mut segments, GraphPoint {
+                    days,
+                    position: position.longitude.rem_euclid(modulus),
+                }, modulus);
+
+                if days >= span {
+                    break;
+                }
+                let step = (modulus * MAX_MOVE / position.speed.abs().max(1e-9)).min(MAX_STEP);
+                days = (days + step).min(span);
+            }
+
+            curves.push(GraphCurve { body: *body, segments });
+        }
+
+        Ok(Self {
+            modulus,
+            from,
+            to,
+            curves,
+            natal: Vec::new(),
+        })
+    }
+
+    /// Draw the natal points as reference lines
+    pub fn with_natal(mut self, points: &[(ChartPoint, Angle)]) -> Self {
+        self.natal = points.iter()
+            .map(|(point, angle)| ReferenceLine {
+                point: point.clone(),
+                position: angle.degrees().rem_euclid(self.modulus),
+            })
+            .collect();
+        self
+    }
+
+    /// Length of the period in days
+    pub fn span_days(&self) -> f64 {
+        (self.to - self.from).num_milliseconds() as f64 / 86_400_000.0
+    }
+
+    /// Render the graph as an SVG image: time from left to right, the dial
+    /// from 0 at the bottom to the modulus at the top
+    pub fn to_svg(&self, width: f64, height: f64) -> String {
+        const MARGIN: f64 = 40.0;
+        let plot_width = width - 2.0 * MARGIN;
+        let plot_height = height - 2.0 * MARGIN;
+        let span = self.span_days().max(f64::EPSILON);
+        let x = |days: f64| MARGIN + days / span * plot_width;
+        let y = |position: f64| MARGIN + (1.0 - position / self.modulus) * plot_height;
+
+        let mut svg = String::new();
+        let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="10">"#);
+        let _ = writeln!(svg, r##"<rect x="{MARGIN}" y="{MARGIN}" width="{plot_width:.1}" height="{plot_height:.1}" fill="white" stroke="#888"/>"##);
+
+        // Degree grid with labels on the left
+        let label_step = if self.modulus > 90.0 { 30.0 } else { 5.0 };
+        let mut degree = 0.0;
+        while degree <= self.modulus + 1e-9 {
+            let _ = writeln!(svg, r##"<line x1="{MARGIN}" y1="{0:.1}" x2="{1:.1}" y2="{0:.1}" stroke="#eee"/>"##, y(degree), width - MARGIN);
+            let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{}°</text>"#, MARGIN - 4.0, y(degree) + 3.0, degree);
+            degree += label_step;
+        }
+
+        // Month boundaries with labels below the graph
+        let mut month = Utc.with_ymd_and_hms(self.from.year(), self.from.month(), 1, 0, 0, 0).single();
+        while let Some(start) = month.filter(|start| *start <= self.to) {
+            if start >= self.from {
+                let days = (start - self.from).num_milliseconds() as f64 / 86_400_000.0;
+                let _ = writeln!(svg, r##"<line x1="{0:.1}" y1="{MARGIN}" x2="{0:.1}" y2="{1:.1}" stroke="#eee"/>"##, x(days), height - MARGIN);
+                let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#, x(days), height - MARGIN + 14.0, start.format("%Y-%m"));
+            }
+            month = next_month(start);
+        }
+
+        // Natal positions
+        for line in &self.natal {
+            let _ = writeln!(svg, r#"<line x1="{MARGIN}" y1="{0:.1}" x2="{1:.1}" y2="{0:.1}" stroke="{2}" stroke-dasharray="4 3"/>"#, y(line.position), width - MARGIN, line.point.color());
+            let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" fill="{}">{}</text>"#, width - MARGIN + 4.0, y(line.position) + 3.0, line.point.color(), line.point.symbol());
+        }
+
+        // Transiting bodies
+        for curve in &self.curves {
+            for segment in curve.segments.iter().filter(|segment| segment.len() > 1) {
+                let points: Vec<String> = segment.iter()
+                    .map(|point| format!("{:.1},{:.1}", x(point.days), y(point.position)))
+                    .collect();
+                let _ = writeln!(svg, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="1.5"/>"#, points.join(" "), curve.body.color());
+            }
+            if let Some(first) = curve.segments.first().and_then(|segment| segment.first()) {
+                let _ = writeln!(svg, r#"<text x="{:.1}" y="{:.1}" fill="{}">{}</text>"#, x(first.days) + 2.0, y(first.position) - 3.0, curve.body.color(), curve.body.symbol());
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Append a sample to a curve, closing the segment at the edge of the graph
+/// and starting a new one on the other side when the body wraps around
+fn add_point(segments: &mut Vec<Vec<GraphPoint>>, point: GraphPoint, modulus: f64) {
+    let current = segments.last_mut().expect("a curve has at least one segment");
+    let previous = match current.last() {
+        Some(previous) => *previous,
+        None => {
+            current.push(point);
+            return;
+        }
+    };
+
+    // Motion since the previous sample, the short way around the dial
+    let moved = (point.position - previous.position + modulus / 2.0).rem_euclid(modulus) - modulus / 2.0;
+    let unwrapped = previous.position + moved;
+    if (0.0..modulus).contains(&unwrapped) {
+        current.push(point);
+        return;
+    }
+
+    // Cross the edge where the straight line between the samples meets it
+    let (edge, other_edge) = if unwrapped >= modulus { (modulus, 0.0) } else { (0.0, modulus) };
+    let fraction = (edge - previous.position) / moved;
+    let days = previous.days + fraction * (point.days - previous.days);
+    current.push(GraphPoint { days, position: edge });
+    segments.push(vec![GraphPoint { days, position: other_edge }, point]);
+}
+
+/// The first moment of the month after the one starting at `start`
+fn next_month(start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (year, month) = if start.month() == 12 { (start.year() + 1, 1) } else { (start.year(), start.month() + 1) };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::approx_eq;
+    use crate::astrology::models::ephemeris::BackendKind;
+
+    fn date(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_wrap_around() {
+        let mut segments = vec![Vec::new()];
+        add_point(&mut segments, GraphPoint { days: 0.0, position: 43.0 }, 45.0);
+        add_point(&mut segments, GraphPoint { days: 1.0, position: 1.0 }, 45.0);
+
+        // Moving 3° from 43° the curve leaves the top two thirds of the way
+        assert_eq!(segments.len(), 2);
+        let exit = segments[0][1];
+        assert!(approx_eq!(f64, exit.position, 45.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, exit.days, 2.0 / 3.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, segments[1][0].position, 0.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, segments[1][1].position, 1.0, epsilon = 1e-9));
+
+        // Going back (retrograde) wraps the other way
+        add_point(&mut segments, GraphPoint { days: 2.0, position: 44.0 }, 45.0);
+        assert_eq!(segments.len(), 3);
+        assert!(approx_eq!(f64, segments[2][0].position, 45.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn test_graphic_ephemeris() {
+        let ephemeris = Ephemeris::with_backend(date(2000, 1, 1), 0.0, 0.0, BackendKind::Analytic).unwrap();
+        let graph = GraphicEphemeris::new(&ephemeris, &[Planet::Sun, Planet::Saturn], 45.0, date(2000, 1, 1), date(2000, 7, 1))
+            .unwrap()
+            .with_natal(&[(ChartPoint::AriesPoint, Angle::from_degrees(0.0)), (Planet::Moon.into(), Angle::from_degrees(100.0))]);
+
+        // The Sun goes round the 45° dial about four times in half a year
+        let sun = &graph.curves[0];
+        assert!(sun.segments.len() >= 4 && sun.segments.len() <= 5, "{}", sun.segments.len());
+        for segment in &sun.segments {
+            assert!(segment.iter().all(|point| (0.0..=45.0).contains(&point.position)));
+            assert!(segment.windows(2).all(|pair| pair[0].days <= pair[1].days));
+        }
+        assert!(approx_eq!(f64, sun.segments.last().unwrap().last().unwrap().days, graph.span_days(), epsilon = 1e-9));
+        assert!(approx_eq!(f64, graph.natal[1].position, 10.0, epsilon = 1e-9));
+
+        let svg = graph.to_svg(800.0, 400.0);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("stroke-dasharray").count(), 2);
+        assert!(svg.matches("<polyline").count() >= sun.segments.len());
+        assert!(svg.contains(">2000-03<"));
+    }
+}
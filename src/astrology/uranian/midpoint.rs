@@ -0,0 +1,286 @@
+//! Midpoints between chart points
+//!
+//! Every pair of points has two midpoints: the near one, halfway along the
+//! shorter arc between them, and the far one directly opposite. On a dial whose
+//! modulus divides 180° (the 90° and 45° dials, for instance) both fall on the
+//! same spot, while on the 360° dial they sit on opposite sides.
+
+use serde::{Serialize, Deserialize};
+use strum::IntoEnumIterator;
+
+use crate::astrology::models::aspect::AspectKind;
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::utils::angle::Angle;
+
+/// Represents the midpoint between two points on the dial
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Midpoint {
+    pub point1: ChartPoint,
+    pub point2: ChartPoint,
+    /// Midpoint on the shorter arc between the points
+    pub near: Angle,
+    /// Midpoint on the longer arc, opposite the near one
+    pub far: Angle,
+}
+
+impl Midpoint {
+    /// Calculate the midpoint of two points from their longitudes
+    pub fn new(point1: ChartPoint, angle1: Angle, point2: ChartPoint, angle2: Angle) -> Self {
+        let near = angle1.midpoint(angle2);
+
+        Self {
+            point1,
+            point2,
+            near,
+            far: near.opposite(),
+        }
+    }
+
+    /// Get the position of the near midpoint on a dial of the given modulus
+    /// in degrees (360 for the full circle, 90 for the 90° dial, ...)
+    pub fn dial_position(&self, modulus: f64) -> f64 {
+        self.near.degrees().rem_euclid(modulus)
+    }
+
+    /// Get the position of the far midpoint on a dial of the given modulus
+    pub fn far_dial_position(&self, modulus: f64) -> f64 {
+        self.far.degrees().rem_euclid(modulus)
+    }
+
+    /// Get the effective angle of the midpoint, applying the current harmonic
+    pub fn get_effective_angle(&self, harmonic: u32) -> f64 {
+        (self.near.degrees() * harmonic as f64) % 360.0
+    }
+
+    /// Check if a point is one of the two points of the midpoint
+    pub fn involves(&self, point: &ChartPoint) -> bool {
+        self.point1 == *point || self.point2 == *point
+    }
+
+    /// Check if either point is a personal point (Aries point, MC, Ascendant or node)
+    pub fn is_personal(&self) -> bool {
+        self.point1.is_personal_point() || self.point2.is_personal_point()
+    }
+}
+
+/// Calculate the midpoints between all pairs of points
+///
+/// A point and its own antiscion or contra-antiscion always have their
+/// midpoint on the solstice or equinox axis, so such pairs are skipped.
+pub fn calculate_midpoints(points: &[(ChartPoint, Angle)]) -> Vec<Midpoint> {
+    let mut midpoints = Vec::new();
+
+    for (i, (point1, angle1)) in points.iter().enumerate() {
+        for (point2, angle2) in &points[i + 1..] {
+            if point1.source() == point2.source() {
+                continue;
+            }
+            midpoints.push(Midpoint::new(point1.clone(), *angle1, point2.clone(), *angle2));
+        }
+    }
+
+    midpoints
+}
+
+/// Calculate the midpoints involving a personal point, adding the Aries point
+/// to the given points when it is missing
+pub fn calculate_personal_midpoints(points: &[(ChartPoint, Angle)]) -> Vec<Midpoint> {
+    let mut points = points.to_vec();
+    if !points.iter().any(|(point, _)| *point == ChartPoint::AriesPoint) {
+        points.insert(0, (ChartPoint::AriesPoint, Angle::from_degrees(0.0)));
+    }
+
+    calculate_midpoints(&points)
+        .into_iter()
+        .filter(Midpoint::is_personal)
+        .collect()
+}
+
+/// A midpoint falling on the focal point of a midpoint tree
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MidpointContact {
+    pub midpoint: Midpoint,
+    /// Angle between the focal point and the closer of the two midpoints:
+    /// 0 for a direct contact, otherwise the hard aspect (a multiple of the
+    /// dial's modulus) through which the contact is made
+    pub angle: f64,
+    /// Distance from the exact contact in degrees
+    pub orb: f64,
+}
+
+impl MidpointContact {
+    /// Check if the midpoint (near or far) stands on the focal point itself
+    pub fn is_direct(&self) -> bool {
+        self.angle == 0.0
+    }
+
+    /// Get the aspect of the contact, if it is one of the known aspects
+    pub fn aspect(&self) -> Option<AspectKind> {
+        AspectKind::iter().find(|kind| (kind.angle() - self.angle).abs() < 1e-9)
+    }
+}
+
+/// Every midpoint falling on a focal point, closest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MidpointTree {
+    pub focus: ChartPoint,
+    /// Longitude of the focal point
+    pub angle: Angle,
+    /// Span of the dial the contacts were found on in degrees
+    pub modulus: f64,
+    pub contacts: Vec<MidpointContact>,
+}
+
+impl MidpointTree {
+    /// Find the midpoints of the other points falling on `focus` within `orb`
+    /// degrees on a dial of the given modulus. `None` if the focal point is not
+    /// among the points.
+    pub fn new(focus: &ChartPoint, points: &[(ChartPoint, Angle)], modulus: f64, orb: f64) -> Option<Self> {
+        let angle = points.iter().find(|(point, _)| point == focus)?.1;
+        let others: Vec<(ChartPoint, Angle)> = points.iter()
+            .filter(|(point, _)| point != focus)
+            .cloned()
+            .collect();
+
+        Some(Self::with_midpoints(focus, angle, calculate_midpoints(&others), modulus, orb))
+    }
+
+    /// Find which of the given midpoints fall on a focal point at `angle`
+    /// within `orb` degrees on a dial of the given modulus, e.g. the midpoints
+    /// of another chart
+    pub fn with_midpoints(focus: &ChartPoint, angle: Angle, midpoints: Vec<Midpoint>, modulus: f64, orb: f64) -> Self {
+        let mut contacts: Vec<MidpointContact> = midpoints
+            .into_iter()
+            .filter_map(|midpoint| {
+                // Measured to the near and the far midpoint separately, as
+                // the two only fold onto each other on dials dividing 180°;
+                // the closer contact counts, the smaller angle on a tie
+                let separation = angle.distance_to(midpoint.near).degrees();
+                let (angle, orb_to_contact) = [separation, 180.0 - separation]
+                    .map(|separation| {
+                        let steps = (separation / modulus).round();
+                        (steps * modulus, (separation - steps * modulus).abs())
+                    })
+                    .into_iter()
+                    .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.total_cmp(&b.0)))
+                    .expect("two candidates");
+
+                (orb_to_contact <= orb).then_some(MidpointContact {
+                    midpoint,
+                    angle,
+                    orb: orb_to_contact,
+                })
+            })
+            .collect();
+
+        contacts.sort_by(|a, b| a.orb.total_cmp(&b.orb));
+        Self {
+            focus: focus.clone(),
+            angle,
+            modulus,
+            contacts,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::models::planet::Planet;
+    use float_cmp::approx_eq;
+
+    fn point(planet: Planet, longitude: f64) -> (ChartPoint, Angle) {
+        (ChartPoint::Planet(planet), Angle::from_degrees(longitude))
+    }
+
+    #[test]
+    fn test_near_and_far_midpoints() {
+        // Across 0° Aries the near midpoint lies between the points
+        let midpoint = Midpoint::new(
+            ChartPoint::Planet(Planet::Sun), Angle::from_degrees(350.0),
+            ChartPoint::Planet(Planet::Moon), Angle::from_degrees(20.0),
+        );
+        assert!(approx_eq!(f64, midpoint.near.degrees(), 5.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, midpoint.far.degrees(), 185.0, epsilon = 1e-9));
+
+        // Both midpoints meet on the 90° dial but not on the 360° one
+        assert!(approx_eq!(f64, midpoint.dial_position(90.0), midpoint.far_dial_position(90.0), epsilon = 1e-9));
+        assert!(approx_eq!(f64, midpoint.dial_position(360.0), 5.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, midpoint.far_dial_position(360.0), 185.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, midpoint.far_dial_position(22.5), 5.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn test_midpoint_tree() {
+        let points = [
+            point(Planet::Sun, 100.0),
+            point(Planet::Moon, 80.0),
+            point(Planet::Mars, 121.0),     // Moon/Mars at 100.5, on the Sun
+            point(Planet::Jupiter, 210.0),  // Moon/Jupiter at 145, semi-square the Sun
+        ];
+
+        // On the 360° dial only the direct contact counts
+        let tree = MidpointTree::new(&Planet::Sun.into(), &points, 360.0, 1.0).unwrap();
+        assert_eq!(tree.contacts.len(), 1);
+        assert!(tree.contacts[0].is_direct());
+        assert!(approx_eq!(f64, tree.contacts[0].orb, 0.5, epsilon = 1e-9));
+        assert!(tree.contacts[0].midpoint.involves(&Planet::Moon.into()));
+
+        // The 45° dial adds the semi-square, closest first
+        let tree = MidpointTree::new(&Planet::Sun.into(), &points, 45.0, 1.0).unwrap();
+        assert_eq!(tree.contacts.len(), 2);
+        assert!(tree.contacts[0].midpoint.involves(&Planet::Jupiter.into()));
+        assert!(!tree.contacts[0].is_direct());
+        assert_eq!(tree.contacts[0].aspect(), Some(AspectKind::SemiSquare));
+        assert!(approx_eq!(f64, tree.contacts[0].orb, 0.0, epsilon = 1e-9));
+        assert!(tree.contacts.iter().all(|c| !c.midpoint.involves(&Planet::Sun.into())));
+
+        assert!(MidpointTree::new(&Planet::Pluto.into(), &points, 90.0, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_midpoint_tree_on_odd_harmonic_dial() {
+        // Moon/Mars at 100°, its far point at 280°: on the dial of the 7th
+        // harmonic (360/7°) the near midpoint is 2.86° from two steps, which
+        // folding onto the far one (80°, 22.86° from two steps) would miss
+        let points = [
+            point(Planet::Sun, 0.0),
+            point(Planet::Moon, 80.0),
+            point(Planet::Mars, 120.0),
+        ];
+        let modulus = 360.0 / 7.0;
+
+        let tree = MidpointTree::new(&Planet::Sun.into(), &points, modulus, 3.0).unwrap();
+        assert_eq!(tree.contacts.len(), 1);
+        assert!(approx_eq!(f64, tree.contacts[0].angle, 2.0 * modulus, epsilon = 1e-9));
+        assert!(approx_eq!(f64, tree.contacts[0].orb, 2.0 * modulus - 100.0, epsilon = 1e-9));
+
+        // On the 72° dial of the 5th harmonic the far midpoint is the closer:
+        // 80° is 8° past one step, 100° is 28° from one
+        let tree = MidpointTree::new(&Planet::Sun.into(), &points, 72.0, 10.0).unwrap();
+        assert_eq!(tree.contacts.len(), 1);
+        assert!(approx_eq!(f64, tree.contacts[0].angle, 72.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, tree.contacts[0].orb, 8.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn test_personal_midpoints() {
+        let points = [
+            point(Planet::Sun, 100.0),
+            point(Planet::Mars, 200.0),
+            point(Planet::MC, 300.0),
+        ];
+        assert_eq!(calculate_midpoints(&points).len(), 3);
+
+        // Aries point with each of the three points, plus Sun/MC and Mars/MC
+        let personal = calculate_personal_midpoints(&points);
+        assert_eq!(personal.len(), 5);
+        assert!(personal.iter().all(Midpoint::is_personal));
+        assert!(!personal.iter().any(|m| m.involves(&ChartPoint::Planet(Planet::Sun)) && m.involves(&ChartPoint::Planet(Planet::Mars))));
+
+        let aries_mc = personal.iter()
+            .find(|m| m.involves(&ChartPoint::AriesPoint) && m.involves(&ChartPoint::Planet(Planet::MC)))
+            .unwrap();
+        assert!(approx_eq!(f64, aries_mc.near.degrees(), 330.0, epsilon = 1e-9));
+    }
+}
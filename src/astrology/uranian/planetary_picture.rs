@@ -0,0 +1,237 @@
+//! Planetary pictures of the Hamburg School
+//!
+//! A planetary picture `A+B-C=D` says that the sensitive point `A+B-C` falls
+//! on `D`. Since that is the same as `A+B=C+D`, the pictures `A+B-D=C`,
+//! `C+D-A=B` and `C+D-B=A` all describe one configuration, which is reported
+//! once. Distances are measured on a dial of the given modulus, so on the 90°
+//! dial a picture also holds when `D` is square or opposite the sensitive point.
+//! A point and its own antiscion always sum to 180° and a point and its own
+//! contra-antiscion to 0°, so pictures pairing two such couples hold for any
+//! positions and are skipped.
+
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::utils::angle::Angle;
+
+/// A sensitive point `A+B-C`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensitivePoint {
+    pub a: ChartPoint,
+    pub b: ChartPoint,
+    pub c: ChartPoint,
+    /// Longitude of `A+B-C`
+    pub angle: Angle,
+}
+
+/// A planetary picture `A+B-C=D` found within orb
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetaryPicture {
+    pub a: ChartPoint,
+    pub b: ChartPoint,
+    pub c: ChartPoint,
+    pub d: ChartPoint,
+    /// Longitude of the sensitive point `A+B-C`
+    pub sensitive_point: Angle,
+    /// Distance between the sensitive point and `D` on the dial in degrees
+    pub orb: f64,
+    /// Modulus of the dial the picture was found on
+    pub modulus: f64,
+}
+
+impl PlanetaryPicture {
+    /// Check if a point takes part in the picture
+    pub fn involves(&self, point: &ChartPoint) -> bool {
+        [&self.a, &self.b, &self.c, &self.d].contains(&point)
+    }
+}
+
+impl fmt::Display for PlanetaryPicture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}+{}-{}={}", self.a, self.b, self.c, self.d)
+    }
+}
+
+/// Calculate the sensitive point `A+B-C`
+pub fn sensitive_point(a: Angle, b: Angle, c: Angle) -> Angle {
+    Angle::from_degrees(a.degrees() + b.degrees() - c.degrees())
+}
+
+/// Calculate every sensitive point `A+B-C` between distinct points, `A+B` and
+/// `B+A` counted once
+pub fn sensitive_points(points: &[(ChartPoint, Angle)]) -> Vec<SensitivePoint> {
+    let mut sensitive = Vec::new();
+
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            for (k, (c, angle_c)) in points.iter().enumerate() {
+                if k == i || k == j {
+                    continue;
+                }
+
+                let (a, angle_a) = &points[i];
+                let (b, angle_b) = &points[j];
+                sensitive.push(SensitivePoint {
+                    a: a.clone(),
+                    b: b.clone(),
+                    c: c.clone(),
+                    angle: sensitive_point(*angle_a, *angle_b, *angle_c),
+                });
+            }
+        }
+    }
+
+    sensitive
+}
+
+/// Find the planetary pictures within `orb` degrees on a dial of the given
+/// modulus (360, 90, 45 or 22.5 for the usual Uranian dials), closest first
+pub fn find_planetary_pictures(points: &[(ChartPoint, Angle)], modulus: f64, orb: f64) -> Vec<PlanetaryPicture> {
+    let mut pictures = Vec::new();
+    let n = points.len();
+
+    // Each configuration A+B=C+D is visited once: A<B, C<D and (A,B) before (C,D)
+    for i in 0..n {
+        for j in (i + 1)..n {
+            for k in i..n {
+                for l in (k + 1)..n {
+                    if (k, l) <= (i, j) || k == j || l == i || l == j {
+                        continue;
+                    }
+
+                    let (a, angle_a) = &points[i];
+                    let (b, angle_b) = &points[j];
+                    let (c, angle_c) = &points[k];
+                    let (d, angle_d) = &points[l];
+
+                    if let (Some(first), Some(second)) = (reflection_sum(a, b), reflection_sum(c, d)) {
+                        if (first - second).rem_euclid(modulus) == 0.0 {
+                            continue;
+                        }
+                    }
+
+                    let sensitive = sensitive_point(*angle_a, *angle_b, *angle_c);
+                    let distance = (sensitive.degrees() - angle_d.degrees()).rem_euclid(modulus);
+                    let distance = distance.min(modulus - distance);
+
+                    if distance <= orb {
+                        pictures.push(PlanetaryPicture {
+                            a: a.clone(),
+                            b: b.clone(),
+                            c: c.clone(),
+                            d: d.clone(),
+                            sensitive_point: sensitive,
+                            orb: distance,
+                            modulus,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    pictures.sort_by(|a, b| a.orb.total_cmp(&b.orb));
+    pictures
+}
+
+/// The constant sum of a point and its own antiscion (180°) or
+/// contra-antiscion (0°)
+fn reflection_sum(a: &ChartPoint, b: &ChartPoint) -> Option<f64> {
+    match (a, b) {
+        (ChartPoint::Antiscion(point), other) | (other, ChartPoint::Antiscion(point)) if **point == *other => Some(180.0),
+        (ChartPoint::ContraAntiscion(point), other) | (other, ChartPoint::ContraAntiscion(point)) if **point == *other => Some(0.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::models::planet::Planet;
+    use float_cmp::approx_eq;
+
+    fn point(planet: Planet, longitude: f64) -> (ChartPoint, Angle) {
+        (ChartPoint::Planet(planet), Angle::from_degrees(longitude))
+    }
+
+    #[test]
+    fn test_sensitive_points() {
+        let angle = sensitive_point(Angle::from_degrees(350.0), Angle::from_degrees(40.0), Angle::from_degrees(100.0));
+        assert!(approx_eq!(f64, angle.degrees(), 290.0, epsilon = 1e-9));
+
+        let points = [point(Planet::Sun, 10.0), point(Planet::Moon, 20.0), point(Planet::Mars, 30.0), point(Planet::Jupiter, 40.0)];
+        // Six pairs A+B, each with two remaining points as C
+        assert_eq!(sensitive_points(&points).len(), 12);
+    }
+
+    #[test]
+    fn test_planetary_pictures() {
+        // Sun+Jupiter-Moon = 10+130-50 = 90, one degree from Saturn
+        let points = [
+            point(Planet::Sun, 10.0),
+            point(Planet::Moon, 50.0),
+            point(Planet::Jupiter, 130.0),
+            point(Planet::Saturn, 91.0),
+        ];
+
+        let pictures = find_planetary_pictures(&points, 360.0, 1.5);
+        assert_eq!(pictures.len(), 1);
+        let picture = &pictures[0];
+        assert_eq!(picture.to_string(), "sun+jupiter-moon=saturn");
+        assert!(approx_eq!(f64, picture.sensitive_point.degrees(), 90.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, picture.orb, 1.0, epsilon = 1e-9));
+        assert!(picture.involves(&ChartPoint::Planet(Planet::Saturn)));
+
+        assert!(find_planetary_pictures(&points, 360.0, 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_pictures_with_antiscia() {
+        // Sun+Sun'-Moon always equals Moon', whatever the positions
+        let sun = point(Planet::Sun, 33.0);
+        let moon = point(Planet::Moon, 251.0);
+        let points = [
+            sun.clone(),
+            moon.clone(),
+            (ChartPoint::Antiscion(Box::new(sun.0)), sun.1.antiscion()),
+            (ChartPoint::Antiscion(Box::new(moon.0)), moon.1.antiscion()),
+        ];
+
+        assert!(find_planetary_pictures(&points, 360.0, 1.0).is_empty());
+
+        // Sun+Moon-Sun' = 10+50-170 = 250, exactly on Mars
+        let sun = point(Planet::Sun, 10.0);
+        let points = [
+            sun.clone(),
+            point(Planet::Moon, 50.0),
+            (ChartPoint::Antiscion(Box::new(sun.0)), sun.1.antiscion()),
+            point(Planet::Mars, 250.0),
+        ];
+
+        let pictures = find_planetary_pictures(&points, 360.0, 1.0);
+        assert_eq!(pictures.len(), 1);
+        assert_eq!(pictures[0].to_string(), "sun+moon-sun_antiscion=mars");
+        assert!(approx_eq!(f64, pictures[0].orb, 0.0, epsilon = 1e-9));
+    }
+
+    #[test]
+    fn test_pictures_on_smaller_dials() {
+        // The sensitive point at 90 is square Saturn at 180.5, which only
+        // counts on the 90° dial and below
+        let points = [
+            point(Planet::Sun, 10.0),
+            point(Planet::Moon, 50.0),
+            point(Planet::Jupiter, 130.0),
+            point(Planet::Saturn, 180.5),
+        ];
+
+        assert!(find_planetary_pictures(&points, 360.0, 1.0).is_empty());
+        for modulus in [90.0, 45.0, 22.5] {
+            let pictures = find_planetary_pictures(&points, modulus, 1.0);
+            assert_eq!(pictures.len(), 1);
+            assert!(approx_eq!(f64, pictures[0].orb, 0.5, epsilon = 1e-9));
+        }
+    }
+}
@@ -0,0 +1,335 @@
+//! Solar arc directions
+//!
+//! Every point of the natal chart is moved forward by the same arc, the
+//! distance the Sun has travelled since birth in the secondary progression
+//! (one day after birth for each year of life). The Hamburg School also uses
+//! the Naibod arc, the Sun's mean daily motion of 59'08" per year, which only
+//! depends on the age. The directed points are shown in a ring around the
+//! natal dial; a directed point standing on a natal point or midpoint, or in
+//! hard aspect to it on the dial, marks the date of an event.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Serialize, Deserialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::astrology::models::aspect::AspectKind;
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::ephemeris::{BackendKind, Ephemeris, EphemerisError};
+use crate::astrology::models::planet::{ChartPositions, Planet};
+pub use crate::astrology::models::progression::{NAIBOD_RATE, TROPICAL_YEAR};
+use crate::astrology::uranian::midpoint::calculate_midpoints;
+use crate::astrology::uranian::sort_list::SortItem;
+use crate::utils::angle::Angle;
+
+/// How the arc of direction is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumIter, EnumString, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
+pub enum ArcMethod {
+    /// True solar arc: progressed Sun minus natal Sun
+    #[default]
+    SolarArc,
+    /// Naibod arc: the Sun's mean motion for every year of life
+    Naibod,
+}
+
+/// A set of directed points for one date
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectedChart {
+    pub method: ArcMethod,
+    /// Date the points are directed to
+    pub date: DateTime<Utc>,
+    /// Arc of direction in degrees
+    pub arc: f64,
+    /// Natal points moved forward by the arc
+    pub points: Vec<(ChartPoint, Angle)>,
+}
+
+/// A directed point reaching a natal point or midpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectionContact {
+    /// The directed point
+    pub directed: ChartPoint,
+    /// The natal point or midpoint it reaches
+    pub natal: SortItem,
+    /// Angle between them at the exact date: 0 for a conjunction, otherwise
+    /// the hard aspect (a multiple of the dial's modulus) of the contact
+    pub angle: f64,
+    /// Arc of direction at which the contact is exact
+    pub arc: f64,
+    /// Date the contact is exact
+    pub date: DateTime<Utc>,
+}
+
+impl DirectionContact {
+    /// Get the aspect of the contact, if it is one of the known aspects
+    pub fn aspect(&self) -> Option<AspectKind> {
+        AspectKind::iter().find(|kind| (kind.angle() - self.angle).abs() < 1e-6)
+    }
+}
+
+/// Solar arc directions of a natal chart
+#[derive(Debug, Clone)]
+pub struct SolarArc {
+    method: ArcMethod,
+    /// Ephemeris of the birth, used for the progressed Sun
+    ephemeris: Ephemeris,
+    /// Longitude of the natal Sun
+    natal_sun: f64,
+    /// Natal points to direct
+    points: Vec<(ChartPoint, Angle)>,
+}
+
+impl SolarArc {
+    /// Prepare the directions of a natal chart, using the best available backend
+    pub fn new(natal: &ChartPositions, method: ArcMethod) -> Result<Self, EphemerisError> {
+        Self::with_backend(natal, method, BackendKind::detect())
+    }
+
+    /// Prepare the directions of a natal chart with a specific backend
+    pub fn with_backend(natal: &ChartPositions, method: ArcMethod, kind: BackendKind) -> Result<Self, EphemerisError> {
+        let (latitude, longitude) = natal.location;
+        let ephemeris = Ephemeris::with_backend(natal.datetime, latitude, longitude, kind)?;
+        let natal_sun = ephemeris.get_planet_position(&Planet::Sun)?.longitude;
+        let points = Planet::iter()
+            .filter_map(|planet| natal.get_position(&planet))
+            .map(|position| (ChartPoint::Planet(position.planet), Angle::from_degrees(position.longitude)))
+            .collect();
+
+        Ok(Self {
+            method,
+            ephemeris,
+            natal_sun,
+            points,
+        })
+    }
+
+    /// Direct other natal points instead of the bodies of the chart (fixed
+    /// stars and minor planets on the dial, for instance)
+    pub fn with_points(mut self, points: &[(ChartPoint, Angle)]) -> Self {
+        self.points = points.to_vec();
+        self
+    }
+
+    /// Get the method of measuring the arc
+    pub fn method(&self) -> ArcMethod {
+        self.method
+    }
+
+    /// Get the moment of birth
+    pub fn birth(&self) -> DateTime<Utc> {
+        self.ephemeris.datetime()
+    }
+
+    /// Get the natal points being directed
+    pub fn points(&self) -> &[(ChartPoint, Angle)] {
+        &self.points
+    }
+
+    /// Get the age in tropical years at a date
+    pub fn age_at(&self, date: DateTime<Utc>) -> f64 {
+        (date - self.birth()).num_milliseconds() as f64 / 86_400_000.0 / TROPICAL_YEAR
+    }
+
+    /// Calculate the arc of direction for an age in years
+    pub fn arc_for_age(&self, years: f64) -> Result<f64, EphemerisError> {
+        match self.method {
+            ArcMethod::Naibod => Ok(years * NAIBOD_RATE),
+            ArcMethod::SolarArc => {
+                let sun = self.progressed_sun(years)?.0;
+                // Whole circles only after 360 years, so the arc keeps its sign
+                Ok((sun - self.natal_sun + 180.0).rem_euclid(360.0) - 180.0)
+            }
+        }
+    }
+
+    /// Calculate the arc of direction at a date
+    pub fn arc_at(&self, date: DateTime<Utc>) -> Result<f64, EphemerisError> {
+        self.arc_for_age(self.age_at(date))
+    }
+
+    /// Find the age in years at which the arc of direction reaches `arc`
+    pub fn age_for_arc(&self, arc: f64) -> Result<f64, EphemerisError> {
+        let mut years = arc / NAIBOD_RATE;
+        if self.method == ArcMethod::Naibod {
+            return Ok(years);
+        }
+
+        // Newton's method on the progressed Sun, whose daily motion is the
+        // rate of the arc per year; converges in a few steps
+        for _ in 0..20 {
+            let (sun, speed) = self.progressed_sun(years)?;
+            let arc_error = (arc - (sun - self.natal_sun) + 180.0).rem_euclid(360.0) - 180.0;
+            years += arc_error / speed;
+            if arc_error.abs() < 1e-8 {
+                break;
+            }
+        }
+
+        Ok(years)
+    }
+
+    /// Find the date at which the arc of direction reaches `arc`
+    pub fn date_for_arc(&self, arc: f64) -> Result<DateTime<Utc>, EphemerisError> {
+        let years = self.age_for_arc(arc)?;
+        Ok(self.birth() + Duration::milliseconds((years * TROPICAL_YEAR * 86_400_000.0).round() as i64))
+    }
+
+    /// Direct the natal points to a date
+    pub fn direct(&self, date: DateTime<Utc>) -> Result<DirectedChart, EphemerisError> {
+        let arc = self.arc_at(date)?;
+        let points = self.points.iter()
+            .map(|(point, angle)| (point.clone(), Angle::from_degrees(angle.degrees() + arc)))
+            .collect();
+
+        Ok(DirectedChart {
+            method: self.method,
+            date,
+            arc,
+            points,
+        })
+    }
+
+    /// Find the dates between `from` and `to` at which a directed point
+    /// reaches a natal point or midpoint on a dial of the given modulus, in
+    /// order of date. A directed point is not matched with its own natal place.
+    pub fn find_contacts(
+        &self,
+        modulus: f64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<DirectionContact>, EphemerisError> {
+        let first_arc = self.arc_at(from.max(self.birth()))?;
+        let last_arc = self.arc_at(to)?;
+        let far_midpoints = (180.0 % modulus).abs() > 1e-9;
+
+        // Natal targets with the longitudes at which they are reached
+        let mut targets: Vec<(SortItem, Angle)> = self.points.iter()
+            .map(|(point, angle)| (SortItem::Point(point.clone()), *angle))
+            .collect();
+        for midpoint in calculate_midpoints(&self.points) {
+            let item = SortItem::Midpoint(midpoint.point1, midpoint.point2);
+            if far_midpoints {
+                targets.push((item.clone(), midpoint.far));
+            }
+            targets.push((item, midpoint.near));
+        }
+
+        let mut contacts = Vec::new();
+        for (directed, natal_angle) in &self.points {
+            for (target, target_angle) in &targets {
+                if let SortItem::Point(point) = target {
+                    if point.source() == directed.source() {
+                        continue;
+                    }
+                }
+
+                // Every arc that carries the point onto the target on the dial
+                let mut arc = first_arc + (target_angle.degrees() - natal_angle.degrees() - first_arc).rem_euclid(modulus);
+                while arc <= last_arc {
+                    let directed_angle = Angle::from_degrees(natal_angle.degrees() + arc);
+                    let separation = directed_angle.distance_to(*target_angle).degrees();
+                    let separation = match target {
+                        SortItem::Midpoint(..) => separation.min(180.0 - separation),
+                        SortItem::Point(_) => separation,
+                    };
+
+                    contacts.push(DirectionContact {
+                        directed: directed.clone(),
+                        natal: target.clone(),
+                        angle: (separation / modulus).round() * modulus,
+                        arc,
+                        date: self.date_for_arc(arc)?,
+                    });
+                    arc += modulus;
+                }
+            }
+        }
+
+        contacts.sort_by_key(|contact| contact.date);
+        Ok(contacts)
+    }
+
+    /// Longitude and daily motion of the secondary progressed Sun for an age in years
+    fn progressed_sun(&self, years: f64) -> Result<(f64, f64), EphemerisError> {
+        let mut ephemeris = self.ephemeris.clone();
+        ephemeris.update_time(self.birth() + Duration::milliseconds((years * 86_400_000.0).round() as i64))?;
+        let sun = ephemeris.get_planet_position(&Planet::Sun)?;
+        Ok((sun.longitude, sun.speed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use float_cmp::approx_eq;
+    use crate::astrology::models::planet::PlanetPosition;
+
+    fn j2000() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    fn chart(longitudes: &[(Planet, f64)]) -> ChartPositions {
+        let mut chart = ChartPositions::new(j2000(), (52.5, 13.4));
+        for (planet, longitude) in longitudes {
+            chart.add_position(PlanetPosition::new(*planet, *longitude, 0.0, 1.0, 1.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        }
+        chart
+    }
+
+    #[test]
+    fn test_naibod_arc() {
+        let directions = SolarArc::with_backend(&chart(&[(Planet::Sun, 10.0)]), ArcMethod::Naibod, BackendKind::Analytic).unwrap();
+
+        assert!(approx_eq!(f64, directions.arc_for_age(30.0).unwrap(), 29.569, epsilon = 1e-3));
+        assert!(approx_eq!(f64, directions.age_for_arc(NAIBOD_RATE).unwrap(), 1.0, epsilon = 1e-12));
+
+        let directed = directions.direct(directions.date_for_arc(20.0).unwrap()).unwrap();
+        assert!(approx_eq!(f64, directed.arc, 20.0, epsilon = 1e-6));
+        assert!(approx_eq!(f64, directed.points[0].1.degrees(), 30.0, epsilon = 1e-6));
+    }
+
+    #[test]
+    fn test_true_solar_arc() {
+        let directions = SolarArc::with_backend(&chart(&[(Planet::Sun, 280.4)]), ArcMethod::SolarArc, BackendKind::Analytic).unwrap();
+
+        // Thirty days after the birth in January the Sun moves a little
+        // faster than its mean motion
+        let arc = directions.arc_for_age(30.0).unwrap();
+        assert!(arc > 30.0 && arc < 31.0, "{arc}");
+
+        // The date of an arc is found again from the arc
+        let date = Utc.with_ymd_and_hms(2040, 6, 15, 0, 0, 0).unwrap();
+        let arc = directions.arc_at(date).unwrap();
+        let found = directions.date_for_arc(arc).unwrap();
+        assert!((found - date).num_minutes().abs() < 5, "{found}");
+    }
+
+    #[test]
+    fn test_direction_contacts() {
+        let natal = chart(&[(Planet::Sun, 10.0), (Planet::Mars, 50.0)]);
+        let directions = SolarArc::with_backend(&natal, ArcMethod::Naibod, BackendKind::Analytic).unwrap();
+        let until = directions.date_for_arc(95.0).unwrap();
+
+        // Sun to Sun/Mars (arc 20), Sun to Mars (40), Mars square Sun (50)
+        // and Mars square Sun/Mars (70)
+        let contacts = directions.find_contacts(90.0, j2000(), until).unwrap();
+        let arcs: Vec<f64> = contacts.iter().map(|contact| contact.arc).collect();
+        assert_eq!(arcs.len(), 4);
+        for (arc, expected) in arcs.iter().zip([20.0, 40.0, 50.0, 70.0]) {
+            assert!(approx_eq!(f64, *arc, expected, epsilon = 1e-9));
+        }
+
+        assert_eq!(contacts[0].natal, SortItem::Midpoint(Planet::Sun.into(), Planet::Mars.into()));
+        assert_eq!(contacts[0].aspect(), Some(AspectKind::Conjunction));
+        assert_eq!(contacts[2].directed, Planet::Mars);
+        assert_eq!(contacts[2].aspect(), Some(AspectKind::Square));
+        assert!(approx_eq!(f64, directions.age_at(contacts[1].date), 40.0 / NAIBOD_RATE, epsilon = 1e-6));
+
+        // The 360° dial keeps only the conjunctions, far midpoints included
+        let contacts = directions.find_contacts(360.0, j2000(), until).unwrap();
+        assert!(contacts.iter().all(|contact| contact.angle == 0.0));
+        assert_eq!(contacts.len(), 2);
+    }
+}
@@ -0,0 +1,183 @@
+//! Sorted listing of points and midpoints on a dial ("90° sort")
+//!
+//! Every point and every midpoint is reduced modulo the dial's span and the
+//! entries are listed in ascending order, so that whatever stands together on
+//! the dial stands together in the list. On dials whose span divides 180° the
+//! near and far midpoints coincide; on the others both are listed.
+
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::uranian::midpoint::calculate_midpoints;
+use crate::utils::angle::Angle;
+
+/// What an entry of the sort list stands for
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SortItem {
+    Point(ChartPoint),
+    Midpoint(ChartPoint, ChartPoint),
+}
+
+impl fmt::Display for SortItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortItem::Point(point) => write!(f, "{}", point),
+            SortItem::Midpoint(point1, point2) => write!(f, "{}/{}", point1, point2),
+        }
+    }
+}
+
+/// An entry of the sort list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortEntry {
+    pub item: SortItem,
+    /// Longitude of the point or midpoint
+    pub longitude: Angle,
+    /// Position on the dial in degrees (0 to modulus)
+    pub position: f64,
+}
+
+/// Points and midpoints sorted by their position on a dial
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortList {
+    /// Span of the dial in degrees
+    pub modulus: f64,
+    /// Entries in ascending order of position
+    pub entries: Vec<SortEntry>,
+}
+
+impl SortList {
+    /// Create the sort list of some points and all their midpoints for a dial
+    /// of the given modulus (90 for the classic 90° sort)
+    pub fn new(points: &[(ChartPoint, Angle)], modulus: f64) -> Self {
+        let entry = |item, longitude: Angle| SortEntry {
+            item,
+            longitude,
+            position: longitude.degrees().rem_euclid(modulus),
+        };
+        let far_midpoints = (180.0 % modulus).abs() > 1e-9;
+
+        let mut entries: Vec<SortEntry> = points.iter()
+            .map(|(point, angle)| entry(SortItem::Point(point.clone()), *angle))
+            .collect();
+
+        for midpoint in calculate_midpoints(points) {
+            let item = SortItem::Midpoint(midpoint.point1, midpoint.point2);
+            if far_midpoints {
+                entries.push(entry(item.clone(), midpoint.far));
+            }
+            entries.push(entry(item, midpoint.near));
+        }
+
+        entries.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Self { modulus, entries }
+    }
+
+    /// Group entries standing within `orb` degrees of their neighbour,
+    /// wrapping around the end of the dial
+    pub fn groups(&self, orb: f64) -> Vec<Vec<&SortEntry>> {
+        let mut groups: Vec<Vec<&SortEntry>> = Vec::new();
+
+        for entry in &self.entries {
+            match groups.last_mut() {
+                Some(group) if entry.position - group[group.len() - 1].position <= orb => group.push(entry),
+                _ => groups.push(vec![entry]),
+            }
+        }
+
+        // The first and last groups meet across 0°
+        if groups.len() > 1 {
+            let first = groups[0][0].position;
+            let last = groups[groups.len() - 1].last().map_or(0.0, |entry| entry.position);
+            if first + self.modulus - last <= orb {
+                let mut wrapped = groups.pop().unwrap_or_default();
+                wrapped.append(&mut groups[0]);
+                groups[0] = wrapped;
+            }
+        }
+
+        groups
+    }
+
+    /// Render the list as text, one entry per line and a blank line between
+    /// groups of entries within `orb` degrees
+    pub fn to_text(&self, orb: f64) -> String {
+        self.groups(orb)
+            .iter()
+            .map(|group| {
+                group.iter()
+                    .map(|entry| format!("{} {}", format_position(entry.position), entry.item))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+impl fmt::Display for SortList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{} {}", format_position(entry.position), entry.item)?;
+        }
+        Ok(())
+    }
+}
+
+/// Format a dial position as degrees and minutes, e.g. " 7°05'"
+fn format_position(position: f64) -> String {
+    let minutes = (position * 60.0).round() as u32;
+    format!("{:>3}°{:02}'", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::models::planet::Planet;
+    use float_cmp::approx_eq;
+
+    fn point(planet: Planet, longitude: f64) -> (ChartPoint, Angle) {
+        (ChartPoint::Planet(planet), Angle::from_degrees(longitude))
+    }
+
+    #[test]
+    fn test_ninety_degree_sort() {
+        let points = [point(Planet::Sun, 100.0), point(Planet::Moon, 200.0), point(Planet::Mars, 355.0)];
+        let list = SortList::new(&points, 90.0);
+
+        // Three points and three midpoints, near and far coinciding
+        assert_eq!(list.entries.len(), 6);
+        assert!(list.entries.windows(2).all(|pair| pair[0].position <= pair[1].position));
+
+        // Sun 10°, Moon/Mars 7.5°, Moon 20°, Sun/Moon 60°, Sun/Mars 47.5°, Mars 85°
+        let positions: Vec<f64> = list.entries.iter().map(|entry| entry.position).collect();
+        for (position, expected) in positions.iter().zip([7.5, 10.0, 20.0, 47.5, 60.0, 85.0]) {
+            assert!(approx_eq!(f64, *position, expected, epsilon = 1e-9));
+        }
+        assert_eq!(list.entries[0].item, SortItem::Midpoint(Planet::Moon.into(), Planet::Mars.into()));
+
+        // On the 360° dial the far midpoints are listed as well
+        assert_eq!(SortList::new(&points, 360.0).entries.len(), 9);
+    }
+
+    #[test]
+    fn test_groups_and_text() {
+        let points = [point(Planet::Sun, 0.5), point(Planet::Moon, 89.8), point(Planet::Jupiter, 45.0)];
+        let list = SortList::new(&points, 90.0);
+
+        // Sun/Moon falls at 45.15° next to Jupiter; Sun and Moon meet across 0°
+        let groups = list.groups(1.0);
+        let names: Vec<Vec<String>> = groups.iter()
+            .map(|group| group.iter().map(|entry| entry.item.to_string()).collect())
+            .collect();
+        assert!(names.contains(&vec!["moon".to_string(), "sun".to_string()]));
+        assert!(names.iter().any(|group| group.contains(&"jupiter".to_string()) && group.contains(&"sun/moon".to_string())));
+
+        let text = list.to_text(1.0);
+        assert!(text.contains(" 45°09' sun/moon"));
+        assert!(text.contains("\n\n"));
+        assert_eq!(list.to_string().lines().count(), list.entries.len());
+    }
+}
@@ -0,0 +1,131 @@
+//! Synastry: the comparison of two charts
+//!
+//! The points of the second chart are set against those of the first: the
+//! aspects between them, found with the same aspects and orbs as within a
+//! chart, and the midpoints of the first chart that the points of the second
+//! one fall on, directly or by hard aspect on the dial. The comparison is not
+//! symmetric for midpoints; [`Synastry::swapped`] gives the other direction.
+
+use serde::{Serialize, Deserialize};
+
+use crate::astrology::models::aspect::{Aspect, AspectCalculator, AspectPoint};
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::planet::ChartPositions;
+use crate::astrology::uranian::midpoint::{calculate_midpoints, MidpointTree};
+use crate::utils::angle::Angle;
+
+/// Two charts compared point by point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Synastry {
+    /// Points of the first chart (person A), whose midpoints are looked at
+    pub first: Vec<(ChartPoint, Angle)>,
+    /// Points of the second chart (person B), activating them
+    pub second: Vec<(ChartPoint, Angle)>,
+}
+
+impl Synastry {
+    /// Compare the points of two charts
+    pub fn new(first: &[(ChartPoint, Angle)], second: &[(ChartPoint, Angle)]) -> Self {
+        Self {
+            first: first.to_vec(),
+            second: second.to_vec(),
+        }
+    }
+
+    /// Compare the bodies of two charts
+    pub fn from_charts(first: &ChartPositions, second: &ChartPositions) -> Self {
+        Self::new(&first.points(), &second.points())
+    }
+
+    /// The same comparison the other way round
+    pub fn swapped(&self) -> Self {
+        Self::new(&self.second, &self.first)
+    }
+
+    /// Find the aspects between the points of the first chart and those of
+    /// the second, closest first, each with the point of the first chart as
+    /// `point1`
+    ///
+    /// The charts are fixed moments, so every aspect is reported as stationary.
+    pub fn aspects(&self, calculator: &AspectCalculator) -> Vec<Aspect> {
+        let points = |points: &[(ChartPoint, Angle)]| -> Vec<AspectPoint> {
+            points.iter()
+                .map(|(point, angle)| AspectPoint::new(point.clone(), angle.degrees(), 0.0))
+                .collect()
+        };
+        calculator.find_cross_aspects(&points(&self.first), &points(&self.second))
+    }
+
+    /// Find the midpoints of the first chart falling on each point of the
+    /// second within `orb` degrees on a dial of the given modulus, leaving
+    /// out the points that activate none
+    pub fn midpoint_contacts(&self, modulus: f64, orb: f64) -> Vec<MidpointTree> {
+        let midpoints = calculate_midpoints(&self.first);
+
+        self.second.iter()
+            .map(|(focus, angle)| MidpointTree::with_midpoints(focus, *angle, midpoints.clone(), modulus, orb))
+            .filter(|tree| !tree.contacts.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrology::models::aspect::{AspectKind, AspectMotion};
+    use crate::astrology::models::planet::{Planet, PlanetPosition};
+    use chrono::{TimeZone, Utc};
+    use float_cmp::approx_eq;
+
+    fn chart(positions: &[(Planet, f64)]) -> ChartPositions {
+        let mut chart = ChartPositions::new(Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap(), (0.0, 0.0));
+        for (planet, longitude) in positions {
+            chart.add_position(PlanetPosition::new(*planet, *longitude, 0.0, 1.0, 1.0, 0.0, false, 0.0, 0.0, 0.0, 0.0, 1));
+        }
+        chart
+    }
+
+    #[test]
+    fn test_cross_aspects() {
+        let first = chart(&[(Planet::Sun, 10.0), (Planet::Venus, 160.0)]);
+        let second = chart(&[(Planet::Moon, 12.0), (Planet::Mars, 191.0)]);
+        let synastry = Synastry::from_charts(&first, &second);
+        assert_eq!(synastry.first[0].0, ChartPoint::Planet(Planet::Sun));
+
+        // Sun conjunct Moon and opposite Mars across the charts
+        let aspects = synastry.aspects(&AspectCalculator::major());
+        assert_eq!(aspects.len(), 2);
+        assert_eq!(aspects[0].kind, AspectKind::Opposition);
+        assert_eq!(aspects[0].point1, ChartPoint::Planet(Planet::Sun));
+        assert_eq!(aspects[0].point2, ChartPoint::Planet(Planet::Mars));
+        assert_eq!(aspects[1].kind, AspectKind::Conjunction);
+        assert!(aspects.iter().all(|aspect| aspect.motion == AspectMotion::Stationary));
+
+        // Turned around, the first chart's points come second
+        let aspects = synastry.swapped().aspects(&AspectCalculator::major());
+        assert_eq!(aspects[0].point1, ChartPoint::Planet(Planet::Mars));
+    }
+
+    #[test]
+    fn test_cross_midpoints() {
+        // Person B's Mars squares person A's Sun/Venus (55°), a contact on the 90° dial
+        let first = chart(&[(Planet::Sun, 10.0), (Planet::Venus, 100.0), (Planet::Saturn, 300.0)]);
+        let second = chart(&[(Planet::Mars, 145.5), (Planet::Jupiter, 230.0)]);
+        let synastry = Synastry::from_charts(&first, &second);
+
+        let trees = synastry.midpoint_contacts(90.0, 1.0);
+        assert_eq!(trees.len(), 1);
+        assert_eq!(trees[0].focus, ChartPoint::Planet(Planet::Mars));
+        assert_eq!(trees[0].contacts.len(), 1);
+        let contact = &trees[0].contacts[0];
+        assert!(contact.midpoint.involves(&ChartPoint::Planet(Planet::Sun)));
+        assert!(contact.midpoint.involves(&ChartPoint::Planet(Planet::Venus)));
+        assert!(approx_eq!(f64, contact.angle, 90.0, epsilon = 1e-9));
+        assert!(approx_eq!(f64, contact.orb, 0.5, epsilon = 1e-9));
+
+        // Person B's own Mars/Jupiter midpoint is not looked at
+        assert!(trees.iter().all(|tree| tree.contacts.iter().all(|contact| {
+            !contact.midpoint.involves(&ChartPoint::Planet(Planet::Jupiter))
+        })));
+    }
+}
@@ -0,0 +1,253 @@
+//! Transit search
+//!
+//! Finds the moments a transiting body stands exactly on a natal point,
+//! midpoint or planetary picture, or in hard aspect to it on a dial of the
+//! given modulus ("when does Saturn reach Sun/Moon on the 90° dial"). Every
+//! pass of a retrograde loop is found by the [`CrossingFinder`].
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use strum::IntoEnumIterator;
+
+use crate::astrology::models::aspect::AspectKind;
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::crossing::CrossingFinder;
+use crate::astrology::models::ephemeris::{at, Ephemeris, EphemerisError};
+use crate::astrology::models::planet::Planet;
+use crate::astrology::uranian::midpoint::calculate_midpoints;
+use crate::astrology::uranian::planetary_picture::sensitive_points;
+use crate::utils::angle::{angle_difference, Angle};
+
+/// A natal position a transit can reach
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TransitTarget {
+    Point(ChartPoint),
+    Midpoint(ChartPoint, ChartPoint),
+    /// The sensitive point `A+B-C` of a planetary picture
+    Picture(ChartPoint, ChartPoint, ChartPoint),
+}
+
+impl fmt::Display for TransitTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransitTarget::Point(point) => write!(f, "{}", point),
+            TransitTarget::Midpoint(point1, point2) => write!(f, "{}/{}", point1, point2),
+            TransitTarget::Picture(a, b, c) => write!(f, "{}+{}-{}", a, b, c),
+        }
+    }
+}
+
+/// Collect the targets of a natal chart: its points, optionally followed by
+/// their midpoints and the sensitive points of their planetary pictures
+pub fn natal_targets(points: &[(ChartPoint, Angle)], midpoints: bool, pictures: bool) -> Vec<(TransitTarget, Angle)> {
+    let mut targets: Vec<(TransitTarget, Angle)> = points.iter()
+        .map(|(point, angle)| (TransitTarget::Point(point.clone()), *angle))
+        .collect();
+
+    if midpoints {
+        targets.extend(calculate_midpoints(points).into_iter().map(|midpoint| {
+            (TransitTarget::Midpoint(midpoint.point1, midpoint.point2), midpoint.near)
+        }));
+    }
+    if pictures {
+        targets.extend(sensitive_points(points).into_iter().map(|sensitive| {
+            (TransitTarget::Picture(sensitive.a, sensitive.b, sensitive.c), sensitive.angle)
+        }));
+    }
+
+    targets
+}
+
+/// A transiting body exactly reaching a target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitEvent {
+    pub transiting: Planet,
+    pub target: TransitTarget,
+    /// Moment the transit is exact
+    pub date: DateTime<Utc>,
+    /// Longitude of the transiting body at that moment
+    pub longitude: f64,
+    /// Angle to the target: 0 for a conjunction, otherwise the hard aspect (a
+    /// multiple of the dial's modulus); for midpoints measured to the nearer
+    /// of the near and far midpoints
+    pub angle: f64,
+    pub is_retrograde: bool,
+    /// Number of the pass over the same spot within the search, starting at
+    /// 1; retrograde loops give passes 2 and 3
+    pub pass: usize,
+}
+
+impl TransitEvent {
+    /// Get the aspect of the transit, if it is one of the known aspects
+    pub fn aspect(&self) -> Option<AspectKind> {
+        AspectKind::iter().find(|kind| (kind.angle() - self.angle).abs() < 1e-6)
+    }
+}
+
+/// Searches the transits over a period on a dial of a given modulus
+#[derive(Debug, Clone)]
+pub struct TransitSearch {
+    /// Ephemeris supplying the backend and observer location
+    ephemeris: Ephemeris,
+    /// Span of the dial in degrees
+    modulus: f64,
+}
+
+impl TransitSearch {
+    /// Create a search with the backend and location of an ephemeris, on a
+    /// dial of the given modulus (360 for conjunctions only, 90 to include
+    /// squares and oppositions, ...)
+    pub fn new(ephemeris: &Ephemeris, modulus: f64) -> Self {
+        Self {
+            ephemeris: ephemeris.clone(),
+            modulus,
+        }
+    }
+
+    /// Find every moment between `from` and `to` at which one of the
+    /// transiting bodies reaches one of the targets, in order of time
+    pub fn find_transits(
+        &self,
+        transiting: &[Planet],
+        targets: &[(TransitTarget, Angle)],
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<TransitEvent>, EphemerisError> {
+        // The far midpoints only fall on the near ones on dials dividing 180°
+        let mut target_angles: Vec<(&TransitTarget, f64)> = Vec::new();
+        for (target, angle) in targets {
+            target_angles.push((target, angle.degrees()));
+            if matches!(target, TransitTarget::Midpoint(..)) && (180.0 % self.modulus).abs() > 1e-9 {
+                target_angles.push((target, angle.opposite().degrees()));
+            }
+        }
+
+        let span = (to - from).num_milliseconds() as f64 / 86_400_000.0;
+        let mut events = Vec::new();
+
+        for planet in transiting {
+            let mut finder = CrossingFinder::new(&self.ephemeris, *planet, from, self.modulus);
+            finder.scan::<EphemerisError>(span, |finder, start, end| {
+                for (target, target_angle) in &target_angles {
+                    if let Some(exact) = finder.crossing(|_| *target_angle, start, end)? {
+                        let separation = Angle::from_degrees(exact.longitude).distance_to(Angle::from_degrees(*target_angle)).degrees();
+                        let separation = match target {
+                            TransitTarget::Midpoint(..) => separation.min(180.0 - separation),
+                            _ => separation,
+                        };
+
+                        events.push(TransitEvent {
+                            transiting: *planet,
+                            target: (*target).clone(),
+                            date: at(from, exact.days),
+                            longitude: exact.longitude,
+                            angle: (separation / self.modulus).round() * self.modulus,
+                            is_retrograde: exact.speed < 0.0,
+                            pass: 0,
+                        });
+                    }
+                }
+
+                Ok(())
+            })?;
+        }
+
+        events.sort_by_key(|event| event.date);
+        for i in 0..events.len() {
+            events[i].pass = 1 + events[..i].iter()
+                .filter(|earlier| {
+                    earlier.transiting == events[i].transiting
+                        && earlier.target == events[i].target
+                        && angle_difference(earlier.longitude, events[i].longitude) < 1e-3
+                })
+                .count();
+        }
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::astrology::models::ephemeris::BackendKind;
+
+    fn ephemeris() -> Ephemeris {
+        Ephemeris::with_backend(Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap(), 0.0, 0.0, BackendKind::Analytic).unwrap()
+    }
+
+    fn assert_near(date: DateTime<Utc>, expected: DateTime<Utc>) {
+        assert!((date - expected).num_minutes().abs() <= 10, "{date} instead of {expected}");
+    }
+
+    #[test]
+    fn test_sun_on_the_cardinal_points() {
+        // On the 90° dial 0° Aries is reached at both equinoxes and solstices
+        let search = TransitSearch::new(&ephemeris(), 90.0);
+        let targets = natal_targets(&[(ChartPoint::AriesPoint, Angle::from_degrees(0.0))], false, false);
+        let from = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+
+        let events = search.find_transits(&[Planet::Sun], &targets, from, to).unwrap();
+        assert_eq!(events.len(), 4);
+        assert_near(events[0].date, Utc.with_ymd_and_hms(2000, 3, 20, 7, 35, 0).unwrap());
+        assert_near(events[1].date, Utc.with_ymd_and_hms(2000, 6, 21, 1, 48, 0).unwrap());
+        assert_near(events[3].date, Utc.with_ymd_and_hms(2000, 12, 21, 13, 37, 0).unwrap());
+        assert_eq!(events[0].aspect(), Some(AspectKind::Conjunction));
+        assert_eq!(events[1].aspect(), Some(AspectKind::Square));
+        assert_eq!(events[2].aspect(), Some(AspectKind::Opposition));
+
+        // Only the conjunction on the 360° dial
+        let search = TransitSearch::new(&ephemeris(), 360.0);
+        assert_eq!(search.find_transits(&[Planet::Sun], &targets, from, to).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_retrograde_passes() {
+        // Mercury loops over a natal Venus at 10° Pisces in February and March 2000
+        let search = TransitSearch::new(&ephemeris(), 360.0);
+        let targets = [(TransitTarget::Point(Planet::Venus.into()), Angle::from_degrees(340.0))];
+        let from = Utc.with_ymd_and_hms(2000, 2, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2000, 4, 15, 0, 0, 0).unwrap();
+
+        let events = search.find_transits(&[Planet::Mercury], &targets, from, to).unwrap();
+        let passes: Vec<(usize, bool)> = events.iter().map(|event| (event.pass, event.is_retrograde)).collect();
+        assert_eq!(passes, [(1, false), (2, true), (3, false)]);
+        for event in &events {
+            assert!((event.longitude - 340.0).abs() < 1e-4, "{}", event.longitude);
+        }
+        assert!(events[0].date < Utc.with_ymd_and_hms(2000, 2, 16, 0, 0, 0).unwrap());
+        assert!(events[2].date > Utc.with_ymd_and_hms(2000, 3, 26, 0, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_midpoint_and_picture_targets() {
+        let points = [
+            (ChartPoint::Planet(Planet::Sun), Angle::from_degrees(10.0)),
+            (ChartPoint::Planet(Planet::Moon), Angle::from_degrees(50.0)),
+            (ChartPoint::Planet(Planet::Mars), Angle::from_degrees(100.0)),
+        ];
+        // Three points, three midpoints and three pairs with one C each
+        let targets = natal_targets(&points, true, true);
+        assert_eq!(targets.len(), 9);
+        assert!(targets.iter().any(|(target, angle)| {
+            target.to_string() == "sun/moon" && (angle.degrees() - 30.0).abs() < 1e-9
+        }));
+        assert!(targets.iter().any(|(target, angle)| {
+            target.to_string() == "sun+moon-mars" && (angle.degrees() - 320.0).abs() < 1e-9
+        }));
+
+        // The Sun reaches Sun/Moon at 30° in April and its far point at 210°
+        // in October on the 360° dial
+        let search = TransitSearch::new(&ephemeris(), 360.0);
+        let midpoint = [targets[3].clone()];
+        let from = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2001, 1, 1, 0, 0, 0).unwrap();
+        let events = search.find_transits(&[Planet::Sun], &midpoint, from, to).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.angle == 0.0 && event.pass == 1));
+    }
+}
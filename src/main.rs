@@ -10,10 +10,29 @@ mod astrology {
         pub mod planet;
         pub mod zodiac;
         pub mod celestial_body;
+        pub mod chart_point;
+        pub mod crossing;
         pub mod ephemeris;
+        pub mod fixed_star;
+        pub mod houses;
+        pub mod minor_body;
+        pub mod aspect;
+        pub mod declination;
+        pub mod rise_set;
+        pub mod progression;
+        pub mod planetary_return;
+        pub mod relationship;
     }
     pub mod uranian {
         pub mod dial;
+        pub mod antiscia;
+        pub mod midpoint;
+        pub mod planetary_picture;
+        pub mod sort_list;
+        pub mod solar_arc;
+        pub mod transit;
+        pub mod graphic_ephemeris;
+        pub mod synastry;
     }
 }
 
@@ -5,15 +5,33 @@ use std::sync::{Arc, Mutex};
 use std::ffi::CString;
 use std::os::raw::c_char;
 use std::convert::TryFrom;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use strum::IntoEnumIterator;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
-use crate::astrology::models::planet::Planet;
-use crate::astrology::uranian::dial::UranianDial;
+use crate::astrology::models::aspect::{Aspect, AspectCalculator, AspectKind, AspectPoint};
+use crate::astrology::models::chart_point::ChartPoint;
+use crate::astrology::models::declination::{DeclinationAspect, DeclinationCalculator, DeclinationPoint};
+use crate::astrology::models::ephemeris::{ephemeris_path, julian_day, CoordinateFrame, Ephemeris};
+use crate::astrology::models::fixed_star::FixedStarCatalog;
+use crate::astrology::models::houses::HouseSystem;
+use crate::astrology::models::minor_body::{MinorBody, MinorBodyPosition, MinorBodyRegistry, MPCORB_FILE};
+use crate::astrology::models::planet::{ChartPositions, Planet, PlanetPosition};
+use crate::astrology::models::planetary_return::{PlanetaryReturn, ReturnFinder, ReturnZodiac};
+use crate::astrology::models::progression::{MCProgression, Progression, ProgressionKind};
+use crate::astrology::models::relationship::{relationship_chart, RelationshipKind};
+use crate::astrology::models::rise_set::{RiseSetFinder, RiseSetTimes};
+use crate::astrology::models::zodiac::{Ayanamsa, Zodiac};
+use crate::astrology::uranian::antiscia;
+use crate::astrology::uranian::dial::{DialType, UranianDial};
+use crate::astrology::uranian::graphic_ephemeris::GraphicEphemeris;
+use crate::astrology::uranian::midpoint::Midpoint;
+use crate::astrology::uranian::planetary_picture::PlanetaryPicture;
+use crate::astrology::uranian::solar_arc::{ArcMethod, DirectionContact, SolarArc};
+use crate::astrology::uranian::sort_list::SortItem;
+use crate::astrology::uranian::transit::{natal_targets, TransitEvent, TransitSearch};
 use crate::utils::angle::Angle;
-use crate::utils::ephemeris::Ephemeris;
 
 /// Represents a celestial body's position and attributes
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -24,8 +42,18 @@ pub struct CelestialBody {
     pub latitude: f64,
     pub distance: f64,
     pub speed: f64,
+    pub right_ascension: f64,
+    pub declination: f64,
+    pub altitude: f64,
+    pub azimuth: f64,
+    pub apparent_size: f64,
+    pub magnitude: f64,
+    pub phase: f64,
+    pub elongation: f64,
+    pub is_east_of_sun: bool,
     pub color: String,
     pub is_retrograde: bool,
+    pub is_out_of_bounds: bool,
     pub is_dignified: bool,
     pub house: Option<u8>,
 }
@@ -39,8 +67,74 @@ impl Default for CelestialBody {
             latitude: 0.0,
             distance: 0.0,
             speed: 0.0,
+            right_ascension: 0.0,
+            declination: 0.0,
+            altitude: 0.0,
+            azimuth: 0.0,
+            apparent_size: 0.0,
+            magnitude: 0.0,
+            phase: 0.0,
+            elongation: 0.0,
+            is_east_of_sun: false,
             color: "#FFFFFF".to_string(),
             is_retrograde: false,
+            is_out_of_bounds: false,
+            is_dignified: false,
+            house: None,
+        }
+    }
+}
+
+impl From<&PlanetPosition> for CelestialBody {
+    fn from(position: &PlanetPosition) -> Self {
+        Self {
+            name: position.planet.to_string(),
+            symbol: position.planet.symbol().to_string(),
+            longitude: position.longitude,
+            latitude: position.latitude,
+            distance: position.distance_au,
+            speed: position.speed,
+            right_ascension: position.right_ascension,
+            declination: position.declination,
+            altitude: position.altitude,
+            azimuth: position.azimuth,
+            apparent_size: position.apparent_size,
+            magnitude: position.magnitude,
+            phase: position.phase,
+            elongation: position.elongation,
+            is_east_of_sun: position.is_east_of_sun,
+            color: position.planet.color().to_string(),
+            is_retrograde: position.is_retrograde,
+            is_out_of_bounds: position.is_out_of_bounds,
+            is_dignified: false,
+            house: position.house,
+        }
+    }
+}
+
+impl From<&MinorBodyPosition> for CelestialBody {
+    fn from(position: &MinorBodyPosition) -> Self {
+        let point = ChartPoint::MinorBody { number: position.number, name: position.name.clone() };
+        
+        Self {
+            name: position.name.clone(),
+            symbol: point.symbol().to_string(),
+            longitude: position.longitude,
+            latitude: position.latitude,
+            distance: position.distance_au,
+            speed: position.speed,
+            right_ascension: 0.0,
+            declination: 0.0,
+            altitude: 0.0,
+            azimuth: 0.0,
+            apparent_size: 0.0,
+            magnitude: 0.0,
+            phase: 0.0,
+            elongation: 0.0,
+            is_east_of_sun: false,
+            color: point.color().to_string(),
+            is_retrograde: position.is_retrograde,
+            is_out_of_bounds: false,
             is_dignified: false,
             house: None,
         }
@@ -59,8 +153,18 @@ pub struct CelestialBodyViewModel {
     latitude: qt_property!(f64; NOTIFY data_changed),
     distance: qt_property!(f64; NOTIFY data_changed),
     speed: qt_property!(f64; NOTIFY data_changed),
+    right_ascension: qt_property!(f64; NOTIFY data_changed),
+    declination: qt_property!(f64; NOTIFY data_changed),
+    altitude: qt_property!(f64; NOTIFY data_changed),
+    azimuth: qt_property!(f64; NOTIFY data_changed),
+    apparent_size: qt_property!(f64; NOTIFY data_changed),
+    magnitude: qt_property!(f64; NOTIFY data_changed),
+    phase: qt_property!(f64; NOTIFY data_changed),
+    elongation: qt_property!(f64; NOTIFY data_changed),
+    is_east_of_sun: qt_property!(bool; NOTIFY data_changed),
     color: qt_property!(QString; NOTIFY data_changed),
     is_retrograde: qt_property!(bool; NOTIFY data_changed),
+    is_out_of_bounds: qt_property!(bool; NOTIFY data_changed),
     is_dignified: qt_property!(bool; NOTIFY data_changed),
     house: qt_property!(i32; NOTIFY data_changed), // -1 for none/unknown
     display_position: qt_property!(f64; NOTIFY data_changed), // Position for display (considering harmonics)
@@ -83,8 +187,18 @@ impl CelestialBodyViewModel {
         self.latitude = body.latitude;
         self.distance = body.distance;
         self.speed = body.speed;
+        self.right_ascension = body.right_ascension;
+        self.declination = body.declination;
+        self.altitude = body.altitude;
+        self.azimuth = body.azimuth;
+        self.apparent_size = body.apparent_size;
+        self.magnitude = body.magnitude;
+        self.phase = body.phase;
+        self.elongation = body.elongation;
+        self.is_east_of_sun = body.is_east_of_sun;
         self.color = planet.color().into();
         self.is_retrograde = body.is_retrograde;
+        self.is_out_of_bounds = body.is_out_of_bounds;
         self.is_dignified = body.is_dignified;
         self.house = body.house.map(|h| h as i32).unwrap_or(-1);
         self.display_position = body.longitude; // Will be updated by harmonic calculation
@@ -97,7 +211,17 @@ impl CelestialBodyViewModel {
         self.latitude = body.latitude;
         self.distance = body.distance;
         self.speed = body.speed;
+        self.right_ascension = body.right_ascension;
+        self.declination = body.declination;
+        self.altitude = body.altitude;
+        self.azimuth = body.azimuth;
+        self.apparent_size = body.apparent_size;
+        self.magnitude = body.magnitude;
+        self.phase = body.phase;
+        self.elongation = body.elongation;
+        self.is_east_of_sun = body.is_east_of_sun;
         self.is_retrograde = body.is_retrograde;
+        self.is_out_of_bounds = body.is_out_of_bounds;
         self.is_dignified = body.is_dignified;
         self.house = body.house.map(|h| h as i32).unwrap_or(-1);
         self.display_position = body.longitude; // Will be updated by harmonic calculation
@@ -122,14 +246,72 @@ pub struct DialController {
     // Ephemeris for astronomical calculations
     ephemeris: Arc<Mutex<Option<Ephemeris>>>,
     
+    // Fixed star catalogue and the stars shown on the dial
+    fixed_stars: FixedStarCatalog,
+    dial_stars: Vec<String>,
+    
+    // Minor planets known by MPC number and the ones shown on the dial
+    minor_bodies: MinorBodyRegistry,
+    dial_minor_bodies: Vec<u32>,
+    
+    // House system chosen for the chart
+    selected_house_system: HouseSystem,
+    
+    // Zodiac the signs are counted in
+    selected_zodiac: Zodiac,
+    
+    // Point the chart is seen from, and a second one shown in a ring beside it
+    selected_frame: CoordinateFrame,
+    ring_frame: Option<CoordinateFrame>,
+    
+    // Aspects looked for and the ones found in the current chart
+    aspect_calculator: AspectCalculator,
+    aspect_list: Vec<Aspect>,
+    
+    // Parallels and contra-parallels looked for and the ones found in the current chart
+    declination_calculator: DeclinationCalculator,
+    parallel_list: Vec<DeclinationAspect>,
+    
+    // Solar arc directions shown in the outer ring: method and target date
+    direction: Option<(ArcMethod, DateTime<Utc>)>,
+    
+    // Progressions shown in their ring: kind, MC progression and target date
+    progression: Option<(ProgressionKind, MCProgression, DateTime<Utc>)>,
+    
+    // Ephemeris of the second chart compared with this one (synastry)
+    partner: Option<Ephemeris>,
+    
+    // Relationship chart of the two charts shown on the dial instead of the first one
+    relationship: Option<RelationshipKind>,
+    
     // Properties exposed to QML
     celestial_bodies: qt_property!(QVariantList; NOTIFY data_changed),
     rotation: qt_property!(f64; NOTIFY view_changed),
     zoom: qt_property!(f64; NOTIFY view_changed),
     harmonic: qt_property!(u32; NOTIFY harmonic_changed),
+    dial_type: qt_property!(QString; NOTIFY dial_type_changed),
+    dial_modulus: qt_property!(f64; NOTIFY dial_type_changed),
+    tick_count: qt_property!(u32; NOTIFY dial_type_changed),
+    tick_step: qt_property!(f64; NOTIFY dial_type_changed),
+    label_step: qt_property!(f64; NOTIFY dial_type_changed),
+    sign_markers: qt_property!(u32; NOTIFY dial_type_changed),
     current_time: qt_property!(QString; NOTIFY time_changed),
     location_latitude: qt_property!(f64; NOTIFY location_changed),
     location_longitude: qt_property!(f64; NOTIFY location_changed),
+    house_system: qt_property!(QString; NOTIFY data_changed),
+    house_cusps: qt_property!(QVariantList; NOTIFY data_changed),
+    zodiac: qt_property!(QString; NOTIFY data_changed),
+    ayanamsa: qt_property!(f64; NOTIFY data_changed), // Tropical longitude of sidereal 0° Aries
+    frame: qt_property!(QString; NOTIFY data_changed),
+    frame_bodies: qt_property!(QVariantList; NOTIFY data_changed),
+    aspects: qt_property!(QVariantList; NOTIFY data_changed),
+    parallels: qt_property!(QVariantList; NOTIFY data_changed),
+    midpoint_orb: qt_property!(f64; NOTIFY data_changed),
+    directed_bodies: qt_property!(QVariantList; NOTIFY data_changed),
+    solar_arc: qt_property!(f64; NOTIFY data_changed),
+    progressed_bodies: qt_property!(QVariantList; NOTIFY data_changed),
+    partner_bodies: qt_property!(QVariantList; NOTIFY data_changed),
+    synastry_aspects: qt_property!(QVariantList; NOTIFY data_changed),
     
     // View models for celestial bodies
     celestial_body_models: HashMap<Planet, QObjectPinned<CelestialBodyViewModel>>,
@@ -141,6 +323,7 @@ pub struct DialController {
     data_changed: qt_signal!(),
     view_changed: qt_signal!(),
     harmonic_changed: qt_signal!(),
+    dial_type_changed: qt_signal!(),
     time_changed: qt_signal!(),
     location_changed: qt_signal!(),
     
@@ -153,10 +336,50 @@ pub struct DialController {
     zoom_by: qt_method!(fn(&mut self, factor: f64) -> f64),
     set_zoom: qt_method!(fn(&mut self, zoom: f64) -> f64),
     set_harmonic: qt_method!(fn(&mut self, harmonic: u32)),
+    set_dial_type: qt_method!(fn(&mut self, dial_type: QString) -> bool),
     get_planet_positions: qt_method!(fn(&self) -> QVariantMap),
     calculate_aspects: qt_method!(fn(&self, planet1: QString, planet2: QString) -> QVariantMap),
-    calculate_midpoints: qt_method!(fn(&self) -> QVariantList),
+    set_aspect_set: qt_method!(fn(&mut self, name: QString, orb: f64) -> bool),
+    set_aspect_orb: qt_method!(fn(&mut self, aspect: QString, orb: f64) -> bool),
+    set_orb_factor: qt_method!(fn(&mut self, body: QString, factor: f64) -> bool),
+    set_parallel_orb: qt_method!(fn(&mut self, orb: f64)),
+    calculate_midpoints: qt_method!(fn(&self, personal_only: bool) -> QVariantList),
+    get_planetary_pictures: qt_method!(fn(&self, orb: f64) -> QVariantList),
+    export_planetary_pictures: qt_method!(fn(&self, orb: f64) -> QString),
+    get_sort_list: qt_method!(fn(&self, orb: f64) -> QVariantList),
+    get_sort_list_text: qt_method!(fn(&self, orb: f64) -> QString),
+    set_antiscia: qt_method!(fn(&mut self, antiscia: bool, contra_antiscia: bool)),
+    set_directions: qt_method!(fn(&mut self, datetime: QString, method: QString) -> bool),
+    clear_directions: qt_method!(fn(&mut self)),
+    set_progressions: qt_method!(fn(&mut self, datetime: QString, kind: QString, mc_progression: QString) -> bool),
+    clear_progressions: qt_method!(fn(&mut self)),
+    load_partner: qt_method!(fn(&mut self, datetime: QString, lat: f64, lng: f64) -> bool),
+    clear_partner: qt_method!(fn(&mut self)),
+    get_synastry_midpoints: qt_method!(fn(&self, orb: f64, reverse: bool) -> QVariantList),
+    show_relationship_chart: qt_method!(fn(&mut self, kind: QString) -> bool),
+    clear_relationship_chart: qt_method!(fn(&mut self)),
+    export_relationship_chart: qt_method!(fn(&self, kind: QString) -> QString),
+    cast_return: qt_method!(fn(&self, body: QString, year: i32, month: u32, lat: f64, lng: f64, zodiac: QString) -> QVariantMap),
+    get_rise_set_times: qt_method!(fn(&self, from: QString, refraction: bool, horizon: f64) -> QVariantList),
+    get_direction_contacts: qt_method!(fn(&self, from: QString, to: QString) -> QVariantList),
+    get_antiscion_contacts: qt_method!(fn(&self, orb: f64) -> QVariantList),
+    find_transits: qt_method!(fn(&self, bodies: QString, from: QString, to: QString, midpoints: bool, pictures: bool) -> QVariantList),
+    get_graphic_ephemeris: qt_method!(fn(&self, bodies: QString, from: QString, to: QString, modulus: f64) -> QVariantMap),
+    export_graphic_ephemeris: qt_method!(fn(&self, bodies: QString, from: QString, to: QString, modulus: f64) -> QString),
     get_planet_info: qt_method!(fn(&self, planet: QString) -> QVariantMap),
+    get_fixed_stars: qt_method!(fn(&self) -> QVariantList),
+    add_fixed_star: qt_method!(fn(&mut self, name: QString) -> bool),
+    remove_fixed_star: qt_method!(fn(&mut self, name: QString) -> bool),
+    load_minor_bodies: qt_method!(fn(&mut self, path: QString) -> i32),
+    add_minor_body: qt_method!(fn(&mut self, number: u32, name: QString) -> bool),
+    remove_minor_body: qt_method!(fn(&mut self, number: u32) -> bool),
+    set_house_system: qt_method!(fn(&mut self, system: QString) -> bool),
+    get_houses: qt_method!(fn(&self) -> QVariantMap),
+    set_zodiac: qt_method!(fn(&mut self, name: QString) -> bool),
+    set_user_ayanamsa: qt_method!(fn(&mut self, epoch: QString, value: f64, rate: f64) -> bool),
+    set_frame: qt_method!(fn(&mut self, name: QString) -> bool),
+    show_frame_ring: qt_method!(fn(&mut self, name: QString) -> bool),
+    clear_frame_ring: qt_method!(fn(&mut self)),
 }
 
 impl DialController {
@@ -164,9 +387,40 @@ impl DialController {
         let mut obj = Self::default();
         obj.dial = Arc::new(Mutex::new(UranianDial::new()));
         obj.ephemeris = Arc::new(Mutex::new(None));
+        obj.fixed_stars = FixedStarCatalog::bundled();
+        obj.dial_stars = Vec::new();
+        obj.minor_bodies = MinorBodyRegistry::new();
+        obj.dial_minor_bodies = Vec::new();
+        obj.selected_house_system = HouseSystem::default();
+        obj.house_system = HouseSystem::default().to_string().into();
+        obj.house_cusps = QVariantList::default();
+        obj.selected_zodiac = Zodiac::default();
+        obj.zodiac = Zodiac::default().to_string().into();
+        obj.ayanamsa = 0.0;
+        obj.selected_frame = CoordinateFrame::default();
+        obj.frame = CoordinateFrame::default().to_string().into();
+        obj.ring_frame = None;
+        obj.frame_bodies = QVariantList::default();
+        obj.aspect_calculator = AspectCalculator::major();
+        obj.aspect_list = Vec::new();
+        obj.aspects = QVariantList::default();
+        obj.declination_calculator = DeclinationCalculator::default();
+        obj.parallel_list = Vec::new();
+        obj.parallels = QVariantList::default();
+        obj.midpoint_orb = 1.5;
+        obj.direction = None;
+        obj.directed_bodies = QVariantList::default();
+        obj.solar_arc = 0.0;
+        obj.progression = None;
+        obj.progressed_bodies = QVariantList::default();
+        obj.partner = None;
+        obj.relationship = None;
+        obj.partner_bodies = QVariantList::default();
+        obj.synastry_aspects = QVariantList::default();
         obj.rotation = 0.0;
         obj.zoom = 1.0;
         obj.harmonic = 1;
+        obj.apply_dial_type(DialType::default());
         obj.celestial_body_models = HashMap::new();
         obj.celestial_bodies = QVariantList::default();
         obj.is_initialized = false;
@@ -180,11 +434,10 @@ impl DialController {
     
     /// Initialize the dial controller with the given datetime and location
     pub fn initialize(&mut self, datetime: QString, lat: f64, lng: f64) -> bool {
-        // Parse the datetime string (expected format: "YYYY-MM-DD HH:MM:SS")
-        let datetime_str = datetime.to_string();
-        let dt = match DateTime::parse_from_str(&datetime_str, "%Y-%m-%d %H:%M:%S") {
-            Ok(dt) => dt,
-            Err(_) => return false,
+        // Parse the datetime string (expected format: "YYYY-MM-DD HH:MM:SS", UTC)
+        let dt = match parse_datetime(&datetime) {
+            Some(dt) => dt,
+            None => return false,
         };
         
         // Update location
@@ -192,8 +445,11 @@ impl DialController {
         self.location_longitude = lng;
         
         // Create ephemeris for the given time and location
-        match Ephemeris::new(dt.with_timezone(&Utc), lat, lng) {
-            Ok(ephem) => {
+        match Ephemeris::new(dt, lat, lng) {
+            Ok(mut ephem) => {
+                ephem.set_house_system(self.selected_house_system);
+                ephem.set_zodiac(self.selected_zodiac);
+                ephem.set_frame(self.selected_frame);
                 *self.ephemeris.lock().unwrap() = Some(ephem);
                 self.current_time = datetime;
                 self.is_initialized = true;
@@ -211,14 +467,13 @@ impl DialController {
             return false;
         }
         
-        let datetime_str = datetime.to_string();
-        let dt = match DateTime::parse_from_str(&datetime_str, "%Y-%m-%d %H:%M:%S") {
-            Ok(dt) => dt,
-            Err(_) => return false,
+        let dt = match parse_datetime(&datetime) {
+            Some(dt) => dt,
+            None => return false,
         };
         
         if let Some(ephem) = self.ephemeris.lock().unwrap().as_mut() {
-            if ephem.update_time(dt.with_timezone(&Utc)).is_ok() {
+            if ephem.update_time(dt).is_ok() {
                 self.current_time = datetime;
                 self.update_celestial_bodies();
                 self.time_changed();
@@ -284,6 +539,21 @@ impl DialController {
         }
     }
     
+    /// Change the dial type: a modulus such as "90" or "22.5", or "360/n"
+    /// for the dial of any harmonic. Unlike `set_harmonic`, positions are
+    /// taken modulo the dial's span rather than multiplied.
+    fn set_dial_type(&mut self, dial_type: QString) -> bool {
+        match dial_type.to_string().parse::<DialType>() {
+            Ok(dial_type) => {
+                self.dial.lock().unwrap().set_dial_type(dial_type);
+                self.apply_dial_type(dial_type);
+                self.update_celestial_bodies();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+    
     fn get_planet_positions(&self) -> QVariantMap {
         let mut result = QVariantMap::default();
         
@@ -302,64 +572,469 @@ impl DialController {
         result
     }
     
+    /// Get the aspect between two bodies of the current chart, or an empty map
+    /// when they are not in aspect (the full list is in `aspects`)
     fn calculate_aspects(&self, planet1: QString, planet2: QString) -> QVariantMap {
-        let mut result = QVariantMap::default();
+        let (planet1, planet2) = (planet1.to_string(), planet2.to_string());
         
-        if let Some(ephem) = self.ephemeris.lock().unwrap().as_ref() {
-            // Get planet positions and calculate aspects
-            // This is a simplified version - actual implementation would use the ephemeris
-            // to calculate precise aspects between the two planets
-            
-            let planet1_name = planet1.to_string();
-            let planet2_name = planet2.to_string();
-            
-            // For demonstration, return a mock aspect
-            result.insert("type".into(), "trine".into());
-            result.insert("orb".into(), 2.5.into());
-            result.insert("exact".into(), false.into());
-            result.insert("planet1".into(), planet1_name.into());
-            result.insert("planet2".into(), planet2_name.into());
+        self.aspect_list.iter()
+            .find(|aspect| {
+                let (name1, name2) = (aspect.point1.to_string(), aspect.point2.to_string());
+                (name1 == planet1 && name2 == planet2) || (name1 == planet2 && name2 == planet1)
+            })
+            .map(aspect_map)
+            .unwrap_or_default()
+    }
+    
+    /// Choose the aspects to look for: "major", "minor" (major and minor) or
+    /// "harmonic8"/"harmonic16" for Uranian work, the latter with the given orb
+    fn set_aspect_set(&mut self, name: QString, orb: f64) -> bool {
+        self.aspect_calculator = match name.to_string().as_str() {
+            "major" => AspectCalculator::major(),
+            "minor" => AspectCalculator::major_and_minor(),
+            "harmonic8" => AspectCalculator::harmonic(8, orb),
+            "harmonic16" => AspectCalculator::harmonic(16, orb),
+            _ => return false,
+        };
+        self.update_celestial_bodies();
+        true
+    }
+    
+    /// Set the orb of an aspect in degrees, e.g. "square" or "semi_square"
+    fn set_aspect_orb(&mut self, aspect: QString, orb: f64) -> bool {
+        match aspect.to_string().parse::<AspectKind>() {
+            Ok(kind) => {
+                self.aspect_calculator.set_orb(kind, orb);
+                self.update_celestial_bodies();
+                true
+            }
+            Err(_) => false,
         }
-        
-        result
     }
     
-    fn calculate_midpoints(&self) -> QVariantList {
+    /// Scale the orbs of every aspect to a planet or point
+    fn set_orb_factor(&mut self, body: QString, factor: f64) -> bool {
+        match body.to_string().parse::<Planet>() {
+            Ok(planet) => {
+                self.aspect_calculator.set_orb_factor(planet, factor);
+                self.update_celestial_bodies();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+    
+    /// Set the orb of parallels and contra-parallels in degrees of declination
+    fn set_parallel_orb(&mut self, orb: f64) {
+        self.declination_calculator.set_orb(orb);
+        self.update_celestial_bodies();
+    }
+    
+    /// Get the midpoints of everything on the dial, or only those involving
+    /// the Aries point, MC, Ascendant or node when `personal_only` is set
+    fn calculate_midpoints(&self, personal_only: bool) -> QVariantList {
         let mut midpoints = QVariantList::default();
         
-        if let Some(_ephem) = self.ephemeris.lock().unwrap().as_ref() {
-            // Calculate midpoints between all planet pairs
-            let planets: Vec<&Planet> = self.celestial_body_models.keys().collect();
+        if self.is_initialized {
+            let dial = self.dial.lock().unwrap();
+            let found = if personal_only {
+                dial.calculate_personal_midpoints()
+            } else {
+                dial.calculate_midpoints()
+            };
             
-            for i in 0..planets.len() {
-                for j in (i + 1)..planets.len() {
-                    let p1 = planets[i];
-                    let p2 = planets[j];
-                    
-                    if let (Some(b1), Some(b2)) = (
-                        self.celestial_body_models.get(p1),
-                        self.celestial_body_models.get(p2)
-                    ) {
-                        let b1 = b1.borrow();
-                        let b2 = b2.borrow();
-                        
-                        // Calculate midpoint (simplified)
-                        let midpoint = (b1.longitude + b2.longitude) / 2.0 % 360.0;
-                        
-                        let mut mp_map = QVariantMap::default();
-                        mp_map.insert("planet1".to_string(), p1.to_string().into());
-                        mp_map.insert("planet2".to_string(), p2.to_string().into());
-                        mp_map.insert("position".to_string(), midpoint.into());
-                        
-                        midpoints.push(mp_map.into());
-                    }
-                }
+            for midpoint in &found {
+                midpoints.push(midpoint_map(midpoint, dial.modulus()).into());
             }
         }
         
         midpoints
     }
     
+    /// Get the planetary pictures A+B-C=D on the current dial within `orb` degrees
+    fn get_planetary_pictures(&self, orb: f64) -> QVariantList {
+        let mut pictures = QVariantList::default();
+        
+        if self.is_initialized {
+            for picture in self.dial.lock().unwrap().find_planetary_pictures(orb) {
+                pictures.push(planetary_picture_map(&picture).into());
+            }
+        }
+        
+        pictures
+    }
+    
+    /// Export the planetary pictures on the current dial as JSON, or an empty
+    /// string when there is no chart
+    fn export_planetary_pictures(&self, orb: f64) -> QString {
+        if !self.is_initialized {
+            return QString::default();
+        }
+        
+        let pictures = self.dial.lock().unwrap().find_planetary_pictures(orb);
+        serde_json::to_string_pretty(&pictures).unwrap_or_default().into()
+    }
+    
+    /// Get the sort list of the current dial for a list view, each entry
+    /// carrying the index of its group of entries within `orb` degrees
+    fn get_sort_list(&self, orb: f64) -> QVariantList {
+        let mut entries = QVariantList::default();
+        
+        if self.is_initialized {
+            let list = self.dial.lock().unwrap().sort_list();
+            for (group_index, group) in list.groups(orb).iter().enumerate() {
+                for entry in group {
+                    let mut entry_map = QVariantMap::default();
+                    entry_map.insert("name".to_string(), entry.item.to_string().into());
+                    entry_map.insert("is_midpoint".to_string(), matches!(entry.item, SortItem::Midpoint(..)).into());
+                    entry_map.insert("longitude".to_string(), entry.longitude.degrees().into());
+                    entry_map.insert("position".to_string(), entry.position.into());
+                    entry_map.insert("group".to_string(), (group_index as u32).into());
+                    entries.push(entry_map.into());
+                }
+            }
+        }
+        
+        entries
+    }
+    
+    /// Get the sort list of the current dial as text
+    fn get_sort_list_text(&self, orb: f64) -> QString {
+        if !self.is_initialized {
+            return QString::default();
+        }
+        
+        self.dial.lock().unwrap().sort_list().to_text(orb).into()
+    }
+
+    fn set_antiscia(&mut self, antiscia: bool, contra_antiscia: bool) {
+        {
+            let mut dial = self.dial.lock().unwrap();
+            dial.show_antiscia = antiscia;
+            dial.show_contra_antiscia = contra_antiscia;
+        }
+        self.update_celestial_bodies();
+    }
+
+    /// Show the solar arc directions for a date ("YYYY-MM-DD HH:MM:SS") in the
+    /// outer ring, measured by "solar_arc" or "naibod"
+    fn set_directions(&mut self, datetime: QString, method: QString) -> bool {
+        let (date, method) = match (parse_datetime(&datetime), method.to_string().parse::<ArcMethod>()) {
+            (Some(date), Ok(method)) => (date, method),
+            _ => return false,
+        };
+        
+        self.direction = Some((method, date));
+        self.update_celestial_bodies();
+        true
+    }
+    
+    fn clear_directions(&mut self) {
+        self.direction = None;
+        self.update_celestial_bodies();
+    }
+    
+    /// Show the chart progressed to a date ("YYYY-MM-DD HH:MM:SS") in its
+    /// ring: "secondary", "tertiary" or "minor", with the MC progressed by
+    /// "solar_arc", "solar_arc_in_ra", "naibod" or "progressed_moment"
+    fn set_progressions(&mut self, datetime: QString, kind: QString, mc_progression: QString) -> bool {
+        let date = parse_datetime(&datetime);
+        let kind = kind.to_string().parse::<ProgressionKind>();
+        let mc_progression = mc_progression.to_string().parse::<MCProgression>();
+        match (date, kind, mc_progression) {
+            (Some(date), Ok(kind), Ok(mc_progression)) => {
+                self.progression = Some((kind, mc_progression, date));
+                self.update_celestial_bodies();
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    fn clear_progressions(&mut self) {
+        self.progression = None;
+        self.update_celestial_bodies();
+    }
+    
+    /// Load a second chart for a date ("YYYY-MM-DD HH:MM:SS") and location,
+    /// shown in the outermost ring and compared with the current one
+    fn load_partner(&mut self, datetime: QString, lat: f64, lng: f64) -> bool {
+        let date = match parse_datetime(&datetime) {
+            Some(date) => date,
+            None => return false,
+        };
+        
+        match Ephemeris::new(date, lat, lng) {
+            Ok(mut ephem) => {
+                ephem.set_zodiac(self.selected_zodiac);
+                ephem.set_frame(self.selected_frame);
+                self.partner = Some(ephem);
+                self.update_celestial_bodies();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+    
+    fn clear_partner(&mut self) {
+        self.partner = None;
+        self.relationship = None;
+        self.update_celestial_bodies();
+    }
+    
+    /// List the midpoints of the current chart that the bodies of the second
+    /// chart fall on within `orb` degrees on the current dial, or with
+    /// `reverse` the second chart's midpoints activated by the current one
+    fn get_synastry_midpoints(&self, orb: f64, reverse: bool) -> QVariantList {
+        let mut contacts = QVariantList::default();
+        
+        let dial = self.dial.lock().unwrap();
+        let synastry = match dial.synastry() {
+            Some(synastry) if reverse => synastry.swapped(),
+            Some(synastry) => synastry,
+            None => return contacts,
+        };
+        
+        let modulus = dial.modulus();
+        for tree in synastry.midpoint_contacts(modulus, orb) {
+            for contact in &tree.contacts {
+                let mut contact_map = QVariantMap::default();
+                contact_map.insert("focus".to_string(), tree.focus.to_string().into());
+                contact_map.insert("midpoint".to_string(), midpoint_map(&contact.midpoint, modulus).into());
+                contact_map.insert("angle".to_string(), contact.angle.into());
+                contact_map.insert("aspect".to_string(), contact.aspect().map(|kind| kind.to_string()).unwrap_or_default().into());
+                contact_map.insert("orb".to_string(), contact.orb.into());
+                contacts.push(contact_map.into());
+            }
+        }
+        contacts
+    }
+    
+    /// Put the "composite" or "davison" chart of the current and the second
+    /// chart on the dial in place of the current one; needs a second chart
+    fn show_relationship_chart(&mut self, kind: QString) -> bool {
+        match kind.to_string().parse::<RelationshipKind>() {
+            Ok(kind) if self.partner.is_some() => {
+                self.relationship = Some(kind);
+                self.update_celestial_bodies();
+                true
+            }
+            _ => false,
+        }
+    }
+    
+    fn clear_relationship_chart(&mut self) {
+        self.relationship = None;
+        self.update_celestial_bodies();
+    }
+    
+    /// Get the "composite" or "davison" chart of the current and the second
+    /// chart as JSON, to be saved like any other chart; empty on error
+    fn export_relationship_chart(&self, kind: QString) -> QString {
+        let chart = kind.to_string().parse::<RelationshipKind>().ok().and_then(|kind| {
+            self.relationship_chart(self.ephemeris.lock().unwrap().as_ref()?, kind)
+        });
+        chart.and_then(|chart| serde_json::to_string_pretty(&chart).ok())
+            .unwrap_or_default()
+            .into()
+    }
+    
+    /// Cast the return of a body ("sun", "moon", ...) to its natal longitude
+    /// at a location, in the "tropical" or "sidereal" zodiac: the solar return
+    /// of a year, otherwise the first return in a month. Empty on error
+    fn cast_return(&self, body: QString, year: i32, month: u32, lat: f64, lng: f64, zodiac: QString) -> QVariantMap {
+        let (body, zodiac) = match (body.to_string().parse::<Planet>(), zodiac.to_string().parse::<ReturnZodiac>()) {
+            (Ok(body), Ok(zodiac)) => (body, zodiac),
+            _ => return QVariantMap::default(),
+        };
+        let natal = match self.ephemeris.lock().unwrap().as_ref() {
+            Some(ephem) => ephem.chart_positions(),
+            None => return QVariantMap::default(),
+        };
+        
+        let finder = ReturnFinder::new(&natal, zodiac);
+        let found = match body {
+            Planet::Sun => finder.solar_return(year, (lat, lng)),
+            Planet::Moon => finder.lunar_return(year, month, (lat, lng)),
+            _ => match Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single() {
+                Some(after) => finder.planetary_return(&body, after, (lat, lng)),
+                None => return QVariantMap::default(),
+            },
+        };
+        found.map(|planetary_return| planetary_return_map(&planetary_return)).unwrap_or_default()
+    }
+    
+    /// Get the rising, setting and culmination times of the bodies on the dial
+    /// at the chart location during the 24 hours after a date, with or
+    /// without refraction and for a horizon at an altitude in degrees
+    fn get_rise_set_times(&self, from: QString, refraction: bool, horizon: f64) -> QVariantList {
+        let mut times = QVariantList::default();
+        
+        let from = match parse_datetime(&from) {
+            Some(from) => from,
+            None => return times,
+        };
+        let finder = match self.ephemeris.lock().unwrap().as_ref() {
+            Some(ephem) => RiseSetFinder::new(ephem).with_refraction(refraction).with_horizon(horizon),
+            None => return times,
+        };
+        
+        let bodies: Vec<Planet> = Planet::iter().filter(|planet| self.celestial_body_models.contains_key(planet)).collect();
+        if let Ok(found) = finder.find_all(&bodies, from) {
+            for body_times in &found {
+                times.push(rise_set_map(body_times).into());
+            }
+        }
+        times
+    }
+    
+    /// List the dates between two dates at which a directed point reaches a
+    /// natal point or midpoint on the current dial, with the arc method of the
+    /// directions shown (none when no directions are shown)
+    fn get_direction_contacts(&self, from: QString, to: QString) -> QVariantList {
+        let mut contacts = QVariantList::default();
+        
+        let (from, to) = match (parse_datetime(&from), parse_datetime(&to)) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return contacts,
+        };
+        let found = match self.solar_arc_directions() {
+            Some(directions) => directions.find_contacts(self.dial.lock().unwrap().modulus(), from, to),
+            None => return contacts,
+        };
+        
+        for contact in found.iter().flatten() {
+            contacts.push(direction_contact_map(contact).into());
+        }
+        contacts
+    }
+    
+    /// Find the exact transits of some bodies (comma separated, e.g.
+    /// "saturn,uranus") to the points on the dial between two dates, and
+    /// optionally to their midpoints and planetary pictures, on the current dial
+    fn find_transits(&self, bodies: QString, from: QString, to: QString, midpoints: bool, pictures: bool) -> QVariantList {
+        let mut transits = QVariantList::default();
+        
+        let (bodies, from, to) = match (parse_bodies(&bodies), parse_datetime(&from), parse_datetime(&to)) {
+            (Some(bodies), Some(from), Some(to)) => (bodies, from, to),
+            _ => return transits,
+        };
+        let search = match self.ephemeris.lock().unwrap().as_ref() {
+            Some(ephem) => TransitSearch::new(ephem, self.dial.lock().unwrap().modulus()),
+            None => return transits,
+        };
+        
+        let targets = natal_targets(&self.dial.lock().unwrap().points(), midpoints, pictures);
+        if let Ok(events) = search.find_transits(&bodies, &targets, from, to) {
+            for event in &events {
+                transits.push(transit_event_map(event).into());
+            }
+        }
+        transits
+    }
+    
+    /// Get the graphic ephemeris of some bodies (comma separated) between two
+    /// dates on a dial of the given modulus, with the points on the dial as
+    /// natal lines: "span_days", "modulus", "curves" (each with "segments",
+    /// lists of {days, position}) and "natal" ({name, position})
+    fn get_graphic_ephemeris(&self, bodies: QString, from: QString, to: QString, modulus: f64) -> QVariantMap {
+        let mut graph_map = QVariantMap::default();
+        let graph = match self.graphic_ephemeris(&bodies, &from, &to, modulus) {
+            Some(graph) => graph,
+            None => return graph_map,
+        };
+        
+        let mut curves = QVariantList::default();
+        for curve in &graph.curves {
+            let mut segments = QVariantList::default();
+            for segment in &curve.segments {
+                let mut points = QVariantList::default();
+                for point in segment {
+                    let mut point_map = QVariantMap::default();
+                    point_map.insert("days".to_string(), point.days.into());
+                    point_map.insert("position".to_string(), point.position.into());
+                    points.push(point_map.into());
+                }
+                segments.push(points.into());
+            }
+            
+            let mut curve_map = QVariantMap::default();
+            curve_map.insert("name".to_string(), curve.body.to_string().into());
+            curve_map.insert("symbol".to_string(), curve.body.symbol().to_string().into());
+            curve_map.insert("color".to_string(), curve.body.color().to_string().into());
+            curve_map.insert("segments".to_string(), segments.into());
+            curves.push(curve_map.into());
+        }
+        
+        let mut natal = QVariantList::default();
+        for line in &graph.natal {
+            let mut line_map = QVariantMap::default();
+            line_map.insert("name".to_string(), line.point.to_string().into());
+            line_map.insert("symbol".to_string(), line.point.symbol().to_string().into());
+            line_map.insert("color".to_string(), line.point.color().to_string().into());
+            line_map.insert("position".to_string(), line.position.into());
+            natal.push(line_map.into());
+        }
+        
+        graph_map.insert("span_days".to_string(), graph.span_days().into());
+        graph_map.insert("modulus".to_string(), graph.modulus.into());
+        graph_map.insert("curves".to_string(), curves.into());
+        graph_map.insert("natal".to_string(), natal.into());
+        graph_map
+    }
+    
+    /// Render the graphic ephemeris as an SVG image, empty on error
+    fn export_graphic_ephemeris(&self, bodies: QString, from: QString, to: QString, modulus: f64) -> QString {
+        self.graphic_ephemeris(&bodies, &from, &to, modulus)
+            .map(|graph| graph.to_svg(1000.0, 500.0))
+            .unwrap_or_default()
+            .into()
+    }
+    
+    fn graphic_ephemeris(&self, bodies: &QString, from: &QString, to: &QString, modulus: f64) -> Option<GraphicEphemeris> {
+        let bodies = parse_bodies(bodies)?;
+        let (from, to) = (parse_datetime(from)?, parse_datetime(to)?);
+        if modulus <= 0.0 || to <= from {
+            return None;
+        }
+        
+        let graph = GraphicEphemeris::new(self.ephemeris.lock().unwrap().as_ref()?, &bodies, modulus, from, to).ok()?;
+        Some(graph.with_natal(&self.dial.lock().unwrap().points()))
+    }
+    
+    /// Relationship chart of a chart and the second chart, in the selected house system
+    fn relationship_chart(&self, ephem: &Ephemeris, kind: RelationshipKind) -> Option<ChartPositions> {
+        let mut partner = self.partner.clone()?;
+        partner.set_house_system(self.selected_house_system);
+        relationship_chart(kind, &ephem.chart_positions(), &partner.chart_positions()).ok()
+    }
+    
+    /// Solar arc directions of the natal chart on the dial, if directions are shown
+    fn solar_arc_directions(&self) -> Option<SolarArc> {
+        let (method, _) = self.direction?;
+        let chart = self.ephemeris.lock().unwrap().as_ref()?.chart_positions();
+        let points = self.dial.lock().unwrap().points();
+        SolarArc::new(&chart, method).ok().map(|directions| directions.with_points(&points))
+    }
+    
+    fn get_antiscion_contacts(&self, orb: f64) -> QVariantList {
+        let mut contacts = QVariantList::default();
+
+        if self.is_initialized {
+            let points = self.dial.lock().unwrap().points();
+            for contact in antiscia::find_antiscion_contacts(&points, orb) {
+                let mut contact_map = QVariantMap::default();
+                contact_map.insert("reflection".to_string(), contact.reflection.to_string().into());
+                contact_map.insert("source".to_string(), contact.reflection.source().to_string().into());
+                contact_map.insert("body".to_string(), contact.body.to_string().into());
+                contact_map.insert("orb".to_string(), contact.orb.into());
+                contacts.push(contact_map.into());
+            }
+        }
+
+        contacts
+    }
+    
     fn remove_planet(&mut self, planet: QString) -> bool {
         if let Ok(planet_enum) = planet.to_string().parse::<Planet>() {
             // Remove the planet from the dial model
@@ -395,7 +1070,41 @@ impl DialController {
                     result.insert("speed".to_string(), body.speed.into());
                     result.insert("is_retrograde".to_string(), body.is_retrograde.into());
                     result.insert("is_dignified".to_string(), body.is_dignified.into());
-                    result.insert("house".to_string(), body.house.map(|h| h as i32).unwrap_or(-1).into());
+                    result.insert("house".to_string(), body.house.into());
+                    result.insert("position".to_string(), Angle::from_degrees(body.longitude).to_dms_string_in(self.ayanamsa).into());
+                    result.insert("right_ascension".to_string(), body.right_ascension.into());
+                    result.insert("declination".to_string(), body.declination.into());
+                    result.insert("is_out_of_bounds".to_string(), body.is_out_of_bounds.into());
+                    result.insert("altitude".to_string(), body.altitude.into());
+                    result.insert("azimuth".to_string(), body.azimuth.into());
+                    result.insert("apparent_size".to_string(), body.apparent_size.into());
+                    result.insert("magnitude".to_string(), body.magnitude.into());
+                    result.insert("phase".to_string(), body.phase.into());
+                    result.insert("elongation".to_string(), body.elongation.into());
+                    result.insert("is_east_of_sun".to_string(), body.is_east_of_sun.into());
+                    
+                    // Parallels and contra-parallels the body takes part in
+                    let mut parallels = QVariantList::default();
+                    for parallel in self.parallel_list.iter().filter(|parallel| parallel.involves(&ChartPoint::Planet(*p))) {
+                        parallels.push(parallel_map(parallel).into());
+                    }
+                    result.insert("parallels".to_string(), parallels.into());
+                    
+                    // Midpoints falling on the body on the current dial
+                    let mut tree = QVariantList::default();
+                    if let Some(midpoint_tree) = self.dial.lock().unwrap().midpoint_tree(&ChartPoint::Planet(*p), self.midpoint_orb) {
+                        for contact in &midpoint_tree.contacts {
+                            let mut contact_map = midpoint_map(&contact.midpoint, midpoint_tree.modulus);
+                            contact_map.insert("contact_angle".to_string(), contact.angle.into());
+                            contact_map.insert("orb".to_string(), contact.orb.into());
+                            contact_map.insert("is_direct".to_string(), contact.is_direct().into());
+                            if let Some(aspect) = contact.aspect() {
+                                contact_map.insert("aspect".to_string(), aspect.to_string().into());
+                            }
+                            tree.push(contact_map.into());
+                        }
+                    }
+                    result.insert("midpoint_tree".to_string(), tree.into());
                     
                     break;
                 }
@@ -405,8 +1114,239 @@ impl DialController {
         result
     }
     
+    fn get_fixed_stars(&self) -> QVariantList {
+        let mut stars = QVariantList::default();
+        
+        if let Some(ephem) = self.ephemeris.lock().unwrap().as_ref() {
+            for star in ephem.star_positions(&self.fixed_stars) {
+                let mut star_map = QVariantMap::default();
+                star_map.insert("on_dial".to_string(), self.dial_stars.contains(&star.name).into());
+                star_map.insert("name".to_string(), star.name.into());
+                star_map.insert("longitude".to_string(), star.longitude.into());
+                star_map.insert("latitude".to_string(), star.latitude.into());
+                star_map.insert("right_ascension".to_string(), star.right_ascension.into());
+                star_map.insert("declination".to_string(), star.declination.into());
+                star_map.insert("magnitude".to_string(), star.magnitude.into());
+                stars.push(star_map.into());
+            }
+        }
+        
+        stars
+    }
+    
+    fn add_fixed_star(&mut self, name: QString) -> bool {
+        let name = match self.fixed_stars.get(&name.to_string()) {
+            Some(star) => star.name.clone(),
+            None => return false,
+        };
+        
+        if !self.dial_stars.contains(&name) {
+            self.dial_stars.push(name);
+        }
+        self.update_celestial_bodies();
+        true
+    }
+    
+    fn remove_fixed_star(&mut self, name: QString) -> bool {
+        let name = name.to_string();
+        let before = self.dial_stars.len();
+        self.dial_stars.retain(|star| !star.eq_ignore_ascii_case(&name));
+        
+        if self.dial_stars.len() == before {
+            return false;
+        }
+        
+        self.dial.lock().unwrap().stars.retain(|star, _| !star.eq_ignore_ascii_case(&name));
+        self.update_celestial_bodies();
+        true
+    }
+    
+    /// Load minor planet orbits from a file in the MPCORB.DAT format,
+    /// returning the number of bodies loaded or -1 on error
+    fn load_minor_bodies(&mut self, path: QString) -> i32 {
+        match MinorBodyRegistry::load_mpcorb(path.to_string()) {
+            Ok(registry) => {
+                let count = registry.len();
+                for body in registry.bodies() {
+                    self.minor_bodies.insert(body.clone());
+                }
+                self.update_celestial_bodies();
+                i32::try_from(count).unwrap_or(i32::MAX)
+            }
+            Err(_) => -1,
+        }
+    }
+    
+    /// Add a minor planet to the dial by MPC number
+    ///
+    /// Bodies not loaded yet are looked up in the MPC orbit file of the
+    /// ephemeris path, and otherwise rely on a Swiss Ephemeris asteroid file.
+    fn add_minor_body(&mut self, number: u32, name: QString) -> bool {
+        if self.minor_bodies.get(number).is_none() {
+            let body = match MinorBodyRegistry::find_in_mpcorb(ephemeris_path().join(MPCORB_FILE), number) {
+                Ok(Some(body)) => body,
+                _ => {
+                    let name = name.to_string();
+                    MinorBody::numbered(number, if name.is_empty() { number.to_string() } else { name })
+                }
+            };
+            self.minor_bodies.insert(body);
+        }
+        
+        // Only keep bodies the ephemeris can actually compute
+        if let (Some(ephem), Some(body)) = (self.ephemeris.lock().unwrap().as_ref(), self.minor_bodies.get(number)) {
+            if ephem.get_minor_body_position(body).is_err() {
+                return false;
+            }
+        }
+        
+        if !self.dial_minor_bodies.contains(&number) {
+            self.dial_minor_bodies.push(number);
+        }
+        self.update_celestial_bodies();
+        true
+    }
+    
+    fn remove_minor_body(&mut self, number: u32) -> bool {
+        let before = self.dial_minor_bodies.len();
+        self.dial_minor_bodies.retain(|&n| n != number);
+        
+        if self.dial_minor_bodies.len() == before {
+            return false;
+        }
+        
+        self.dial.lock().unwrap().remove_minor_body(number);
+        self.update_celestial_bodies();
+        true
+    }
+    
+    /// Change the house system, e.g. "placidus", "koch" or "whole_sign"
+    fn set_house_system(&mut self, system: QString) -> bool {
+        let system = match system.to_string().parse::<HouseSystem>() {
+            Ok(system) => system,
+            Err(_) => return false,
+        };
+        
+        self.selected_house_system = system;
+        if let Some(ephem) = self.ephemeris.lock().unwrap().as_mut() {
+            ephem.set_house_system(system);
+        }
+        self.update_celestial_bodies();
+        true
+    }
+    
+    /// Count the signs in the tropical zodiac or a sidereal one by the name
+    /// of its ayanamsa ("lahiri", "fagan_bradley", "krishnamurti", "raman")
+    fn set_zodiac(&mut self, name: QString) -> bool {
+        match name.to_string().parse::<Zodiac>() {
+            Ok(zodiac) => {
+                self.apply_zodiac(zodiac);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+    
+    /// Count the signs in a sidereal zodiac whose ayanamsa is `value` degrees
+    /// on a date ("YYYY-MM-DD HH:MM:SS") and grows by `rate` arcseconds a year
+    fn set_user_ayanamsa(&mut self, epoch: QString, value: f64, rate: f64) -> bool {
+        let epoch = match parse_datetime(&epoch) {
+            Some(epoch) => julian_day(&epoch),
+            None => return false,
+        };
+        
+        self.apply_zodiac(Zodiac::Sidereal(Ayanamsa::UserDefined { epoch, value, rate }));
+        true
+    }
+    
+    fn apply_zodiac(&mut self, zodiac: Zodiac) {
+        self.selected_zodiac = zodiac;
+        self.zodiac = zodiac.to_string().into();
+        if let Some(ephem) = self.ephemeris.lock().unwrap().as_mut() {
+            ephem.set_zodiac(zodiac);
+        }
+        if let Some(partner) = self.partner.as_mut() {
+            partner.set_zodiac(zodiac);
+        }
+        self.update_celestial_bodies();
+    }
+    
+    /// See the chart from the centre of the Earth, the observer, the Sun or
+    /// the barycentre ("geocentric", "topocentric", "heliocentric",
+    /// "barycentric")
+    fn set_frame(&mut self, name: QString) -> bool {
+        let frame = match name.to_string().parse::<CoordinateFrame>() {
+            Ok(frame) => frame,
+            Err(_) => return false,
+        };
+        
+        self.selected_frame = frame;
+        self.frame = frame.to_string().into();
+        if let Some(ephem) = self.ephemeris.lock().unwrap().as_mut() {
+            ephem.set_frame(frame);
+        }
+        if let Some(partner) = self.partner.as_mut() {
+            partner.set_frame(frame);
+        }
+        self.update_celestial_bodies();
+        true
+    }
+    
+    /// Show the chart seen from a second point in a ring beside it, e.g.
+    /// "heliocentric" beside a geocentric chart
+    fn show_frame_ring(&mut self, name: QString) -> bool {
+        match name.to_string().parse::<CoordinateFrame>() {
+            Ok(frame) => {
+                self.ring_frame = Some(frame);
+                self.update_celestial_bodies();
+                true
+            }
+            Err(_) => false,
+        }
+    }
+    
+    fn clear_frame_ring(&mut self) {
+        self.ring_frame = None;
+        self.update_celestial_bodies();
+    }
+    
+    /// Get the house cusps and angles of the chart
+    fn get_houses(&self) -> QVariantMap {
+        let mut result = QVariantMap::default();
+        
+        if let Some(ephem) = self.ephemeris.lock().unwrap().as_ref() {
+            let houses = ephem.houses();
+            let mut cusps = QVariantList::default();
+            for cusp in houses.cusps {
+                cusps.push(cusp.into());
+            }
+            
+            // The system actually used, which differs near the poles
+            result.insert("system".to_string(), houses.system.to_string().into());
+            result.insert("cusps".to_string(), cusps.into());
+            result.insert("ascendant".to_string(), houses.ascendant.into());
+            result.insert("mc".to_string(), houses.mc.into());
+            result.insert("armc".to_string(), houses.armc.into());
+            result.insert("vertex".to_string(), houses.vertex.into());
+            result.insert("east_point".to_string(), houses.east_point.into());
+        }
+        
+        result
+    }
+    
     // Helper methods
     
+    /// Update the properties telling the QML dial how to draw the dial type
+    fn apply_dial_type(&mut self, dial_type: DialType) {
+        self.dial_type = dial_type.to_string().into();
+        self.dial_modulus = dial_type.modulus();
+        self.tick_count = dial_type.tick_count();
+        self.tick_step = dial_type.tick_step();
+        self.label_step = dial_type.label_step();
+        self.sign_markers = dial_type.sign_markers();
+        self.dial_type_changed();
+    }
+    
     fn update_celestial_bodies(&mut self) {
         if !self.is_initialized {
             return;
@@ -415,12 +1355,53 @@ impl DialController {
         let mut celestial_bodies = QVariantList::default();
         
         if let Some(ephem) = self.ephemeris.lock().unwrap().as_ref() {
+            // Place every computed body, including the Hamburg points, on the
+            // dial, or those of the relationship chart when one is shown
+            let chart = self.relationship
+                .and_then(|kind| self.relationship_chart(ephem, kind))
+                .unwrap_or_else(|| ephem.chart_positions());
+            self.dial.lock().unwrap().update_from_chart(&chart);
+            
+            // House cusps for the dial
+            if let Some(houses) = &chart.houses {
+                let mut house_cusps = QVariantList::default();
+                for cusp in houses.cusps {
+                    house_cusps.push(cusp.into());
+                }
+                self.house_cusps = house_cusps;
+                self.house_system = houses.system.to_string().into();
+            }
+            self.ayanamsa = chart.zodiac.ayanamsa(julian_day(&chart.datetime));
+            
+            let mut aspect_points: Vec<AspectPoint> = Planet::iter()
+                .filter_map(|planet| chart.get_position(&planet))
+                .map(AspectPoint::from)
+                .collect();
+            let mut declination_points: Vec<DeclinationPoint> = Planet::iter()
+                .filter_map(|planet| chart.get_position(&planet))
+                .map(DeclinationPoint::from)
+                .collect();
+            
+            // Fixed stars chosen for the dial follow the chart date as well
+            for name in &self.dial_stars {
+                if let Some(star) = self.fixed_stars.get(name) {
+                    let position = ephem.get_star_position(star);
+                    self.dial.lock().unwrap().update_star_position(&position);
+                    declination_points.push(DeclinationPoint::new(ChartPoint::Star(position.name.clone()), position.declination, 0.0));
+                    aspect_points.push(AspectPoint::new(ChartPoint::Star(position.name), position.longitude, 0.0));
+                }
+            }
+            
+            // Bodies the frame in use cannot compute (the Sun, nodes and
+            // angles seen from the Sun) lose their stale view models
+            self.celestial_body_models.retain(|planet, _| chart.get_position(planet).is_some());
+            
             // Update positions for all known planets
             for planet in Planet::iter() {
-                // Get position from ephemeris (simplified)
-                let body = match ephem.get_planet_position(&planet) {
-                    Ok(pos) => pos,
-                    Err(_) => continue,
+                // Bodies the ephemeris cannot compute are left off the dial
+                let body = match chart.get_position(&planet) {
+                    Some(pos) => CelestialBody::from(pos),
+                    None => continue,
                 };
                 
                 // Update or create the view model
@@ -431,30 +1412,289 @@ impl DialController {
                     let obj = QObjectPinned::new(vm);
                     self.celestial_body_models.insert(planet, obj);
                 }
+                let display_position = self.dial.lock().unwrap().get_effective_angle(body.longitude);
+                if let Some(vm) = self.celestial_body_models.get_mut(&planet) {
+                    vm.borrow_mut().update_display_position(display_position);
+                }
                 
-                // Create a QVariantMap for the celestial body
-                let mut body_map = QVariantMap::default();
-                
-                // Add the planet name as a string
-                body_map.insert("name".to_string(), planet.to_string().into());
-                
-                // Add position data
-                body_map.insert("longitude".to_string(), body.longitude.into());
-                body_map.insert("latitude".to_string(), body.latitude.into());
-                body_map.insert("distance".to_string(), body.distance.into());
-                body_map.insert("is_retrograde".to_string(), body.is_retrograde.into());
-                body_map.insert("is_dignified".to_string(), body.is_dignified.into());
-                body_map.insert("house".to_string(), body.house.map(|h| h as i32).unwrap_or(-1).into());
+                celestial_bodies.push(celestial_body_map(&planet.to_string(), &body).into());
+            }
+            
+            // Minor planets follow the planets in the list
+            for number in &self.dial_minor_bodies {
+                let position = match self.minor_bodies.get(*number).map(|body| ephem.get_minor_body_position(body)) {
+                    Some(Ok(position)) => position,
+                    _ => continue,
+                };
+                self.dial.lock().unwrap().update_minor_body_position(&position);
+                aspect_points.push(AspectPoint::from(&position));
                 
-                // Add the body map to the list
+                let mut body_map = celestial_body_map(&position.name, &CelestialBody::from(&position));
+                body_map.insert("number".to_string(), (*number).into());
                 celestial_bodies.push(body_map.into());
             }
             
-            // Update the QML property
-            self.celestial_bodies = celestial_bodies;
-            self.data_changed();
+            // Aspects between everything on the dial, closest first
+            self.aspect_list = self.aspect_calculator.find_aspects(&aspect_points);
+            let mut aspects = QVariantList::default();
+            for aspect in &self.aspect_list {
+                aspects.push(aspect_map(aspect).into());
+            }
+            self.aspects = aspects;
+            
+            // Parallels and contra-parallels between the bodies and stars
+            self.parallel_list = self.declination_calculator.find_aspects(&declination_points);
+            let mut parallels = QVariantList::default();
+            for parallel in &self.parallel_list {
+                parallels.push(parallel_map(parallel).into());
+            }
+            self.parallels = parallels;
+        }
+        
+        // Directed points of the outer ring follow the natal chart
+        let directed = match (self.direction, self.solar_arc_directions()) {
+            (Some((_, date)), Some(directions)) => directions.direct(date).ok(),
+            _ => None,
+        };
+        let mut directed_bodies = QVariantList::default();
+        self.solar_arc = directed.as_ref().map_or(0.0, |directed| directed.arc);
+        self.dial.lock().unwrap().set_directed(directed);
+        for (point, display_position) in self.dial.lock().unwrap().get_directed_positions() {
+            directed_bodies.push(ring_point_map(&point, display_position).into());
+        }
+        self.directed_bodies = directed_bodies;
+        
+        // Progressed points of their ring likewise
+        let progressed = self.progression.and_then(|(kind, mc_progression, date)| {
+            let chart = self.ephemeris.lock().unwrap().as_ref()?.chart_positions();
+            Progression::new(&chart, kind, mc_progression).ok()?.progress(date).ok()
+        });
+        self.dial.lock().unwrap().set_progressed(progressed);
+        let mut progressed_bodies = QVariantList::default();
+        for (point, display_position) in self.dial.lock().unwrap().get_progressed_positions() {
+            progressed_bodies.push(ring_point_map(&point, display_position).into());
+        }
+        self.progressed_bodies = progressed_bodies;
+        
+        // The second chart in the outermost ring, in the same house system,
+        // and its aspects to the points on the dial
+        let partner = self.partner.clone().map(|mut ephem| {
+            ephem.set_house_system(self.selected_house_system);
+            ephem.chart_positions()
+        });
+        self.dial.lock().unwrap().set_partner(partner);
+        let mut partner_bodies = QVariantList::default();
+        for (point, display_position) in self.dial.lock().unwrap().get_partner_positions() {
+            partner_bodies.push(ring_point_map(&point, display_position).into());
+        }
+        self.partner_bodies = partner_bodies;
+        
+        let mut synastry_aspects = QVariantList::default();
+        if let Some(synastry) = self.dial.lock().unwrap().synastry() {
+            for aspect in synastry.aspects(&self.aspect_calculator) {
+                synastry_aspects.push(aspect_map(&aspect).into());
+            }
+        }
+        self.synastry_aspects = synastry_aspects;
+        
+        // The chart seen from a second point in the inner ring
+        let frame_chart = self.ring_frame.and_then(|frame| {
+            let mut ephem = self.ephemeris.lock().unwrap().clone()?;
+            ephem.set_frame(frame);
+            Some(ephem.chart_positions())
+        });
+        self.dial.lock().unwrap().set_frame_chart(frame_chart);
+        let mut frame_bodies = QVariantList::default();
+        for (point, display_position) in self.dial.lock().unwrap().get_frame_chart_positions() {
+            frame_bodies.push(ring_point_map(&point, display_position).into());
+        }
+        self.frame_bodies = frame_bodies;
+        
+        // Update the QML property
+        self.celestial_bodies = celestial_bodies;
+        self.data_changed();
+    }
+}
+
+/// Create the QVariantMap describing a return: its moment, the longitude
+/// returned to, and the bodies and house cusps of the return chart
+fn planetary_return_map(planetary_return: &PlanetaryReturn) -> QVariantMap {
+    let mut return_map = QVariantMap::default();
+    return_map.insert("body".to_string(), planetary_return.body.to_string().into());
+    return_map.insert("zodiac".to_string(), planetary_return.zodiac.to_string().into());
+    return_map.insert("date".to_string(), planetary_return.moment.format("%Y-%m-%d %H:%M:%S").to_string().into());
+    return_map.insert("longitude".to_string(), planetary_return.longitude.into());
+    
+    let chart = &planetary_return.chart;
+    let mut bodies = QVariantList::default();
+    for planet in Planet::iter() {
+        if let Some(position) = chart.get_position(&planet) {
+            let mut body_map = celestial_body_map(&planet.to_string(), &CelestialBody::from(position));
+            body_map.insert("house".to_string(), position.house.map_or(0, u32::from).into());
+            bodies.push(body_map.into());
         }
     }
+    return_map.insert("bodies".to_string(), bodies.into());
+    
+    let mut cusps = QVariantList::default();
+    for cusp in chart.houses.iter().flat_map(|houses| houses.cusps) {
+        cusps.push(cusp.into());
+    }
+    return_map.insert("cusps".to_string(), cusps.into());
+    return_map.insert("location_latitude".to_string(), chart.location.0.into());
+    return_map.insert("location_longitude".to_string(), chart.location.1.into());
+    return_map
+}
+
+/// Create the QVariantMap describing a celestial body in `celestial_bodies`
+fn celestial_body_map(name: &str, body: &CelestialBody) -> QVariantMap {
+    let mut body_map = QVariantMap::default();
+    
+    // Add the body name as a string
+    body_map.insert("name".to_string(), name.to_string().into());
+    
+    // Add position data
+    body_map.insert("longitude".to_string(), body.longitude.into());
+    body_map.insert("latitude".to_string(), body.latitude.into());
+    body_map.insert("distance".to_string(), body.distance.into());
+    body_map.insert("is_retrograde".to_string(), body.is_retrograde.into());
+    body_map.insert("is_dignified".to_string(), body.is_dignified.into());
+    body_map.insert("house".to_string(), body.house.map(|h| h as i32).unwrap_or(-1).into());
+    
+    // Add observational data, the phase driving the Moon glyph
+    body_map.insert("magnitude".to_string(), body.magnitude.into());
+    body_map.insert("phase".to_string(), body.phase.into());
+    body_map.insert("elongation".to_string(), body.elongation.into());
+    body_map.insert("is_east_of_sun".to_string(), body.is_east_of_sun.into());
+    
+    body_map
+}
+
+/// Create the QVariantMap describing an aspect in `aspects`
+fn aspect_map(aspect: &Aspect) -> QVariantMap {
+    let mut aspect_map = QVariantMap::default();
+    aspect_map.insert("point1".to_string(), aspect.point1.to_string().into());
+    aspect_map.insert("point2".to_string(), aspect.point2.to_string().into());
+    aspect_map.insert("type".to_string(), aspect.kind.to_string().into());
+    aspect_map.insert("symbol".to_string(), aspect.kind.symbol().to_string().into());
+    aspect_map.insert("angle".to_string(), aspect.kind.angle().into());
+    aspect_map.insert("separation".to_string(), aspect.separation.into());
+    aspect_map.insert("orb".to_string(), aspect.orb.into());
+    aspect_map.insert("exact".to_string(), aspect.is_exact().into());
+    aspect_map.insert("applying".to_string(), aspect.is_applying().into());
+    aspect_map.insert("motion".to_string(), aspect.motion.to_string().into());
+    aspect_map
+}
+
+/// Create the QVariantMap describing a parallel or contra-parallel in `parallels`
+fn parallel_map(parallel: &DeclinationAspect) -> QVariantMap {
+    let mut parallel_map = QVariantMap::default();
+    parallel_map.insert("point1".to_string(), parallel.point1.to_string().into());
+    parallel_map.insert("point2".to_string(), parallel.point2.to_string().into());
+    parallel_map.insert("type".to_string(), parallel.kind.to_string().into());
+    parallel_map.insert("symbol".to_string(), parallel.kind.symbol().to_string().into());
+    parallel_map.insert("orb".to_string(), parallel.orb.into());
+    parallel_map.insert("exact".to_string(), parallel.is_exact().into());
+    parallel_map.insert("applying".to_string(), parallel.is_applying().into());
+    parallel_map.insert("motion".to_string(), parallel.motion.to_string().into());
+    parallel_map
+}
+
+/// Create the QVariantMap describing a midpoint, with its positions on a dial of the given modulus
+fn midpoint_map(midpoint: &Midpoint, modulus: f64) -> QVariantMap {
+    let mut mp_map = QVariantMap::default();
+    mp_map.insert("planet1".to_string(), midpoint.point1.to_string().into());
+    mp_map.insert("planet2".to_string(), midpoint.point2.to_string().into());
+    mp_map.insert("position".to_string(), midpoint.near.degrees().into());
+    mp_map.insert("far_position".to_string(), midpoint.far.degrees().into());
+    mp_map.insert("dial_position".to_string(), midpoint.dial_position(modulus).into());
+    mp_map.insert("far_dial_position".to_string(), midpoint.far_dial_position(modulus).into());
+    mp_map.insert("is_personal".to_string(), midpoint.is_personal().into());
+    mp_map
+}
+
+/// Create the QVariantMap describing a point of the directed, progressed or partner ring
+fn ring_point_map(point: &ChartPoint, display_position: f64) -> QVariantMap {
+    let mut point_map = QVariantMap::default();
+    point_map.insert("name".to_string(), point.to_string().into());
+    point_map.insert("symbol".to_string(), point.symbol().to_string().into());
+    point_map.insert("color".to_string(), point.color().to_string().into());
+    point_map.insert("display_position".to_string(), display_position.into());
+    point_map
+}
+
+/// Create the QVariantMap describing a solar arc contact
+fn direction_contact_map(contact: &DirectionContact) -> QVariantMap {
+    let mut contact_map = QVariantMap::default();
+    contact_map.insert("directed".to_string(), contact.directed.to_string().into());
+    contact_map.insert("natal".to_string(), contact.natal.to_string().into());
+    contact_map.insert("angle".to_string(), contact.angle.into());
+    contact_map.insert("aspect".to_string(), contact.aspect().map(|kind| kind.to_string()).unwrap_or_default().into());
+    contact_map.insert("arc".to_string(), contact.arc.into());
+    contact_map.insert("date".to_string(), contact.date.format("%Y-%m-%d %H:%M:%S").to_string().into());
+    contact_map
+}
+
+/// Create the QVariantMap describing a transit
+fn transit_event_map(event: &TransitEvent) -> QVariantMap {
+    let mut event_map = QVariantMap::default();
+    event_map.insert("transiting".to_string(), event.transiting.to_string().into());
+    event_map.insert("target".to_string(), event.target.to_string().into());
+    event_map.insert("date".to_string(), event.date.format("%Y-%m-%d %H:%M:%S").to_string().into());
+    event_map.insert("longitude".to_string(), event.longitude.into());
+    event_map.insert("angle".to_string(), event.angle.into());
+    event_map.insert("aspect".to_string(), event.aspect().map(|kind| kind.to_string()).unwrap_or_default().into());
+    event_map.insert("is_retrograde".to_string(), event.is_retrograde.into());
+    event_map.insert("pass".to_string(), (event.pass as i32).into());
+    event_map
+}
+
+/// Create the QVariantMap describing the horizon crossings of a body over a day
+fn rise_set_map(times: &RiseSetTimes) -> QVariantMap {
+    let mut times_map = QVariantMap::default();
+    times_map.insert("body".to_string(), times.body.to_string().into());
+    times_map.insert("visibility".to_string(), times.visibility.to_string().into());
+    
+    let mut events = QVariantList::default();
+    for event in &times.events {
+        let mut event_map = QVariantMap::default();
+        event_map.insert("type".to_string(), event.kind.to_string().into());
+        event_map.insert("date".to_string(), event.moment.format("%Y-%m-%d %H:%M:%S").to_string().into());
+        event_map.insert("altitude".to_string(), event.altitude.into());
+        event_map.insert("azimuth".to_string(), event.azimuth.into());
+        events.push(event_map.into());
+    }
+    times_map.insert("events".to_string(), events.into());
+    times_map
+}
+
+/// Parse a UTC date and time given as "YYYY-MM-DD HH:MM:SS"
+fn parse_datetime(datetime: &QString) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(&datetime.to_string(), "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|naive| naive.and_utc())
+}
+
+/// Parse a comma separated list of bodies, e.g. "saturn,uranus"
+fn parse_bodies(bodies: &QString) -> Option<Vec<Planet>> {
+    bodies.to_string()
+        .split(',')
+        .map(|name| name.trim().parse::<Planet>().ok())
+        .collect()
+}
+
+/// Create the QVariantMap describing a planetary picture
+fn planetary_picture_map(picture: &PlanetaryPicture) -> QVariantMap {
+    let mut picture_map = QVariantMap::default();
+    picture_map.insert("formula".to_string(), picture.to_string().into());
+    picture_map.insert("a".to_string(), picture.a.to_string().into());
+    picture_map.insert("b".to_string(), picture.b.to_string().into());
+    picture_map.insert("c".to_string(), picture.c.to_string().into());
+    picture_map.insert("d".to_string(), picture.d.to_string().into());
+    picture_map.insert("sensitive_point".to_string(), picture.sensitive_point.degrees().into());
+    picture_map.insert("orb".to_string(), picture.orb.into());
+    picture_map.insert("modulus".to_string(), picture.modulus.into());
+    picture_map
 }
 
 // Register the QML types
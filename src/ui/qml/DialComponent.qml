@@ -15,10 +15,28 @@ Item {
     property bool showZodiacSigns: true
     property bool showDegreeMarkers: true
     property bool showAspects: true
+    property bool showDirections: true
+    property bool showProgressions: true
+    property bool showPartner: true
+    property bool showFrameRing: true
+    property bool showMoonPhase: true
+    property bool showHouses: true
     property bool showGrid: false
     property int harmonic: 1
     property var controller: null
     
+    // Dial type: span of the zodiac around the circle and how to mark it
+    // (DialController.dial_modulus, tick_count, tick_step, label_step, sign_markers)
+    property real dialModulus: 360
+    property int tickCount: 360
+    property real tickStep: 1
+    property real labelStep: 5
+    property int signMarkers: 12
+    
+    // Tropical longitude of 0° Aries in the zodiac of the chart: the sign
+    // ring turns by the ayanamsa in a sidereal zodiac (DialController.ayanamsa)
+    property real ayanamsa: 0
+    
     // Colors
     property color backgroundColor: "#1e1e2e"
     property color foregroundColor: "#cdd6f4"
@@ -73,13 +91,14 @@ Item {
             angle: dialComponent.rotation
         }
         
-        // Zodiac signs (outer ring)
+        // Zodiac signs (outer ring), one marker every 30 dial degrees from
+        // 0° Aries of the chart's zodiac
         Repeater {
-            model: 12
+            model: signMarkers
             
             Item {
                 property int signIndex: index
-                property real angle: index * 30
+                property real angle: (index * 30 + ayanamsa % dialModulus) * 360 / dialModulus
                 property real signStart: angle - 15
                 property real signEnd: (angle + 15) % 360
                 
@@ -164,15 +183,15 @@ Item {
             }
         }
         
-        // Degree marks (inner ring)
+        // Degree marks (inner ring), in dial degrees
         Repeater {
-            model: 360
+            model: tickCount
             
             Item {
                 id: degreeMarker
-                property real degree: index
-                property bool isMajor: index % 30 === 0  // Every 30 degrees (sign cusps)
-                property bool isMinor: index % 5 === 0 && !isMajor  // Every 5 degrees
+                property real degree: index * tickStep
+                property bool isMajor: degree % 30 === 0  // Every 30 degrees (sign cusps)
+                property bool isMinor: degree % labelStep === 0 && !isMajor  // Labelled ticks
                 
                 visible: showDegreeMarkers && (isMajor || isMinor)
                 
@@ -210,7 +229,7 @@ Item {
                     Rotation {
                         origin.x: dialCircle.width / 2
                         origin.y: dialCircle.height / 2
-                        angle: degree
+                        angle: degree * 360 / dialModulus
                     },
                     Translate {
                         x: 0
@@ -353,7 +372,7 @@ Item {
                     var planets = controller.getPlanetPositions();
                     for (var i = 0; i < planets.length; i++) {
                         var planet = planets[i];
-                        var planetAngle = getHarmonicPosition(planet.longitude, dialComponent.harmonic);
+                        var planetAngle = getDialPosition(planet.longitude);
                         var angleDiff = Math.abs(planetAngle - clickAngle);
                         if (angleDiff < 5 || angleDiff > 355) { // 5° orb for selection
                             console.log(`Selected ${planet.name} at ${planet.longitude.toFixed(2)}°`);
@@ -380,39 +399,155 @@ Item {
             
             // Draw aspect lines if enabled
             if (showAspects && controller) {
-                var planets = controller.getPlanetPositions();
                 var centerX = width / 2;
                 var centerY = height / 2;
                 var radius = Math.min(width, height) * 0.4;
+                var colors = {
+                    conjunction: '#ff6b6b', sextile: '#51cf66', square: '#ff922b',
+                    trine: '#20c997', opposition: '#ff8787'
+                };
                 
-                // Draw aspect lines between planets
-                for (var i = 0; i < planets.length; i++) {
-                    for (var j = i + 1; j < planets.length; j++) {
-                        var planet1 = planets[i];
-                        var planet2 = planets[j];
-                        
-                        // Calculate angle difference (accounting for harmonic)
-                        var angle1 = getHarmonicPosition(planet1.longitude, harmonic);
-                        var angle2 = getHarmonicPosition(planet2.longitude, harmonic);
-                        var diff = Math.abs(angle1 - angle2);
-                        
-                        // Check for major aspects (0°, 60°, 90°, 120°, 180°)
-                        var aspect = null;
-                        if (Math.abs(diff % 30) < 1) { // 1° orb for aspect detection
-                            if (Math.abs(diff - 0) < 1) aspect = {name: 'Conjunction', color: '#ff6b6b'};
-                            else if (Math.abs(diff - 60) < 1) aspect = {name: 'Sextile', color: '#51cf66'};
-                            else if (Math.abs(diff - 90) < 1) aspect = {name: 'Square', color: '#ff922b'};
-                            else if (Math.abs(diff - 120) < 1) aspect = {name: 'Trine', color: '#20c997'};
-                            else if (Math.abs(diff - 180) < 1) aspect = {name: 'Opposition', color: '#ff8787'};
-                        }
+                // Longitudes of everything on the dial by name
+                var longitudes = {};
+                for (var i = 0; i < controller.celestial_bodies.length; i++) {
+                    var body = controller.celestial_bodies[i];
+                    longitudes[body.name] = body.longitude;
+                }
+                
+                // Draw the aspects found by the controller, exact ones thicker
+                for (var j = 0; j < controller.aspects.length; j++) {
+                    var aspect = controller.aspects[j];
+                    if (!(aspect.point1 in longitudes) || !(aspect.point2 in longitudes)) continue;
+                    
+                    var angle1 = getDialPosition(longitudes[aspect.point1]);
+                    var angle2 = getDialPosition(longitudes[aspect.point2]);
+                    var pos1 = polarToCartesian(centerX, centerY, radius, angle1);
+                    var pos2 = polarToCartesian(centerX, centerY, radius, angle2);
+                    drawAspectLine(ctx, pos1, pos2, colors[aspect.type] || '#adb5bd', aspect.exact ? 2 : 1);
+                }
+            }
+            
+            // House cusps inside the natal points, the angles (cusps 1, 4, 7
+            // and 10) stronger; on the smaller dials they fold like the points
+            if (showHouses && controller && controller.house_cusps.length === 12) {
+                var cuspInner = Math.min(width, height) * 0.3;
+                var cuspOuter = Math.min(width, height) * 0.4;
+                
+                for (var h = 0; h < 12; h++) {
+                    var cuspAngle = getDialPosition(controller.house_cusps[h]);
+                    var isAngle = h % 3 === 0;
+                    drawAspectLine(ctx, polarToCartesian(width / 2, height / 2, cuspInner, cuspAngle),
+                                   polarToCartesian(width / 2, height / 2, cuspOuter, cuspAngle),
+                                   isAngle ? accentColor : gridColor, isAngle ? 2 : 1);
+                    
+                    var labelAt = polarToCartesian(width / 2, height / 2, cuspInner - 10, cuspAngle);
+                    drawPlanetSymbol(ctx, labelAt.x, labelAt.y, String(h + 1), foregroundColor, 10);
+                }
+            }
+            
+            // The chart seen from a second point (heliocentric beside
+            // geocentric, say) in a ring inside the natal points
+            if (showFrameRing && controller && controller.frame_bodies.length > 0) {
+                var frameRadius = Math.min(width, height) * 0.34;
+                
+                ctx.save();
+                ctx.beginPath();
+                ctx.strokeStyle = gridColor;
+                ctx.setLineDash([2, 4]);
+                ctx.arc(width / 2, height / 2, frameRadius + 10, 0, 2 * Math.PI);
+                ctx.stroke();
+                ctx.restore();
+                
+                for (var f = 0; f < controller.frame_bodies.length; f++) {
+                    var framed = controller.frame_bodies[f];
+                    var framedAt = polarToCartesian(width / 2, height / 2, frameRadius, framed.display_position);
+                    drawPlanetSymbol(ctx, framedAt.x, framedAt.y, framed.symbol, framed.color, 12);
+                }
+            }
+            
+            // Phase of the Moon just inside its place on the dial
+            if (showMoonPhase && controller) {
+                for (var q = 0; q < controller.celestial_bodies.length; q++) {
+                    var moon = controller.celestial_bodies[q];
+                    if (moon.name !== "moon") continue;
+                    
+                    var moonAt = polarToCartesian(width / 2, height / 2, Math.min(width, height) * 0.37, getDialPosition(moon.longitude));
+                    drawMoonPhase(ctx, moonAt.x, moonAt.y, 6, moon.phase, moon.is_east_of_sun);
+                }
+            }
+            
+            // Progressed points in a ring just outside the natal ones
+            if (showProgressions && controller && controller.progressed_bodies.length > 0) {
+                var progressedRadius = Math.min(width, height) * 0.435;
+                for (var m = 0; m < controller.progressed_bodies.length; m++) {
+                    var progressed = controller.progressed_bodies[m];
+                    var at = polarToCartesian(width / 2, height / 2, progressedRadius, progressed.display_position);
+                    drawPlanetSymbol(ctx, at.x, at.y, progressed.symbol, progressed.color, 12);
+                }
+            }
+            
+            // Directed points in a ring outside the natal ones
+            if (showDirections && controller && controller.directed_bodies.length > 0) {
+                var ringX = width / 2;
+                var ringY = height / 2;
+                var ringRadius = Math.min(width, height) * 0.47;
+                
+                ctx.save();
+                ctx.beginPath();
+                ctx.strokeStyle = gridColor;
+                ctx.setLineDash([4, 4]);
+                ctx.arc(ringX, ringY, ringRadius, 0, 2 * Math.PI);
+                ctx.stroke();
+                ctx.restore();
+                
+                for (var k = 0; k < controller.directed_bodies.length; k++) {
+                    var directed = controller.directed_bodies[k];
+                    var pos = polarToCartesian(ringX, ringY, ringRadius, directed.display_position);
+                    drawPlanetSymbol(ctx, pos.x, pos.y, directed.symbol, directed.color, 14);
+                }
+            }
+            
+            // Second chart of a synastry in the outermost ring, with dashed
+            // lines for its aspects to the points on the dial
+            if (showPartner && controller && controller.partner_bodies.length > 0) {
+                var partnerX = width / 2;
+                var partnerY = height / 2;
+                var partnerRadius = Math.min(width, height) * 0.49;
+                var innerRadius = Math.min(width, height) * 0.4;
+                
+                ctx.save();
+                ctx.beginPath();
+                ctx.strokeStyle = gridColor;
+                ctx.arc(partnerX, partnerY, partnerRadius - 10, 0, 2 * Math.PI);
+                ctx.stroke();
+                ctx.restore();
+                
+                var partnerPositions = {};
+                for (var n = 0; n < controller.partner_bodies.length; n++) {
+                    var partner = controller.partner_bodies[n];
+                    partnerPositions[partner.name] = partner.display_position;
+                    var partnerAt = polarToCartesian(partnerX, partnerY, partnerRadius, partner.display_position);
+                    drawPlanetSymbol(ctx, partnerAt.x, partnerAt.y, partner.symbol, partner.color, 14);
+                }
+                
+                if (showAspects) {
+                    var natalPositions = {};
+                    for (var b = 0; b < controller.celestial_bodies.length; b++) {
+                        var natal = controller.celestial_bodies[b];
+                        natalPositions[natal.name] = getDialPosition(natal.longitude);
+                    }
+                    
+                    ctx.save();
+                    ctx.setLineDash([3, 3]);
+                    for (var c = 0; c < controller.synastry_aspects.length; c++) {
+                        var crossAspect = controller.synastry_aspects[c];
+                        if (!(crossAspect.point1 in natalPositions) || !(crossAspect.point2 in partnerPositions)) continue;
                         
-                        // Draw aspect line if found
-                        if (aspect) {
-                            var pos1 = polarToCartesian(centerX, centerY, radius, angle1);
-                            var pos2 = polarToCartesian(centerX, centerY, radius, angle2);
-                            drawAspectLine(ctx, pos1, pos2, aspect.color, 1);
-                        }
+                        var from = polarToCartesian(partnerX, partnerY, innerRadius, natalPositions[crossAspect.point1]);
+                        var to = polarToCartesian(partnerX, partnerY, innerRadius, partnerPositions[crossAspect.point2]);
+                        drawAspectLine(ctx, from, to, colors[crossAspect.type] || '#adb5bd', crossAspect.exact ? 2 : 1);
                     }
+                    ctx.restore();
                 }
             }
         }
@@ -463,11 +598,11 @@ Item {
         );
     }
     
-    // Function to calculate harmonic position
-    function getHarmonicPosition(degree, harmonic) {
-        if (harmonic <= 1) return degree;
-        var harmonicRange = 360 / harmonic;
-        return (degree % harmonicRange) * harmonic;
+    // Function to calculate the angle on the drawn circle: the harmonic chart
+    // first, then the dial type (as UranianDial::get_effective_angle)
+    function getDialPosition(degree) {
+        var harmonicDegree = (degree * harmonic) % 360;
+        return (harmonicDegree % dialModulus) * 360 / dialModulus;
     }
     
     // Function to draw aspect lines
@@ -493,6 +628,37 @@ Item {
         ctx.restore();
     }
     
+    // Function to draw the Moon lit by the given fraction, on the right
+    // while waxing (east of the Sun) and on the left while waning
+    function drawMoonPhase(ctx, x, y, radius, phase, waxing) {
+        ctx.save();
+        ctx.translate(x, y);
+        if (!waxing) ctx.scale(-1, 1);
+        
+        // Dark disc, then the lit half
+        ctx.beginPath();
+        ctx.arc(0, 0, radius, 0, 2 * Math.PI);
+        ctx.fillStyle = backgroundColor;
+        ctx.fill();
+        ctx.strokeStyle = foregroundColor;
+        ctx.lineWidth = 1;
+        ctx.stroke();
+        
+        ctx.beginPath();
+        ctx.arc(0, 0, radius, -Math.PI / 2, Math.PI / 2);
+        ctx.closePath();
+        ctx.fillStyle = foregroundColor;
+        ctx.fill();
+        
+        // The terminator: an ellipse darkening the crescent or lighting the gibbous Moon
+        var terminator = Math.abs(1 - 2 * phase) * radius;
+        ctx.beginPath();
+        ctx.ellipse(-terminator, -radius, 2 * terminator, 2 * radius);
+        ctx.fillStyle = phase < 0.5 ? backgroundColor : foregroundColor;
+        ctx.fill();
+        ctx.restore();
+    }
+    
     // Context menu for dial actions
     Menu {
         id: contextMenu
@@ -521,6 +687,48 @@ Item {
             onTriggered: showAspects = !showAspects
         }
         
+        MenuItem {
+            text: "Show Directions"
+            checkable: true
+            checked: showDirections
+            onTriggered: showDirections = !showDirections
+        }
+        
+        MenuItem {
+            text: "Show Progressions"
+            checkable: true
+            checked: showProgressions
+            onTriggered: showProgressions = !showProgressions
+        }
+        
+        MenuItem {
+            text: "Show Partner Chart"
+            checkable: true
+            checked: showPartner
+            onTriggered: showPartner = !showPartner
+        }
+        
+        MenuItem {
+            text: "Show Second Frame"
+            checkable: true
+            checked: showFrameRing
+            onTriggered: showFrameRing = !showFrameRing
+        }
+        
+        MenuItem {
+            text: "Show Houses"
+            checkable: true
+            checked: showHouses
+            onTriggered: showHouses = !showHouses
+        }
+        
+        MenuItem {
+            text: "Show Moon Phase"
+            checkable: true
+            checked: showMoonPhase
+            onTriggered: showMoonPhase = !showMoonPhase
+        }
+        
         MenuItem {
             text: "Show Degree Markers"
             checkable: true
@@ -538,6 +746,12 @@ Item {
     
     // Update canvas when needed
     onShowAspectsChanged: aspectCanvas.requestPaint()
+    onShowDirectionsChanged: aspectCanvas.requestPaint()
+    onShowProgressionsChanged: aspectCanvas.requestPaint()
+    onShowPartnerChanged: aspectCanvas.requestPaint()
+    onShowFrameRingChanged: aspectCanvas.requestPaint()
+    onShowMoonPhaseChanged: aspectCanvas.requestPaint()
+    onShowHousesChanged: aspectCanvas.requestPaint()
     onHarmonicChanged: aspectCanvas.requestPaint()
     
     // Function to update the view
@@ -0,0 +1,116 @@
+import QtQuick 2.15
+import QtQuick.Controls 2.15
+
+Item {
+    id: graphicEphemeris
+    width: 600
+    height: 300
+
+    // Properties exposed to the outside
+    property var controller: null
+    property string bodies: "sun,mars,jupiter,saturn,uranus,neptune,pluto"
+    property string from: ""
+    property string to: ""
+    property real modulus: 45
+
+    // Graph from DialController.get_graphic_ephemeris
+    property var graph: ({})
+
+    // Colors
+    property color backgroundColor: "#1e1e2e"
+    property color foregroundColor: "#cdd6f4"
+    property color gridColor: "#313244"
+    property real margin: 30
+
+    Rectangle {
+        anchors.fill: parent
+        color: backgroundColor
+    }
+
+    Canvas {
+        id: graphCanvas
+        anchors.fill: parent
+        antialiasing: true
+
+        onPaint: {
+            var ctx = getContext('2d');
+            ctx.clearRect(0, 0, width, height);
+            if (!graph || !graph.curves) return;
+
+            var plotWidth = width - 2 * margin;
+            var plotHeight = height - 2 * margin;
+            var span = Math.max(graph.span_days, 1e-9);
+            function x(days) { return margin + days / span * plotWidth; }
+            function y(position) { return margin + (1 - position / graph.modulus) * plotHeight; }
+
+            // Degree grid, every 5° (30° above the 90° dial)
+            var labelStep = graph.modulus > 90 ? 30 : 5;
+            ctx.font = '10px sans-serif';
+            ctx.fillStyle = foregroundColor;
+            ctx.strokeStyle = gridColor;
+            ctx.lineWidth = 1;
+            for (var degree = 0; degree <= graph.modulus + 1e-9; degree += labelStep) {
+                ctx.beginPath();
+                ctx.moveTo(margin, y(degree));
+                ctx.lineTo(width - margin, y(degree));
+                ctx.stroke();
+                ctx.textAlign = 'right';
+                ctx.fillText(degree + '°', margin - 4, y(degree) + 3);
+            }
+
+            // Natal positions as dashed lines
+            ctx.save();
+            ctx.setLineDash([4, 3]);
+            for (var i = 0; i < graph.natal.length; i++) {
+                var line = graph.natal[i];
+                ctx.strokeStyle = line.color;
+                ctx.beginPath();
+                ctx.moveTo(margin, y(line.position));
+                ctx.lineTo(width - margin, y(line.position));
+                ctx.stroke();
+                ctx.fillStyle = line.color;
+                ctx.textAlign = 'left';
+                ctx.fillText(line.symbol, width - margin + 4, y(line.position) + 3);
+            }
+            ctx.restore();
+
+            // Transiting bodies, one polyline per segment
+            ctx.lineWidth = 1.5;
+            for (var j = 0; j < graph.curves.length; j++) {
+                var curve = graph.curves[j];
+                ctx.strokeStyle = curve.color;
+                for (var k = 0; k < curve.segments.length; k++) {
+                    var segment = curve.segments[k];
+                    if (segment.length < 2) continue;
+                    ctx.beginPath();
+                    ctx.moveTo(x(segment[0].days), y(segment[0].position));
+                    for (var p = 1; p < segment.length; p++) {
+                        ctx.lineTo(x(segment[p].days), y(segment[p].position));
+                    }
+                    ctx.stroke();
+                }
+                if (curve.segments.length > 0 && curve.segments[0].length > 0) {
+                    var first = curve.segments[0][0];
+                    ctx.fillStyle = curve.color;
+                    ctx.textAlign = 'left';
+                    ctx.fillText(curve.symbol, x(first.days) + 2, y(first.position) - 3);
+                }
+            }
+        }
+    }
+
+    // Fetch the graph from the controller and redraw it
+    function refresh() {
+        if (controller && from !== "" && to !== "") {
+            graph = controller.get_graphic_ephemeris(bodies, from, to, modulus);
+        }
+    }
+
+    onGraphChanged: graphCanvas.requestPaint()
+    onWidthChanged: graphCanvas.requestPaint()
+    onHeightChanged: graphCanvas.requestPaint()
+    onModulusChanged: refresh()
+    onFromChanged: refresh()
+    onToChanged: refresh()
+    onBodiesChanged: refresh()
+}
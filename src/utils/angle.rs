@@ -63,6 +63,16 @@ impl Angle {
         Angle::from_degrees((self.0 + 180.0) % 360.0)
     }
     
+    /// Get the antiscion, the reflection across the Cancer/Capricorn axis
+    pub fn antiscion(&self) -> Angle {
+        Angle::from_degrees(180.0 - self.0)
+    }
+    
+    /// Get the contra-antiscion, the reflection across the Aries/Libra axis
+    pub fn contra_antiscion(&self) -> Angle {
+        Angle::from_degrees(360.0 - self.0)
+    }
+    
     /// Calculate the midpoint between two angles
     pub fn midpoint(&self, other: Angle) -> Angle {
         let diff = (other.0 - self.0).abs();
@@ -79,6 +89,12 @@ impl Angle {
         crate::astrology::models::zodiac::ZodiacSign::from_degree(self.0)
     }
     
+    /// Get the zodiac sign this angle falls in, in a zodiac starting
+    /// `ayanamsa` degrees after the vernal equinox
+    pub fn zodiac_sign_in(&self, ayanamsa: f64) -> (crate::astrology::models::zodiac::ZodiacSign, f64) {
+        crate::astrology::models::zodiac::ZodiacSign::from_degree_in(self.0, ayanamsa)
+    }
+    
     /// Format the angle as degrees, minutes, and seconds
     pub fn to_dms(&self) -> (i32, u32, f64) {
         let degrees = self.0 as i32;
@@ -91,8 +107,14 @@ impl Angle {
     
     /// Format the angle as a string in DMS format (e.g., "15°27'43.2\" Aries")
     pub fn to_dms_string(&self) -> String {
-        let (degrees, minutes, seconds) = self.to_dms();
-        let (sign, _) = self.zodiac_sign();
+        self.to_dms_string_in(0.0)
+    }
+    
+    /// Format the angle as a string in DMS format within its sign, in a zodiac
+    /// starting `ayanamsa` degrees after the vernal equinox
+    pub fn to_dms_string_in(&self, ayanamsa: f64) -> String {
+        let (sign, degree) = self.zodiac_sign_in(ayanamsa);
+        let (degrees, minutes, seconds) = Angle(degree).to_dms();
         
         format!(
             "{}°{:02}'{:04.1}\" {}",
@@ -250,6 +272,18 @@ mod tests {
         assert!(approx_eq!(f64, a.midpoint(b).degrees(), 0.0, epsilon = 0.0001));
     }
 
+    #[test]
+    fn test_angle_antiscia() {
+        // 10° Gemini mirrors to 20° Cancer and, across Aries, to 20° Capricorn
+        let angle = Angle::from_degrees(70.0);
+        assert!(approx_eq!(f64, angle.antiscion().degrees(), 110.0, epsilon = 0.0001));
+        assert!(approx_eq!(f64, angle.contra_antiscion().degrees(), 290.0, epsilon = 0.0001));
+        assert!(approx_eq!(f64, angle.antiscion().opposite().degrees(), angle.contra_antiscion().degrees(), epsilon = 0.0001));
+        
+        let angle = Angle::from_degrees(200.0);
+        assert!(approx_eq!(f64, angle.antiscion().degrees(), 340.0, epsilon = 0.0001));
+    }
+
     #[test]
     fn test_angle_zodiac() {
         let angle = Angle::from_degrees(0.0);
@@ -263,5 +297,13 @@ mod tests {
         let angle = Angle::from_degrees(359.9);
         let (sign, _) = angle.zodiac_sign();
         assert_eq!(sign, ZodiacSign::Pisces);
+        
+        // Sidereally, with an ayanamsa of 24°, 10° Taurus is 16° Aries
+        let angle = Angle::from_degrees(40.0);
+        let (sign, degree) = angle.zodiac_sign_in(24.0);
+        assert_eq!(sign, ZodiacSign::Aries);
+        assert!(approx_eq!(f64, degree, 16.0, epsilon = 1e-9));
+        assert_eq!(Angle::from_degrees(45.5).to_dms_string(), "15°30'00.0\" Taurus");
+        assert_eq!(angle.to_dms_string_in(24.0), "16°00'00.0\" Aries");
     }
 }